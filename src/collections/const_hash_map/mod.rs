@@ -1,67 +1,42 @@
 use core::borrow::Borrow;
-use core::cell::UnsafeCell;
 use core::fmt;
 use core::fmt::Debug;
 use core::hash::Hash;
 use core::iter::FromIterator;
 use core::ops::Index;
-use core::sync::atomic::{AtomicBool, Ordering};
 use hashbrown::hash_map::HashMap as GBHashMap;
 use hashbrown::{hash_map, TryReserveError};
 #[cfg(feature = "with_serde")]
 use serde::ser::SerializeMap;
 #[cfg(feature = "with_serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use spin::Once;
 
+/// A `HashMap` that can be created in a `const` context, e.g. as a `static`.
+///
+/// The underlying map is allocated lazily on first access. The initialization runs exactly once,
+/// concurrent readers wait for it to complete before they get access to the map.
 pub struct HashMap<K, V> {
-    locked: AtomicBool,
-    data: UnsafeCell<Option<GBHashMap<K, V>>>,
+    data: Once<GBHashMap<K, V>>,
 }
 impl<K, V> HashMap<K, V> {
     pub const fn new() -> HashMap<K, V> {
-        Self {
-            locked: AtomicBool::new(false),
-            data: UnsafeCell::new(None),
-        }
-    }
-
-    fn lock(&self) -> bool {
-        while self.locked.swap(true, Ordering::Acquire) {
-            std::hint::spin_loop();
-        }
-        true
-    }
-
-    fn unlock(&self) {
-        self.locked.store(false, Ordering::Release);
-    }
-
-    fn ensure_initialized(&self) {
-        if self.lock() {
-            unsafe {
-                if (*self.data.get()).is_none() {
-                    *self.data.get() = Some(GBHashMap::new());
-                }
-            }
-            self.unlock();
-        }
+        Self { data: Once::new() }
     }
 
     fn as_inner(&self) -> &GBHashMap<K, V> {
-        self.ensure_initialized();
-        let inner = unsafe { self.data.get().as_ref().unwrap().as_ref().unwrap() };
-        inner
+        self.data.call_once(GBHashMap::new)
     }
 
     fn as_inner_mut(&mut self) -> &mut GBHashMap<K, V> {
-        self.ensure_initialized();
-        let inner = self.data.get_mut().as_mut().unwrap();
-        inner
+        self.data.call_once(GBHashMap::new);
+        self.data
+            .get_mut()
+            .expect("map is initialized by call_once")
     }
 
     fn into_inner(self) -> GBHashMap<K, V> {
-        self.ensure_initialized();
-        unsafe { (*self.data.get()).take().unwrap() }
+        self.data.try_into_inner().unwrap_or_default()
     }
 }
 
@@ -259,34 +234,19 @@ impl<K, V> HashMap<K, V> {
     }
 }
 
-unsafe impl<K, V> Sync for HashMap<K, V>
-where
-    K: Sync,
-    V: Sync,
-{
-}
-
 impl<K, V> Clone for HashMap<K, V>
 where
     K: Clone,
     V: Clone,
 {
     fn clone(&self) -> Self {
-        self.ensure_initialized();
         Self {
-            locked: AtomicBool::new(false),
-            data: UnsafeCell::new(Some(self.as_inner().clone())),
+            data: Once::initialized(self.as_inner().clone()),
         }
     }
 
     fn clone_from(&mut self, other: &Self) {
-        self.ensure_initialized();
-        unsafe {
-            (*self.data.get())
-                .as_mut()
-                .unwrap()
-                .clone_from(&other.as_inner());
-        }
+        self.as_inner_mut().clone_from(other.as_inner());
     }
 }
 
@@ -359,8 +319,7 @@ where
     {
         let hb_hash_map = GBHashMap::deserialize(deserializer)?;
         Ok(HashMap {
-            locked: AtomicBool::new(false),
-            data: UnsafeCell::new(Some(hb_hash_map)),
+            data: Once::initialized(hb_hash_map),
         })
     }
 }
@@ -415,3 +374,59 @@ impl<'a, K, V> IntoIterator for &'a mut HashMap<K, V> {
         self.as_inner_mut().iter_mut()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    static MAP: HashMap<u32, u32> = HashMap::new();
+
+    #[test]
+    fn test_static_map() {
+        assert!(MAP.is_empty());
+        assert_eq!(MAP.get(&1), None);
+        assert_eq!(MAP.capacity(), 0);
+    }
+
+    #[test]
+    fn test_concurrent_first_access() {
+        for _ in 0..100 {
+            let map = Arc::new(HashMap::<u32, u32>::new());
+            let barrier = Arc::new(Barrier::new(8));
+            let handles = (0..8)
+                .map(|_| {
+                    let map = map.clone();
+                    let barrier = barrier.clone();
+                    thread::spawn(move || {
+                        barrier.wait();
+                        let inner = map.as_inner() as *const GBHashMap<u32, u32>;
+                        assert!(map.is_empty());
+                        assert_eq!(map.get(&1), None);
+                        inner as usize
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let inners = handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>();
+            assert!(inners.iter().all(|inner| *inner == inners[0]));
+        }
+    }
+
+    #[test]
+    fn test_mutation_after_init() {
+        let mut map = HashMap::new();
+        map.insert("a", 1);
+        let cloned = map.clone();
+        map.insert("b", 2);
+
+        assert_eq!(cloned.len(), 1);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.into_iter().count(), 2);
+        assert_eq!(HashMap::<u8, u8>::new().into_iter().count(), 0);
+    }
+}
@@ -2,6 +2,8 @@
 pub mod const_hash_map;
 #[cfg(feature = "collections-hash-map")]
 pub mod hash_map;
+#[cfg(feature = "collections-ordered-map")]
+pub mod ordered_map;
 #[cfg(feature = "collections-simple-linked-list")]
 pub mod simple_linked_list;
 #[cfg(feature = "collections-vec-deque")]
@@ -11,6 +13,8 @@ pub mod vec_deque;
 pub use const_hash_map::HashMap as ConstHashMap;
 #[cfg(feature = "collections-hash-map")]
 pub use hash_map::HashMap;
+#[cfg(feature = "collections-ordered-map")]
+pub use ordered_map::OrderedMap;
 #[cfg(feature = "collections-simple-linked-list")]
 pub use simple_linked_list::SimpleLinkedList;
 #[cfg(feature = "collections-vec-deque")]
@@ -20,6 +24,7 @@ pub use vec_deque::VecDeque;
     feature = "collections-vec-deque",
     feature = "collections-simple-linked-list",
     feature = "collections-hash-map",
-    feature = "collections-const-hash-map"
+    feature = "collections-const-hash-map",
+    feature = "collections-ordered-map"
 )))]
 pub struct Empty {}
@@ -0,0 +1,636 @@
+use alloc::vec::{self, Vec};
+use core::borrow::Borrow;
+use core::fmt;
+use core::fmt::Debug;
+use core::hash::{BuildHasher, Hash};
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+use core::ops::Index;
+use core::slice;
+#[cfg(feature = "with_serde")]
+use crate_serde::de::{MapAccess, Visitor};
+#[cfg(feature = "with_serde")]
+use crate_serde::ser::SerializeMap;
+#[cfg(feature = "with_serde")]
+use crate_serde::{Deserialize, Deserializer, Serialize, Serializer};
+use hashbrown::hash_map::DefaultHashBuilder;
+use hashbrown::HashTable;
+
+struct Bucket<K, V> {
+    hash: u64,
+    key: K,
+    value: V,
+}
+
+impl<K: Clone, V: Clone> Clone for Bucket<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            hash: self.hash,
+            key: self.key.clone(),
+            value: self.value.clone(),
+        }
+    }
+}
+
+/// A map that keeps its entries in insertion order.
+///
+/// Lookups are O(1) through a hash table holding the positions of the entries, iteration yields
+/// the entries in the order they were first inserted. Updating the value of an existing key keeps
+/// its position, removing an entry shifts all following entries and is O(n).
+pub struct OrderedMap<K, V> {
+    hash_builder: DefaultHashBuilder,
+    indices: HashTable<usize>,
+    entries: Vec<Bucket<K, V>>,
+}
+
+impl<K, V> OrderedMap<K, V> {
+    pub fn new() -> OrderedMap<K, V> {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> OrderedMap<K, V> {
+        Self {
+            hash_builder: DefaultHashBuilder::default(),
+            indices: HashTable::with_capacity(capacity),
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.entries.capacity()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys(self.entries.iter())
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values(self.entries.iter())
+    }
+
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut(self.entries.iter_mut())
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter(self.entries.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut(self.entries.iter_mut())
+    }
+
+    /// Returns the entry at the given position in insertion order.
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        self.entries
+            .get(index)
+            .map(|bucket| (&bucket.key, &bucket.value))
+    }
+
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.get_index(0)
+    }
+
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.entries
+            .last()
+            .map(|bucket| (&bucket.key, &bucket.value))
+    }
+
+    pub fn clear(&mut self) {
+        self.indices.clear();
+        self.entries.clear();
+    }
+}
+
+impl<K, V> OrderedMap<K, V>
+where
+    K: Eq + Hash,
+{
+    fn hash<Q: ?Sized + Hash>(&self, key: &Q) -> u64 {
+        self.hash_builder.hash_one(key)
+    }
+
+    /// Returns the position of `key` in insertion order.
+    pub fn get_index_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        let entries = &self.entries;
+        self.indices
+            .find(self.hash(key), |&index| entries[index].key.borrow() == key)
+            .copied()
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.get_index_of(key)
+            .map(|index| &self.entries[index].value)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.get_index_of(key)
+            .and_then(|index| self.get_index(index))
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.get_index_of(key)
+            .map(|index| &mut self.entries[index].value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.get_index_of(key).is_some()
+    }
+
+    /// Inserts a key-value pair. If the key already exists its value is replaced in place and the
+    /// old value is returned, otherwise the entry is appended.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(index) = self.get_index_of(&key) {
+            return Some(core::mem::replace(&mut self.entries[index].value, value));
+        }
+
+        let hash = self.hash(&key);
+        let entries = &self.entries;
+        self.indices
+            .insert_unique(hash, entries.len(), |&index| entries[index].hash);
+        self.entries.push(Bucket { hash, key, value });
+        None
+    }
+
+    pub fn get_or_insert_with<F>(&mut self, key: K, default: F) -> &mut V
+    where
+        F: FnOnce() -> V,
+    {
+        let index = match self.get_index_of(&key) {
+            Some(index) => index,
+            None => {
+                self.insert(key, default());
+                self.entries.len() - 1
+            }
+        };
+        &mut self.entries[index].value
+    }
+
+    pub fn get_or_default(&mut self, key: K) -> &mut V
+    where
+        V: Default,
+    {
+        self.get_or_insert_with(key, V::default)
+    }
+
+    /// Removes `key` from the map, preserving the order of the remaining entries.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes `key` from the map, preserving the order of the remaining entries.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        let hash = self.hash(key);
+        let entries = &self.entries;
+        let index = self
+            .indices
+            .find_entry(hash, |&index| entries[index].key.borrow() == key)
+            .ok()?
+            .remove()
+            .0;
+
+        for position in self.indices.iter_mut() {
+            if *position > index {
+                *position -= 1;
+            }
+        }
+        let bucket = self.entries.remove(index);
+        Some((bucket.key, bucket.value))
+    }
+
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.entries
+            .retain_mut(|bucket| f(&bucket.key, &mut bucket.value));
+        self.rebuild_indices();
+    }
+
+    fn rebuild_indices(&mut self) {
+        self.indices.clear();
+        let entries = &self.entries;
+        for (index, bucket) in entries.iter().enumerate() {
+            self.indices
+                .insert_unique(bucket.hash, index, |&index| entries[index].hash);
+        }
+    }
+}
+
+impl<K, V> Clone for OrderedMap<K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            hash_builder: self.hash_builder.clone(),
+            indices: self.indices.clone(),
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<K, V> PartialEq for OrderedMap<K, V>
+where
+    K: Eq + Hash,
+    V: PartialEq,
+{
+    fn eq(&self, other: &OrderedMap<K, V>) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        self.iter()
+            .all(|(key, value)| other.get(key).is_some_and(|v| *value == *v))
+    }
+}
+
+impl<K, V> Eq for OrderedMap<K, V>
+where
+    K: Eq + Hash,
+    V: Eq,
+{
+}
+
+impl<K, V> Debug for OrderedMap<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, Q: ?Sized, V> Index<&Q> for OrderedMap<K, V>
+where
+    K: Eq + Hash + Borrow<Q>,
+    Q: Eq + Hash,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+// Implement Serialize
+#[cfg(feature = "with_serde")]
+impl<K, V> Serialize for OrderedMap<K, V>
+where
+    K: Serialize,
+    V: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (k, v) in self {
+            map.serialize_entry(k, v)?;
+        }
+        map.end()
+    }
+}
+
+// Implement Deserialize
+#[cfg(feature = "with_serde")]
+impl<'de, K, V> Deserialize<'de> for OrderedMap<K, V>
+where
+    K: Deserialize<'de> + Eq + Hash,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(OrderedMapVisitor(PhantomData))
+    }
+}
+
+/// The maximum number of entries preallocated when deserializing, larger maps grow as usual.
+#[cfg(feature = "with_serde")]
+const MAX_PREALLOCATED_ENTRIES: usize = 4096;
+
+#[cfg(feature = "with_serde")]
+struct OrderedMapVisitor<K, V>(PhantomData<(K, V)>);
+
+#[cfg(feature = "with_serde")]
+impl<'de, K, V> Visitor<'de> for OrderedMapVisitor<K, V>
+where
+    K: Deserialize<'de> + Eq + Hash,
+    V: Deserialize<'de>,
+{
+    type Value = OrderedMap<K, V>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a map")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        // The size hint comes from the input, so it is capped to not preallocate arbitrary
+        // amounts of memory for a malicious document.
+        let capacity = access
+            .size_hint()
+            .unwrap_or(0)
+            .min(MAX_PREALLOCATED_ENTRIES);
+        let mut map = OrderedMap::with_capacity(capacity);
+        while let Some((key, value)) = access.next_entry()? {
+            map.insert(key, value);
+        }
+        Ok(map)
+    }
+}
+
+impl<K, V> Default for OrderedMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Implement FromIterator
+impl<K, V> FromIterator<(K, V)> for OrderedMap<K, V>
+where
+    K: Eq + Hash,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (k, v) in iter {
+            map.insert(k, v);
+        }
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for OrderedMap<K, V>
+where
+    K: Eq + Hash,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+// Implement IntoIterator for OrderedMap
+impl<K, V> IntoIterator for OrderedMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self.entries.into_iter())
+    }
+}
+
+// Implement IntoIterator for &OrderedMap
+impl<'a, K, V> IntoIterator for &'a OrderedMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Implement IntoIterator for &mut OrderedMap
+impl<'a, K, V> IntoIterator for &'a mut OrderedMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over the entries of an `OrderedMap` in insertion order.
+pub struct Iter<'a, K, V>(slice::Iter<'a, Bucket<K, V>>);
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|bucket| (&bucket.key, &bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0
+            .next_back()
+            .map(|bucket| (&bucket.key, &bucket.value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+/// A mutable iterator over the entries of an `OrderedMap` in insertion order.
+pub struct IterMut<'a, K, V>(slice::IterMut<'a, Bucket<K, V>>);
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|bucket| (&bucket.key, &mut bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+/// An owning iterator over the entries of an `OrderedMap` in insertion order.
+pub struct IntoIter<K, V>(vec::IntoIter<Bucket<K, V>>);
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|bucket| (bucket.key, bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+/// An iterator over the keys of an `OrderedMap` in insertion order.
+pub struct Keys<'a, K, V>(slice::Iter<'a, Bucket<K, V>>);
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|bucket| &bucket.key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+
+/// An iterator over the values of an `OrderedMap` in insertion order.
+pub struct Values<'a, K, V>(slice::Iter<'a, Bucket<K, V>>);
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|bucket| &bucket.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
+
+/// A mutable iterator over the values of an `OrderedMap` in insertion order.
+pub struct ValuesMut<'a, K, V>(slice::IterMut<'a, Bucket<K, V>>);
+
+impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|bucket| &mut bucket.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for ValuesMut<'_, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insertion_order() {
+        let mut map = OrderedMap::new();
+        for key in ["zeta", "alpha", "mu", "beta"] {
+            map.insert(key, key.len());
+        }
+        assert_eq!(
+            map.keys().copied().collect::<Vec<_>>(),
+            vec!["zeta", "alpha", "mu", "beta"]
+        );
+
+        // Updating keeps the position, removing shifts the following entries.
+        assert_eq!(map.insert("alpha", 10), Some(5));
+        assert_eq!(map.remove("zeta"), Some(4));
+        map.insert("zeta", 1);
+        assert_eq!(
+            map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+            vec![("alpha", 10), ("mu", 2), ("beta", 4), ("zeta", 1)]
+        );
+        assert_eq!(map.get("mu"), Some(&2));
+        assert_eq!(map.get_index_of("zeta"), Some(3));
+        assert_eq!(map["beta"], 4);
+
+        map.retain(|key, _| *key != "mu");
+        assert_eq!(map.get_index_of("beta"), Some(1));
+        assert_eq!(
+            map.into_iter().map(|(k, _)| k).collect::<Vec<_>>(),
+            vec!["alpha", "beta", "zeta"]
+        );
+    }
+
+    #[test]
+    fn test_many_entries() {
+        let mut map = (0..1000).map(|i| (i, i * 2)).collect::<OrderedMap<_, _>>();
+        for i in (0..1000).step_by(3) {
+            assert_eq!(map.remove(&i), Some(i * 2));
+        }
+        let expected = (0..1000).filter(|i| i % 3 != 0).collect::<Vec<_>>();
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), expected);
+        assert!(expected.iter().all(|i| map.get(i) == Some(&(i * 2))));
+    }
+
+    #[cfg(all(feature = "with_serde", feature = "with_json"))]
+    #[test]
+    fn test_serde_round_trip() {
+        let json = r#"{"name":"gearbox","version":"1.0","authors":["nebula"],"edition":"2021"}"#;
+        let map: OrderedMap<String, serde_json::Value> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            map.keys().map(|k| k.as_str()).collect::<Vec<_>>(),
+            vec!["name", "version", "authors", "edition"]
+        );
+        assert_eq!(serde_json::to_string(&map).unwrap(), json);
+    }
+    #[cfg(feature = "with_serde")]
+    #[test]
+    fn test_deserialize_untrusted_size_hint() {
+        use serde::de::value::{Error, MapDeserializer};
+
+        /// Claims far more entries than it yields.
+        struct Lying(core::ops::Range<u32>);
+
+        impl Iterator for Lying {
+            type Item = (u32, u32);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.0.next().map(|t| (t, t))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (usize::MAX, Some(usize::MAX))
+            }
+        }
+
+        let map =
+            OrderedMap::<u32, u32>::deserialize(MapDeserializer::<_, Error>::new(Lying(0..3)))
+                .unwrap();
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+}
@@ -1,6 +1,7 @@
 use serde_derive::{Deserialize, Serialize};
 use serde_json::value::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::{PoisonError, RwLock};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SLD {
@@ -10,14 +11,83 @@ pub struct SLD {
     document: Value,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// Loads the remote documents that `Context::Reference` entries point to.
+///
+/// Implementations are free to fetch documents over HTTP, read them from disk or serve them from
+/// memory. `HashMap<String, Value>` implements this trait, which makes it easy to pin well known
+/// contexts or to stub them in tests.
+pub trait ContextLoader {
+    /// Returns the remote document for `iri`. The context itself is expected under `@context`.
+    fn load(&self, iri: &str) -> Result<Value, String>;
+}
+
+impl ContextLoader for HashMap<String, Value> {
+    fn load(&self, iri: &str) -> Result<Value, String> {
+        self.get(iri)
+            .cloned()
+            .ok_or_else(|| format!("Failed to load context '{}': not found", iri))
+    }
+}
+
+/// A `ContextLoader` that remembers the documents loaded through `L`.
+///
+/// Every IRI is loaded at most once for all documents resolved with the same `CachingLoader`.
+/// Failed loads are not cached, so they are retried on the next resolution. The cache only holds
+/// complete documents, so it stays usable after a panic while the lock was held.
+pub struct CachingLoader<L: ContextLoader> {
+    inner: L,
+    cache: RwLock<HashMap<String, Value>>,
+}
+
+impl<L: ContextLoader> CachingLoader<L> {
+    pub fn new(inner: L) -> Self {
+        Self {
+            inner,
+            cache: RwLock::default(),
+        }
+    }
+
+    /// Returns the wrapped loader.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+
+    /// Drops all cached documents.
+    pub fn clear(&self) {
+        self.cache
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+impl<L: ContextLoader> ContextLoader for CachingLoader<L> {
+    fn load(&self, iri: &str) -> Result<Value, String> {
+        if let Some(document) = self
+            .cache
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(iri)
+        {
+            return Ok(document.clone());
+        }
+        let document = self.inner.load(iri)?;
+        self.cache
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(iri.to_string(), document.clone());
+        Ok(document)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum Context {
     Reference(String),
     Context(ContextData),
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ContextData {
     #[serde(flatten)]
     additional_fields: HashMap<String, Context>,
@@ -56,7 +126,7 @@ pub struct ContextData {
     container: Option<ContainerType>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum ContainerType {
     #[serde(rename = "@list")]
     List,
@@ -76,7 +146,7 @@ pub enum ContainerType {
     None,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum Schema {
     #[serde(rename = "@versions")]
@@ -113,22 +183,134 @@ pub struct FieldDefinition {
 }
 
 impl SLD {
+    pub fn new(context: Context, document: Value) -> Self {
+        Self { context, document }
+    }
+
     pub fn validate(&self) -> Result<(), String> {
-        self.context.validate()
+        self.validate_with(&AllowedSchemes::default(), &FormatRegistry::default())
+    }
+
+    /// Validates the document, only accepting URIs with one of the given schemes and checking
+    /// field formats against the validators in `formats`.
+    pub fn validate_with(
+        &self,
+        schemes: &AllowedSchemes,
+        formats: &FormatRegistry,
+    ) -> Result<(), String> {
+        self.context.validate_with(schemes, formats)?;
+        match &self.context {
+            Context::Context(context) => context.validate_terms(&self.document),
+            Context::Reference(_) => Ok(()),
+        }
+    }
+
+    /// Resolves the `@context` of the document into a single `ContextData`.
+    ///
+    /// Referenced contexts are fetched through `loader`, and nested `@context` entries are
+    /// resolved recursively, with the definitions of the referencing context taking precedence
+    /// over the ones it includes. Wrap `loader` in a `CachingLoader` to fetch each IRI only once
+    /// across resolutions.
+    pub fn resolve_contexts(&self, loader: &dyn ContextLoader) -> Result<ContextData, String> {
+        self.resolve(&self.context, loader, &mut Vec::new())
+    }
+
+    /// Resolves the `@context` of the document with `loader` and validates the merged context.
+    pub fn validate_resolved(&self, loader: &dyn ContextLoader) -> Result<(), String> {
+        let context = self.resolve_contexts(loader)?;
+        context.validate()?;
+        context.validate_terms(&self.document)
+    }
+
+    fn resolve(
+        &self,
+        context: &Context,
+        loader: &dyn ContextLoader,
+        active: &mut Vec<String>,
+    ) -> Result<ContextData, String> {
+        match context {
+            Context::Reference(iri) => self.resolve_reference(iri, loader, active),
+            Context::Context(data) => {
+                let mut data = data.clone();
+                match data.context.take() {
+                    Some(nested) => Ok(self.resolve(&nested, loader, active)?.merge(data)),
+                    None => Ok(data),
+                }
+            }
+        }
+    }
+
+    fn resolve_reference(
+        &self,
+        iri: &str,
+        loader: &dyn ContextLoader,
+        active: &mut Vec<String>,
+    ) -> Result<ContextData, String> {
+        if active.iter().any(|t| t == iri) {
+            return Err(format!("Recursive context inclusion of '{}'", iri));
+        }
+
+        let context = loader
+            .load(iri)?
+            .get_mut("@context")
+            .map(Value::take)
+            .ok_or_else(|| format!("Invalid remote context '{}': missing @context", iri))?;
+        let context: Context = serde_json::from_value(context)
+            .map_err(|e| format!("Invalid remote context '{}': {}", iri, e))?;
+
+        active.push(iri.to_string());
+        let resolved = self.resolve(&context, loader, active);
+        active.pop();
+        resolved
     }
 }
 
 impl Context {
     pub fn validate(&self) -> Result<(), String> {
+        self.validate_with(&AllowedSchemes::default(), &FormatRegistry::default())
+    }
+
+    pub fn validate_with(
+        &self,
+        schemes: &AllowedSchemes,
+        formats: &FormatRegistry,
+    ) -> Result<(), String> {
         match self {
-            Self::Context(c) => c.validate(),
+            Self::Context(c) => c.validate_with(schemes, formats),
             Self::Reference(s) => Ok(()),
         }
     }
 }
 
 impl ContextData {
+    /// Returns this context with the definitions of `other` applied on top of it.
+    pub fn merge(mut self, other: ContextData) -> ContextData {
+        self.additional_fields.extend(other.additional_fields);
+        ContextData {
+            additional_fields: self.additional_fields,
+            context: other.context.or(self.context),
+            base: other.base.or(self.base),
+            vocab: other.vocab.or(self.vocab),
+            language: other.language.or(self.language),
+            protected: other.protected.or(self.protected),
+            version: other.version.or(self.version),
+            context_type: other.context_type.or(self.context_type),
+            graph: other.graph.or(self.graph),
+            implements: other.implements.or(self.implements),
+            schema: other.schema.or(self.schema),
+            container: other.container.or(self.container),
+        }
+    }
+
     pub fn validate(&self) -> Result<(), String> {
+        self.validate_with(&AllowedSchemes::default(), &FormatRegistry::default())
+    }
+
+    pub fn validate_with(
+        &self,
+        schemes: &AllowedSchemes,
+        formats: &FormatRegistry,
+    ) -> Result<(), String> {
         // Validate the @version field
         if let Some(version) = &self.version {
             if version.is_empty() {
@@ -142,14 +324,14 @@ impl ContextData {
 
         // Validate @base as a valid URI
         if let Some(base) = &self.base {
-            if !is_valid_uri(base) {
+            if !schemes.is_valid_uri(base) {
                 return Err("Invalid context: base must be a valid URI".to_string());
             }
         }
 
         // Validate @vocab as a valid URI
         if let Some(vocab) = &self.vocab {
-            if !is_valid_uri(vocab) {
+            if !schemes.is_valid_uri(vocab) {
                 return Err("Invalid context: vocab must be a valid URI".to_string());
             }
         }
@@ -157,27 +339,139 @@ impl ContextData {
         // Validate additional fields as valid URIs or Contexts
         for (key, context) in &self.additional_fields {
             context
-                .validate()
+                .validate_with(schemes, formats)
                 .map_err(|e| format!("Error in field '{}': {}", key, e))?;
         }
 
+        // Validate that @container can be combined with the other keywords
+        if let Some(container) = &self.container {
+            self.validate_container(container)?;
+        }
+
         // Validate schema if present
         if let Some(schema) = &self.schema {
-            schema.validate()?;
+            schema.validate_with(schemes, formats)?;
         }
 
         // Validate nested context
         if let Some(context) = &self.context {
-            context.validate()?;
+            context.validate_with(schemes, formats)?;
         }
 
         // No errors found
         Ok(())
     }
+
+    /// Validates the values of `document` against the `@container` of the matching term
+    /// definitions in this context.
+    pub fn validate_terms(&self, document: &Value) -> Result<(), String> {
+        for (term, definition) in &self.additional_fields {
+            let Context::Context(definition) = definition else {
+                continue;
+            };
+            if let (Some(container), Some(value)) = (&definition.container, document.get(term)) {
+                validate_container_value(container, value)
+                    .map_err(|e| format!("Error in term '{}': {}", term, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_container(&self, container: &ContainerType) -> Result<(), String> {
+        match (container, self.context_type.as_deref()) {
+            (ContainerType::Language, Some(context_type)) => Err(format!(
+                "Invalid context: a @language container cannot be combined with @type '{}'",
+                context_type
+            )),
+            (ContainerType::Type, Some(context_type))
+                if !matches!(context_type, "@id" | "@vocab") =>
+            {
+                Err(format!(
+                    "Invalid context: a @type container requires @type to be @id or @vocab, found '{}'",
+                    context_type
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Validates that `value` has the shape required by `container`.
+fn validate_container_value(container: &ContainerType, value: &Value) -> Result<(), String> {
+    match container {
+        ContainerType::Language => {
+            let map = value
+                .as_object()
+                .ok_or("a @language container requires a language map")?;
+            for (tag, value) in map {
+                if tag != "@none" && !is_valid_language_tag(tag) {
+                    return Err(format!("'{}' is not a valid language tag", tag));
+                }
+                let is_string = |t: &Value| t.is_string() || t.is_null();
+                let valid = match value {
+                    Value::Array(values) => values.iter().all(is_string),
+                    value => is_string(value),
+                };
+                if !valid {
+                    return Err(format!(
+                        "language map values must be strings, found {} for '{}'",
+                        value, tag
+                    ));
+                }
+            }
+            Ok(())
+        }
+        // A single value is treated as an array with one element. Plain objects are node objects,
+        // scalars are values and arrays are nested lists. A list object is ordered and does not fit
+        // an unordered @set, a set object does not fit an ordered @list.
+        ContainerType::List | ContainerType::Set => {
+            let (name, other) = match container {
+                ContainerType::List => ("@list", "@set"),
+                _ => ("@set", "@list"),
+            };
+            let items = match value {
+                Value::Array(values) => values.as_slice(),
+                value => core::slice::from_ref(value),
+            };
+            match items.iter().find(|t| t.get(other).is_some()) {
+                Some(item) => Err(format!(
+                    "a {} container cannot hold the {} object {}",
+                    name, other, item
+                )),
+                None => Ok(()),
+            }
+        }
+        ContainerType::Index | ContainerType::Id | ContainerType::Type => {
+            if value.is_object() {
+                Ok(())
+            } else {
+                Err("an @index, @id or @type container requires an object map".to_string())
+            }
+        }
+        ContainerType::Graph | ContainerType::None => Ok(()),
+    }
+}
+
+/// Loosely checks a BCP 47 language tag, e.g. `en` or `pt-BR`.
+fn is_valid_language_tag(tag: &str) -> bool {
+    let mut subtags = tag.split('-');
+    subtags
+        .next()
+        .is_some_and(|t| (1..=8).contains(&t.len()) && t.chars().all(|c| c.is_ascii_alphabetic()))
+        && subtags
+            .all(|t| (1..=8).contains(&t.len()) && t.chars().all(|c| c.is_ascii_alphanumeric()))
 }
 
 impl Schema {
     pub fn validate(&self) -> Result<(), String> {
+        self.validate_with(&AllowedSchemes::default(), &FormatRegistry::default())
+    }
+
+    pub fn validate_with(
+        &self,
+        schemes: &AllowedSchemes,
+        formats: &FormatRegistry,
+    ) -> Result<(), String> {
         match self {
             Schema::Versions(versions) => {
                 for (key, value) in versions {
@@ -188,7 +482,7 @@ impl Schema {
                         ));
                     }
                     // Ensure that the version keys are valid URIs
-                    if !is_valid_uri(key) {
+                    if !schemes.is_valid_uri(key) {
                         return Err(format!(
                             "Invalid schema version key '{}': must be a valid URI",
                             key
@@ -202,7 +496,7 @@ impl Schema {
                 }
                 // Ensure that each reference is a valid URI
                 for ref_uri in reference {
-                    if !is_valid_uri(ref_uri) {
+                    if !schemes.is_valid_uri(ref_uri) {
                         return Err(format!(
                             "Invalid schema reference '{}': must be a valid URI",
                             ref_uri
@@ -211,7 +505,7 @@ impl Schema {
                 }
             }
             Schema::Value { item } | Schema::Array { items: item } => {
-                item.validate()?;
+                item.validate_with(formats)?;
             }
             Schema::Object { properties } => {
                 for (key, field) in properties {
@@ -219,13 +513,13 @@ impl Schema {
                         return Err("Invalid object property: key is empty".to_string());
                     }
                     // Ensure property keys are valid URIs
-                    if !is_valid_uri(key) {
+                    if !schemes.is_valid_uri(key) {
                         return Err(format!(
                             "Invalid object property key '{}': must be a valid URI",
                             key
                         ));
                     }
-                    field.validate()?;
+                    field.validate_with(formats)?;
                 }
             }
         }
@@ -236,6 +530,11 @@ impl Schema {
 
 impl FieldDefinition {
     pub fn validate(&self) -> Result<(), String> {
+        self.validate_with(&FormatRegistry::default())
+    }
+
+    /// Validates the definition, resolving `format` against the validators in `formats`.
+    pub fn validate_with(&self, formats: &FormatRegistry) -> Result<(), String> {
         if self.title.is_empty() {
             return Err("Field definition error: title cannot be empty".to_string());
         }
@@ -245,7 +544,7 @@ impl FieldDefinition {
         }
 
         // Validate format and pattern if provided
-        if !self.format.is_empty() && !is_valid_format(&self.format) {
+        if !self.format.is_empty() && !formats.contains(&self.format) {
             return Err("Field definition error: invalid format".to_string());
         }
 
@@ -255,7 +554,7 @@ impl FieldDefinition {
 
         // Validate that examples match the schema type, format, and pattern
         for example in &self.examples {
-            if !self.validate_example(example) {
+            if !self.validate_example(example, formats) {
                 return Err(format!(
                     "Field definition error: example '{}' does not match schema constraints",
                     example
@@ -266,8 +565,11 @@ impl FieldDefinition {
         Ok(())
     }
 
-    fn validate_example(&self, example: &str) -> bool {
+    fn validate_example(&self, example: &str, formats: &FormatRegistry) -> bool {
         // Example validation logic (simplified for illustration)
+        if !self.format.is_empty() && formats.validate(&self.format, example) == Some(false) {
+            return false;
+        }
         if self.schema_type == "string" {
             if !self.pattern.is_empty() {
                 let regex = regex::Regex::new(&self.pattern).unwrap();
@@ -278,15 +580,145 @@ impl FieldDefinition {
     }
 }
 
-// Helper functions to validate URIs, formats, and patterns
-fn is_valid_uri(uri: &str) -> bool {
-    // This is a simplified check. You might want to use a more robust URI validation logic.
-    uri.starts_with("http://") || uri.starts_with("https://")
+/// The URI schemes accepted when validating `@base`, `@vocab` and schema URIs.
+#[derive(Debug, Clone, Default)]
+pub enum AllowedSchemes {
+    /// Any syntactically valid URI is accepted, e.g. `https:`, `did:`, `urn:` or `ipfs:`.
+    #[default]
+    Any,
+    /// Only URIs with one of these (lowercase) schemes are accepted.
+    Only(HashSet<String>),
+}
+
+impl AllowedSchemes {
+    /// Accepts only URIs with one of the given schemes, compared case-insensitively.
+    pub fn only<I, S>(schemes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::Only(
+            schemes
+                .into_iter()
+                .map(|t| t.as_ref().to_ascii_lowercase())
+                .collect(),
+        )
+    }
+
+    /// Returns whether `uri` is a valid absolute URI with an allowed scheme.
+    pub fn is_valid_uri(&self, uri: &str) -> bool {
+        url::Url::parse(uri).is_ok_and(|url| match self {
+            Self::Any => true,
+            Self::Only(schemes) => schemes.contains(url.scheme()),
+        })
+    }
 }
 
-fn is_valid_format(format: &str) -> bool {
-    // Validate against known formats (e.g., "date", "email", etc.)
-    matches!(format, "date" | "email" | "uri" | "uuid")
+/// A function that checks whether a value matches a named format.
+pub type FormatValidator = Box<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// The named formats a `FieldDefinition` may use, together with their validators.
+///
+/// `FormatRegistry::default()` knows the `date`, `email`, `uri` and `uuid` formats, further formats
+/// can be added with `FormatRegistry::register`.
+///
+/// # Examples
+/// ```
+/// use gearbox::did::sld::FormatRegistry;
+///
+/// let mut formats = FormatRegistry::default();
+/// formats.register("hex-color", |t| {
+///     t.len() == 7 && t.starts_with('#') && t[1..].chars().all(|c| c.is_ascii_hexdigit())
+/// });
+///
+/// assert_eq!(formats.validate("hex-color", "#1a2b3c"), Some(true));
+/// assert_eq!(formats.validate("date", "2023-02-30x"), Some(false));
+/// assert_eq!(formats.validate("unknown", "value"), None);
+/// ```
+pub struct FormatRegistry {
+    validators: HashMap<String, FormatValidator>,
+}
+
+impl FormatRegistry {
+    /// Creates a registry without any formats, not even the built-in ones.
+    pub fn empty() -> Self {
+        Self {
+            validators: HashMap::new(),
+        }
+    }
+
+    /// Registers `validator` for the format `name`, replacing any validator with the same name.
+    pub fn register<F>(&mut self, name: &str, validator: F) -> &mut Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.validators
+            .insert(name.to_string(), Box::new(validator));
+        self
+    }
+
+    /// Returns whether a validator is registered for the format `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.validators.contains_key(name)
+    }
+
+    /// Checks `value` against the format `name`, or returns `None` if the format is unknown.
+    pub fn validate(&self, name: &str, value: &str) -> Option<bool> {
+        self.validators.get(name).map(|validator| validator(value))
+    }
+}
+
+impl Default for FormatRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register("date", is_valid_date)
+            .register("email", is_valid_email)
+            .register("uri", |t| url::Url::parse(t).is_ok())
+            .register("uuid", is_valid_uuid);
+        registry
+    }
+}
+
+impl core::fmt::Debug for FormatRegistry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.validators.keys()).finish()
+    }
+}
+
+// Helper functions to validate formats and patterns
+fn is_valid_date(date: &str) -> bool {
+    // YYYY-MM-DD with a month and day in range
+    let parts: Vec<&str> = date.split('-').collect();
+    let number = |t: &str, len: usize| {
+        (t.len() == len && t.chars().all(|c| c.is_ascii_digit()))
+            .then(|| t.parse::<u32>().ok())
+            .flatten()
+    };
+    match parts[..] {
+        [year, month, day] => matches!(
+            (number(year, 4), number(month, 2), number(day, 2)),
+            (Some(_), Some(1..=12), Some(1..=31))
+        ),
+        _ => false,
+    }
+}
+
+fn is_valid_email(email: &str) -> bool {
+    email.split_once('@').is_some_and(|(local, domain)| {
+        !local.is_empty()
+            && !domain.contains('@')
+            && domain.split('.').count() > 1
+            && domain.split('.').all(|t| !t.is_empty())
+    })
+}
+
+fn is_valid_uuid(uuid: &str) -> bool {
+    let groups: Vec<&str> = uuid.split('-').collect();
+    groups.iter().map(|t| t.len()).eq([8, 4, 4, 4, 12])
+        && groups
+            .iter()
+            .all(|t| t.chars().all(|c| c.is_ascii_hexdigit()))
 }
 
 fn is_valid_pattern(pattern: &str) -> bool {
@@ -312,7 +744,7 @@ mod tests {
         let context = Context::Context(ContextData {
             additional_fields: HashMap::new(),
             context: None,
-            base: None,
+            base: Some("did:example:123".to_string()),
             vocab: None,
             language: None,
             protected: Some(true),
@@ -324,10 +756,7 @@ mod tests {
             container: None,
         });
 
-        let sld = SLD {
-            context,
-            document: serde_json::json!({ "example": "data" }),
-        };
+        let sld = SLD::new(context, serde_json::json!({ "example": "data" }));
 
         assert!(sld.validate().is_ok());
     }
@@ -358,10 +787,7 @@ mod tests {
             container: None,
         });
 
-        let sld = SLD {
-            context,
-            document: serde_json::json!({ "example": "data" }),
-        };
+        let sld = SLD::new(context, serde_json::json!({ "example": "data" }));
 
         assert!(sld.validate().is_err());
     }
@@ -424,4 +850,313 @@ mod tests {
 
         assert!(validated.is_ok());
     }
+
+    fn stub_loader() -> HashMap<String, Value> {
+        let mut loader = HashMap::new();
+        loader.insert(
+            "https://example.com/credentials/v1".to_string(),
+            serde_json::json!({
+                "@context": {
+                    "@context": "https://example.com/base/v1",
+                    "@version": "1.1",
+                    "name": "https://schema.org/name"
+                }
+            }),
+        );
+        loader.insert(
+            "https://example.com/base/v1".to_string(),
+            serde_json::json!({
+                "@context": {
+                    "@vocab": "https://example.com/vocab#",
+                    "name": "https://example.com/vocab#name",
+                    "email": "https://schema.org/email"
+                }
+            }),
+        );
+        loader
+    }
+
+    struct CountingLoader {
+        inner: HashMap<String, Value>,
+        loads: std::cell::Cell<usize>,
+    }
+
+    impl ContextLoader for CountingLoader {
+        fn load(&self, iri: &str) -> Result<Value, String> {
+            self.loads.set(self.loads.get() + 1);
+            self.inner.load(iri)
+        }
+    }
+
+    #[test]
+    fn test_resolve_remote_context() {
+        let sld: SLD = serde_json::from_str(
+            r#"{ "@context": "https://example.com/credentials/v1", "name": "Alice" }"#,
+        )
+        .unwrap();
+
+        let loader = CachingLoader::new(CountingLoader {
+            inner: stub_loader(),
+            loads: Default::default(),
+        });
+        let context = sld.resolve_contexts(&loader).unwrap();
+
+        assert_eq!(context.version.as_deref(), Some("1.1"));
+        assert_eq!(context.vocab.as_deref(), Some("https://example.com/vocab#"));
+        assert!(context.context.is_none());
+        assert!(matches!(
+            context.additional_fields.get("name"),
+            Some(Context::Reference(iri)) if iri == "https://schema.org/name"
+        ));
+        assert!(context.additional_fields.contains_key("email"));
+        assert!(context.validate().is_ok());
+        assert_eq!(loader.inner().loads.get(), 2);
+
+        // Both contexts are served from the cache the second time around, for any document.
+        assert!(sld.validate_resolved(&loader).is_ok());
+        let other: SLD = serde_json::from_str(
+            r#"{ "@context": "https://example.com/credentials/v1", "email": "a@b.c" }"#,
+        )
+        .unwrap();
+        assert!(other.validate_resolved(&loader).is_ok());
+        assert_eq!(loader.inner().loads.get(), 2);
+
+        loader.clear();
+        assert!(other.resolve_contexts(&loader).is_ok());
+        assert_eq!(loader.inner().loads.get(), 4);
+    }
+
+    #[test]
+    fn test_resolve_remote_context_errors() {
+        let mut loader = stub_loader();
+        let missing: SLD =
+            serde_json::from_str(r#"{ "@context": "https://example.com/missing" }"#).unwrap();
+        assert!(missing.resolve_contexts(&loader).is_err());
+
+        loader.insert(
+            "https://example.com/base/v1".to_string(),
+            serde_json::json!({
+                "@context": { "@context": "https://example.com/credentials/v1" }
+            }),
+        );
+        let recursive: SLD =
+            serde_json::from_str(r#"{ "@context": "https://example.com/credentials/v1" }"#)
+                .unwrap();
+        let err = recursive.resolve_contexts(&loader).unwrap_err();
+        assert!(err.contains("Recursive context inclusion"));
+
+        loader.insert(
+            "https://example.com/base/v1".to_string(),
+            serde_json::json!({ "@context": { "@vocab": "vocab" } }),
+        );
+        let invalid: SLD =
+            serde_json::from_str(r#"{ "@context": "https://example.com/credentials/v1" }"#)
+                .unwrap();
+        // The reference alone passes, only the fetched context is invalid.
+        assert!(invalid.validate().is_ok());
+        assert!(invalid.validate_resolved(&loader).is_err());
+    }
+
+    fn context_with_base(base: &str) -> ContextData {
+        serde_json::from_value(serde_json::json!({ "@base": base })).unwrap()
+    }
+
+    #[test]
+    fn test_uri_validation() {
+        for uri in [
+            "https://example.com/contexts/v1",
+            "did:example:123",
+            "urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6",
+            "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ] {
+            assert!(context_with_base(uri).validate().is_ok(), "{}", uri);
+        }
+
+        for uri in ["http://", "http://exa mple.com", "example.com/contexts", ""] {
+            assert!(context_with_base(uri).validate().is_err(), "{}", uri);
+        }
+
+        let web_only = AllowedSchemes::only(["HTTPS"]);
+        assert!(context_with_base("https://example.com")
+            .validate_with(&web_only, &FormatRegistry::default())
+            .is_ok());
+        assert!(context_with_base("did:example:123")
+            .validate_with(&web_only, &FormatRegistry::default())
+            .is_err());
+    }
+
+    #[test]
+    fn test_language_container() {
+        let sld: SLD = serde_json::from_str(
+            r#"{
+  "@context": {
+    "label": { "@id": "https://schema.org/name", "@container": "@language" },
+    "tags": { "@id": "https://schema.org/keywords", "@container": "@set" }
+  },
+  "label": { "en": "Mint", "pt-BR": ["Hortelã", "Menta"], "@none": null },
+  "tags": ["herb", { "@value": "plant" }]
+}"#,
+        )
+        .unwrap();
+        assert!(sld.validate().is_ok());
+
+        let sld: SLD = serde_json::from_str(
+            r#"{
+  "@context": { "label": { "@id": "https://schema.org/name", "@container": "@language" } },
+  "label": { "en": 42 }
+}"#,
+        )
+        .unwrap();
+        let err = sld.validate().unwrap_err();
+        assert!(err.contains("Error in term 'label'"), "{}", err);
+
+        let sld: SLD = serde_json::from_str(
+            r#"{
+  "@context": { "label": { "@id": "https://schema.org/name", "@container": "@language" } },
+  "label": "Mint"
+}"#,
+        )
+        .unwrap();
+        assert!(sld.validate().is_err());
+    }
+
+    #[test]
+    fn test_invalid_container_combination() {
+        let sld: SLD = serde_json::from_str(
+            r#"{
+  "@context": {
+    "label": {
+      "@id": "https://schema.org/name",
+      "@type": "xsd:string",
+      "@container": "@language"
+    }
+  }
+}"#,
+        )
+        .unwrap();
+        let err = sld.validate().unwrap_err();
+        assert!(
+            err.contains("@language container cannot be combined"),
+            "{}",
+            err
+        );
+
+        // Plain objects are node objects and therefore valid @list items.
+        let sld: SLD = serde_json::from_str(
+            r#"{
+  "@context": { "steps": { "@id": "https://schema.org/step", "@container": "@list" } },
+  "steps": [{ "description": "Crush" }, "Serve", { "@value": "Enjoy" }]
+}"#,
+        )
+        .unwrap();
+        assert!(sld.validate().is_ok());
+    }
+
+    #[test]
+    fn test_list_and_set_container_values() {
+        let sld: SLD = serde_json::from_str(
+            r#"{
+  "@context": { "tags": { "@id": "https://schema.org/keywords", "@container": "@set" } },
+  "tags": { "@list": ["herb", "plant"] }
+}"#,
+        )
+        .unwrap();
+        let err = sld.validate().unwrap_err();
+        assert!(
+            err.contains("a @set container cannot hold the @list object"),
+            "{}",
+            err
+        );
+
+        let sld: SLD = serde_json::from_str(
+            r#"{
+  "@context": { "steps": { "@id": "https://schema.org/step", "@container": "@list" } },
+  "steps": ["Crush", { "@set": ["Serve", "Enjoy"] }]
+}"#,
+        )
+        .unwrap();
+        let err = sld.validate().unwrap_err();
+        assert!(
+            err.contains("a @list container cannot hold the @set object"),
+            "{}",
+            err
+        );
+
+        // Nested arrays are lists of lists.
+        let sld: SLD = serde_json::from_str(
+            r#"{
+  "@context": { "steps": { "@id": "https://schema.org/step", "@container": "@list" } },
+  "steps": [["Crush", "Mix"], "Serve"]
+}"#,
+        )
+        .unwrap();
+        assert!(sld.validate().is_ok());
+    }
+
+    fn number_field(format: &str, examples: &[&str]) -> FieldDefinition {
+        FieldDefinition {
+            title: "Count".to_string(),
+            description: "An even amount.".to_string(),
+            schema_type: "number".to_string(),
+            format: format.to_string(),
+            pattern: "".to_string(),
+            examples: examples.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_custom_format() {
+        let mut formats = FormatRegistry::default();
+        formats.register("even-number", |t| {
+            t.parse::<i64>().is_ok_and(|n| n % 2 == 0)
+        });
+
+        let field = number_field("even-number", &["2", "-4", "10"]);
+        assert!(field.validate_with(&formats).is_ok());
+        // The format is unknown to the built-in registry.
+        assert!(field.validate().is_err());
+
+        let field = number_field("even-number", &["2", "3"]);
+        let err = field.validate_with(&formats).unwrap_err();
+        assert!(err.contains("example '3'"), "{}", err);
+
+        // The registry is threaded through the whole document.
+        let context = Context::Context(ContextData {
+            additional_fields: HashMap::new(),
+            context: None,
+            base: None,
+            vocab: None,
+            language: None,
+            protected: None,
+            version: None,
+            context_type: None,
+            graph: None,
+            implements: None,
+            schema: Some(Schema::Value {
+                item: number_field("even-number", &["8"]),
+            }),
+            container: None,
+        });
+        let sld = SLD::new(context, serde_json::json!({}));
+        assert!(sld
+            .validate_with(&AllowedSchemes::default(), &formats)
+            .is_ok());
+        assert!(sld.validate().is_err());
+    }
+
+    #[test]
+    fn test_builtin_formats() {
+        let formats = FormatRegistry::default();
+        assert_eq!(formats.validate("date", "2023-08-26"), Some(true));
+        assert_eq!(formats.validate("date", "2023-13-01"), Some(false));
+        assert_eq!(formats.validate("email", "alice@example.com"), Some(true));
+        assert_eq!(formats.validate("email", "alice@example"), Some(false));
+        assert_eq!(
+            formats.validate("uuid", "f81d4fae-7dec-11d0-a765-00a0c91e6bf6"),
+            Some(true)
+        );
+        assert_eq!(formats.validate("uuid", "f81d4fae-7dec-11d0"), Some(false));
+        assert_eq!(formats.validate("uri", "did:example:123"), Some(true));
+        assert!(!FormatRegistry::empty().contains("date"));
+    }
 }
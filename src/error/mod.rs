@@ -4,7 +4,7 @@ pub mod tracer;
 pub mod type_registry;
 
 #[cfg(feature = "error-tracer")]
-pub use tracer::{DynTracerError, TracerError};
+pub use tracer::{collect_errors, DynTracerError, TracerError};
 
 #[cfg(feature = "error-type-registry")]
 pub use type_registry::TypeRegistry;
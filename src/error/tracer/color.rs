@@ -0,0 +1,140 @@
+//! ANSI colored rendering of `ErrorDigest` for terminals.
+use crate::error::tracer::ErrorDigest;
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+impl ErrorDigest {
+    /// Renders the digest like `to_stack`, with the messages in red, the codes in yellow and the
+    /// files and lines dimmed.
+    ///
+    /// Colors are only used if stderr is a terminal and `NO_COLOR` is not set, otherwise the
+    /// output is the same as `to_stack`. Use `to_stack_with_color` to decide yourself.
+    pub fn to_stack_colored(&self) -> String {
+        self.to_stack_with_color(color_enabled())
+    }
+
+    /// Renders the digest like `to_stack_colored`, with colors forced on or off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::error::tracer::ErrorDigest;
+    ///
+    /// let digest = ErrorDigest::new("failed".to_string(), None, Some(7), None, None, Some(500), None);
+    /// assert!(digest.to_stack_with_color(true).contains("\x1b[31mfailed\x1b[0m"));
+    /// assert_eq!(digest.to_stack_with_color(false), digest.to_stack());
+    /// ```
+    pub fn to_stack_with_color(&self, color: bool) -> String {
+        if !color {
+            return self.to_stack();
+        }
+        format!(
+            "Error: {message}\nCode: {code}\nFile: {file}\nLine: {line}\nSubsystem: {subsystem}\nStack trace:\n{stack}",
+            message = paint(RED, &self.message),
+            code = paint(YELLOW, &self.code.unwrap_or(0).to_string()),
+            file = paint(DIM, self.file.as_deref().unwrap_or("<Unknown>")),
+            line = paint(DIM, &self.line.map(|t| t.to_string()).unwrap_or("-1".to_string())),
+            subsystem = self.subsystem.as_deref().unwrap_or("<Unknown>"),
+            stack = self.stack_lines(ErrorDigest::to_stack_line_colored).join("\n")
+        )
+    }
+
+    fn to_stack_line_colored(&self) -> String {
+        format!(
+            "{file}::{subsystem}:{line} {code}{message}",
+            message = paint(RED, &self.message),
+            code = self
+                .code
+                .map(|t| format!("{} ", paint(YELLOW, &format!("[code: {}]", t))))
+                .unwrap_or("".to_string()),
+            file = paint(DIM, self.file.as_deref().unwrap_or("<Unknown>")),
+            line = paint(
+                DIM,
+                &self.line.map(|t| t.to_string()).unwrap_or("-1".to_string())
+            ),
+            subsystem = self.subsystem.as_deref().unwrap_or("<Unknown>")
+        )
+    }
+}
+
+fn paint(color: &str, text: &str) -> String {
+    format!("{}{}{}", color, text, RESET)
+}
+
+/// Returns whether stderr is a terminal that colors should be written to.
+#[cfg(feature = "std")]
+fn color_enabled() -> bool {
+    use std::io::IsTerminal;
+    std::env::var_os("NO_COLOR").is_none_or(|t| t.is_empty()) && std::io::stderr().is_terminal()
+}
+
+#[cfg(not(feature = "std"))]
+fn color_enabled() -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn digest() -> ErrorDigest {
+        let cause = ErrorDigest::new(
+            "connection refused".to_string(),
+            None,
+            Some(12),
+            Some("src/db.rs".to_string()),
+            Some("db".to_string()),
+            Some(503),
+            None,
+        );
+        ErrorDigest::new(
+            "loading the user failed".to_string(),
+            None,
+            Some(40),
+            Some("src/user.rs".to_string()),
+            Some("user".to_string()),
+            Some(500),
+            Some(vec![cause]),
+        )
+    }
+
+    #[test]
+    fn test_forced_on() {
+        let rendered = digest().to_stack_with_color(true);
+        assert!(rendered.contains("Error: \x1b[31mloading the user failed\x1b[0m"));
+        assert!(rendered.contains("Code: \x1b[33m500\x1b[0m"));
+        assert!(rendered.contains("File: \x1b[2msrc/user.rs\x1b[0m"));
+        assert!(rendered.contains("Line: \x1b[2m40\x1b[0m"));
+
+        let stack = rendered.lines().last().unwrap();
+        assert_eq!(
+            stack,
+            "\x1b[2msrc/db.rs\x1b[0m::db:\x1b[2m12\x1b[0m \x1b[33m[code: 503]\x1b[0m \x1b[31mconnection refused\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_forced_off() {
+        let digest = digest();
+        let rendered = digest.to_stack_with_color(false);
+        assert!(!rendered.contains('\x1b'));
+        assert_eq!(rendered, digest.to_stack());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_plain_without_terminal() {
+        // Test output is captured, so stderr is usually not a terminal.
+        if !std::io::IsTerminal::is_terminal(&std::io::stderr()) {
+            assert!(!digest().to_stack_colored().contains('\x1b'));
+        }
+    }
+}
@@ -1,13 +1,8 @@
 #[macro_export]
 macro_rules! tracer_err {
     () => {{
-        use core::any::Any;
-        use $crate::alloc::{
-            boxed::Box,
-            string::{String, ToString},
-        };
+        use $crate::alloc::{boxed::Box, string::ToString};
         $crate::error::tracer::TracerError::new(
-            String::new().type_id(),
             Box::new("".to_string()),
             $crate::error::tracer::ErrorTracerExtInfo::new(
                 Some(line!()),
@@ -19,11 +14,8 @@ macro_rules! tracer_err {
         )
     }};
     ($e:expr) => {{
-        use core::any::Any;
         use $crate::alloc::{boxed::Box, string::ToString};
-        let type_id = $e.type_id();
         $crate::error::tracer::TracerError::new(
-            type_id,
             Box::new($e),
             $crate::error::tracer::ErrorTracerExtInfo::new(
                 Some(line!()),
@@ -70,6 +62,61 @@ macro_rules! tracer_dyn_err {
         )
     }};
 }
+/// Like `tracer_err!`, but also sets the `code` of the `ErrorTracerExtInfo`, e.g. the HTTP status
+/// an error maps to.
+///
+/// # Examples
+///
+/// ```
+/// use gearbox::tracer_err_with_code;
+///
+/// let error = tracer_err_with_code!("user not found", 404);
+/// assert_eq!(error.digest().code, Some(404));
+/// ```
+#[macro_export]
+macro_rules! tracer_err_with_code {
+    ($e:expr, $code:expr) => {{
+        use $crate::alloc::{boxed::Box, string::ToString};
+        $crate::error::tracer::TracerError::new(
+            Box::new($e),
+            $crate::error::tracer::ErrorTracerExtInfo::new(
+                Some(line!()),
+                Some(file!().to_string()),
+                Some(module_path!().to_string()),
+                Some($code),
+            ),
+            None,
+        )
+    }};
+}
+
+/// Like `tracer_dyn_err!`, but also sets the `code` of the `ErrorTracerExtInfo`.
+///
+/// # Examples
+///
+/// ```
+/// use gearbox::tracer_dyn_err_with_code;
+///
+/// let error = tracer_dyn_err_with_code!("user not found", 404);
+/// assert_eq!(error.digest().code, Some(404));
+/// ```
+#[macro_export]
+macro_rules! tracer_dyn_err_with_code {
+    ($e:expr, $code:expr) => {{
+        use $crate::alloc::{boxed::Box, string::ToString};
+        $crate::error::tracer::DynTracerError::new(
+            Box::new($e),
+            $crate::error::tracer::ErrorTracerExtInfo::new(
+                Some(line!()),
+                Some(file!().to_string()),
+                Some(module_path!().to_string()),
+                Some($code),
+            ),
+            None,
+        )
+    }};
+}
+
 #[macro_export]
 macro_rules! error_info {
     () => {
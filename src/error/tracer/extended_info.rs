@@ -37,6 +37,19 @@ impl ErrorTracerExtInfo {
         }
     }
 
+    /// Creates an info with the file and line of the caller. When called from a `#[track_caller]`
+    /// function, e.g. a `From` impl used by `?`, this is the location of its caller instead.
+    #[track_caller]
+    pub fn caller() -> Self {
+        let location = core::panic::Location::caller();
+        Self::new(
+            Some(location.line()),
+            Some(location.file().to_string()),
+            None,
+            None,
+        )
+    }
+
     pub fn with_file(mut self, file: &str) -> Self {
         self.file = Option::from(file.to_string());
         self
@@ -1,4 +1,6 @@
 // Mods
+#[cfg(feature = "error-tracer-color")]
+mod color;
 pub mod error_macro;
 pub mod extended_info;
 // Local uses
@@ -32,7 +34,25 @@ use wasm_bindgen::{
     JsValue,
 };
 
-pub trait ErrorDebug: fmt::Debug + Any {
+mod sealed {
+    use core::any::Any;
+
+    /// Gives access to the concrete error as `Any`, also when called through `dyn ErrorDebug`.
+    /// It is implemented for every type and can not be implemented outside this module.
+    pub trait AsDynAny {
+        fn as_dyn_any(&self) -> &dyn Any;
+    }
+
+    impl<T: Any> AsDynAny for T {
+        fn as_dyn_any(&self) -> &dyn Any {
+            self
+        }
+    }
+}
+
+use sealed::AsDynAny;
+
+pub trait ErrorDebug: fmt::Debug + Any + AsDynAny {
     fn to_error_parts(&self) -> (String, Option<String>) {
         (format!("{:?}", self), None)
     }
@@ -50,6 +70,146 @@ impl<T> Serializable for T where T: Serialize {}
 #[cfg(feature = "with_serde")]
 serialize_trait_object!(Serializable);
 
+/// Returns the error as a serializable trait object, if it is of type `T`.
+#[cfg(feature = "with_serde")]
+type ErrorSerializer = fn(&dyn ErrorDebug) -> Option<&dyn Serializable>;
+
+#[cfg(feature = "with_serde")]
+fn serialize_as<T: ErrorDebug + Serialize>(error: &dyn ErrorDebug) -> Option<&dyn Serializable> {
+    error
+        .as_dyn_any()
+        .downcast_ref::<T>()
+        .map(|t| t as &dyn Serializable)
+}
+
+/// Generic representation of a structured error read back into a `DynTracerError`, so it can be
+/// serialized again without knowing its original type.
+#[cfg(feature = "with_serde")]
+#[derive(Debug, Clone, PartialEq)]
+enum ErrorValue {
+    Unit,
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    String(String),
+    Seq(Vec<ErrorValue>),
+    Map(Vec<(ErrorValue, ErrorValue)>),
+}
+
+#[cfg(feature = "with_serde")]
+impl Serialize for ErrorValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use ser::{SerializeMap, SerializeSeq};
+        match self {
+            ErrorValue::Unit => serializer.serialize_unit(),
+            ErrorValue::Bool(v) => serializer.serialize_bool(*v),
+            ErrorValue::I64(v) => serializer.serialize_i64(*v),
+            ErrorValue::U64(v) => serializer.serialize_u64(*v),
+            ErrorValue::F64(v) => serializer.serialize_f64(*v),
+            ErrorValue::String(v) => serializer.serialize_str(v),
+            ErrorValue::Seq(values) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                for value in values {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+            ErrorValue::Map(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+#[cfg(feature = "with_serde")]
+impl<'de> Deserialize<'de> for ErrorValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ErrorValueVisitor;
+
+        impl<'de> Visitor<'de> for ErrorValueVisitor {
+            type Value = ErrorValue;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("any error value")
+            }
+
+            fn visit_bool<E: de::Error>(self, v: bool) -> Result<ErrorValue, E> {
+                Ok(ErrorValue::Bool(v))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<ErrorValue, E> {
+                Ok(ErrorValue::I64(v))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<ErrorValue, E> {
+                Ok(ErrorValue::U64(v))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<ErrorValue, E> {
+                Ok(ErrorValue::F64(v))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<ErrorValue, E> {
+                Ok(ErrorValue::String(v.to_string()))
+            }
+
+            fn visit_string<E: de::Error>(self, v: String) -> Result<ErrorValue, E> {
+                Ok(ErrorValue::String(v))
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<ErrorValue, E> {
+                Ok(ErrorValue::Unit)
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<ErrorValue, E> {
+                Ok(ErrorValue::Unit)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<ErrorValue, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                ErrorValue::deserialize(deserializer)
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<ErrorValue, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut values = Vec::new();
+                while let Some(value) = seq.next_element()? {
+                    values.push(value);
+                }
+                Ok(ErrorValue::Seq(values))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<ErrorValue, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(ErrorValue::Map(entries))
+            }
+        }
+
+        deserializer.deserialize_any(ErrorValueVisitor)
+    }
+}
+
 pub trait AnyBoxError: Any {
     fn as_any(&self) -> Box<&dyn Any>;
 }
@@ -92,6 +252,8 @@ where
     type_name: Option<String>,
     info: ErrorTracerExtInfo,
     cause: Option<Vec<DynTracerError>>,
+    #[cfg(feature = "with_serde")]
+    serializer: Option<ErrorSerializer>,
 }
 
 impl<T> TracerError<T>
@@ -108,6 +270,8 @@ where
             type_name: None,
             info,
             cause,
+            #[cfg(feature = "with_serde")]
+            serializer: None,
         }
     }
     pub fn new(
@@ -115,7 +279,7 @@ where
         info: ErrorTracerExtInfo,
         cause: Option<Vec<DynTracerError>>,
     ) -> Self {
-        #[cfg(feature = "type-registry")]
+        #[cfg(feature = "error-type-registry")]
         {
             use crate::error::type_registry::register_type;
             register_type::<T>();
@@ -126,6 +290,8 @@ where
             type_name: Some(type_name::<T>().to_string()),
             info,
             cause,
+            #[cfg(feature = "with_serde")]
+            serializer: None,
         }
     }
     pub fn kind(&self) -> &Box<T> {
@@ -144,16 +310,54 @@ where
         self.cause.as_ref().map(|t| !t.is_empty()).unwrap_or(false)
     }
 
+    /// Wraps this error as the cause of a new error `err`, building up the cause chain while an
+    /// error propagates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::error::tracer::ErrorTracerExtInfo;
+    /// use gearbox::error::TracerError;
+    ///
+    /// let error = TracerError::from("connection refused")
+    ///     .wrap("fetching the profile failed", ErrorTracerExtInfo::default());
+    /// assert!(error.has_cause());
+    /// ```
+    pub fn wrap<E: 'static + ErrorDebug>(self, err: E, info: ErrorTracerExtInfo) -> TracerError<E> {
+        TracerError::new(Box::new(err), info, Some(vec![self.into()]))
+    }
+
+    /// Adds `other` to the causes of this error.
+    pub fn caused_by<C: Into<DynTracerError>>(mut self, other: C) -> Self {
+        self.cause.get_or_insert_with(Vec::new).push(other.into());
+        self
+    }
+
+    /// Walks the cause chain, depth first, and returns the first cause whose concrete error type
+    /// is `C`.
+    pub fn downcast_cause<C: ErrorDebug + AnyBoxError>(&self) -> Option<&C> {
+        self.cause
+            .iter()
+            .flatten()
+            .find_map(|t| t.downcast_chain::<C>())
+    }
+
     pub fn err_to_string(&self) -> String {
         format!("{:?}", self.error)
     }
 
+    /// Returns the digest of this error and its causes, up to `DEFAULT_DIGEST_DEPTH` levels of
+    /// causes deep.
     pub fn digest(&self) -> ErrorDigest {
+        self.digest_with_depth(DEFAULT_DIGEST_DEPTH)
+    }
+
+    /// Returns the digest of this error and at most `max_depth` levels of its causes. Deeper
+    /// causes are replaced by a single digest with the message `... N more`, where `N` is the
+    /// number of causes left out.
+    pub fn digest_with_depth(&self, max_depth: usize) -> ErrorDigest {
         let (message, detailed_msg) = self.to_error_parts();
-        let stack = self
-            .cause
-            .as_ref()
-            .map(|t| t.iter().map(|t| t.digest()).collect::<Vec<ErrorDigest>>());
+        let stack = digest_causes(self.cause.as_ref(), max_depth);
         ErrorDigest {
             message,
             detailed_msg,
@@ -166,6 +370,28 @@ where
     }
 }
 
+#[cfg(feature = "with_serde")]
+impl<T> TracerError<T>
+where
+    T: 'static + ErrorDebug + Serialize,
+{
+    /// Serializes the error field as the structured value of `T`. Without it the error field is
+    /// serialized as the debug string of the error. The setting is kept when the error is
+    /// converted into a `DynTracerError`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::error::TracerError;
+    ///
+    /// let error = TracerError::from(404u16).with_structured_error();
+    /// ```
+    pub fn with_structured_error(mut self) -> Self {
+        self.serializer = Some(serialize_as::<T>);
+        self
+    }
+}
+
 impl<T> From<T> for TracerError<T>
 where
     T: 'static + ErrorDebug,
@@ -175,8 +401,13 @@ where
     }
 }
 
+/// Serializes the error with its type name, info and causes.
+///
+/// The error field is only written as the structured value of `T` after opting in with
+/// `with_structured_error`. This is deliberate: `TracerError` is built from any `ErrorDebug`
+/// through `From<T>`, and without specialization that conversion cannot detect whether `T`
+/// implements `Serialize`. Every other error field is written as the debug string of the error.
 #[cfg(feature = "with_serde")]
-// Custom Serialize for TracerError
 impl<T> Serialize for TracerError<T>
 where
     T: ErrorDebug,
@@ -186,8 +417,10 @@ where
         S: Serializer,
     {
         let mut state = serializer.serialize_struct("TracerError", 4)?;
-        // TODO! Serialize the error field properly
-        state.serialize_field("error", &format!("{:?}", *self.error))?;
+        match self.serializer.and_then(|f| f(self.error.as_ref())) {
+            Some(error) => state.serialize_field("error", error)?,
+            None => state.serialize_field("error", &format!("{:?}", *self.error))?,
+        }
         state.serialize_field("type_id", &self.type_name)?;
         state.serialize_field("info", &self.info)?;
         state.serialize_field("cause", &self.cause)?;
@@ -261,7 +494,7 @@ where
                             if cause.is_some() {
                                 return Err(de::Error::duplicate_field("cause"));
                             }
-                            cause = Some(map.next_value()?);
+                            cause = Some(map.next_value::<Option<Vec<DynTracerError>>>()?);
                         }
                         _ => {
                             let _ = map.next_value::<de::IgnoredAny>()?;
@@ -275,7 +508,9 @@ where
                     error,
                     type_name: type_id,
                     info,
-                    cause,
+                    cause: cause.flatten(),
+                    #[cfg(feature = "with_serde")]
+                    serializer: None,
                 })
             }
         }
@@ -380,6 +615,8 @@ pub struct DynTracerError {
     type_name: Option<String>,
     info: ErrorTracerExtInfo,
     cause: Option<Vec<DynTracerError>>,
+    #[cfg(feature = "with_serde")]
+    serializer: Option<ErrorSerializer>,
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
@@ -402,6 +639,8 @@ impl DynTracerError {
             type_name: None,
             info,
             cause,
+            #[cfg(feature = "with_serde")]
+            serializer: None,
         }
     }
 
@@ -426,7 +665,7 @@ impl DynTracerError {
         info: ErrorTracerExtInfo,
         cause: Option<Vec<DynTracerError>>,
     ) -> Self {
-        #[cfg(feature = "type-registry")]
+        #[cfg(feature = "error-type-registry")]
         {
             use crate::error::type_registry::register_type;
             register_type::<T>();
@@ -437,6 +676,8 @@ impl DynTracerError {
             type_name: Some(type_name::<T>().to_string()),
             info,
             cause,
+            #[cfg(feature = "with_serde")]
+            serializer: None,
         }
     }
     pub fn kind(&self) -> &Box<dyn ErrorDebug> {
@@ -452,16 +693,87 @@ impl DynTracerError {
     }
 
     pub fn downcast_ref<T: ErrorDebug + AnyBoxError>(&self) -> Option<&T> {
-        let error = self.error.as_any();
-        error.downcast_ref::<T>()
+        self.error.as_ref().as_dyn_any().downcast_ref::<T>()
+    }
+
+    /// Walks the cause chain, depth first, and returns the first cause whose concrete error type
+    /// is `T`. The error itself is not considered, use `downcast_ref` for it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::error::tracer::ErrorTracerExtInfo;
+    /// use gearbox::error::{DynTracerError, TracerError};
+    ///
+    /// #[derive(Debug)]
+    /// struct Timeout;
+    ///
+    /// let error = TracerError::from(Timeout).wrap("request failed", ErrorTracerExtInfo::default());
+    /// let error = DynTracerError::from(error);
+    /// assert!(error.downcast_cause::<Timeout>().is_some());
+    /// ```
+    pub fn downcast_cause<T: ErrorDebug + AnyBoxError>(&self) -> Option<&T> {
+        self.cause
+            .iter()
+            .flatten()
+            .find_map(|t| t.downcast_chain::<T>())
+    }
+
+    /// Returns this error if its concrete type is `T`, otherwise the first matching cause.
+    fn downcast_chain<T: ErrorDebug + AnyBoxError>(&self) -> Option<&T> {
+        if self.type_name.as_deref() == Some(type_name::<T>()) {
+            if let Some(error) = self.downcast_ref::<T>() {
+                return Some(error);
+            }
+        }
+        self.downcast_cause::<T>()
     }
 
+    /// Wraps this error as the cause of a new error `err`, building up the cause chain while an
+    /// error propagates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::error::tracer::ErrorTracerExtInfo;
+    /// use gearbox::error::{DynTracerError, TracerError};
+    ///
+    /// let error = DynTracerError::from(TracerError::from("connection refused"))
+    ///     .wrap("fetching the profile failed", ErrorTracerExtInfo::default());
+    /// assert_eq!(error.cause().map(|t| t.len()), Some(1));
+    /// ```
+    pub fn wrap<E: 'static + ErrorDebug>(self, err: E, info: ErrorTracerExtInfo) -> Self {
+        DynTracerError::new(Box::new(err), info, Some(vec![self]))
+    }
+
+    /// Adds `other` to the causes of this error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::error::{DynTracerError, TracerError};
+    ///
+    /// let error = DynTracerError::from(TracerError::from("fetching the profile failed"))
+    ///     .caused_by(TracerError::from("connection refused"));
+    /// assert_eq!(error.cause().map(|t| t.len()), Some(1));
+    /// ```
+    pub fn caused_by<C: Into<DynTracerError>>(mut self, other: C) -> Self {
+        self.cause.get_or_insert_with(Vec::new).push(other.into());
+        self
+    }
+
+    /// Returns the digest of this error and its causes, up to `DEFAULT_DIGEST_DEPTH` levels of
+    /// causes deep.
     pub fn digest(&self) -> ErrorDigest {
+        self.digest_with_depth(DEFAULT_DIGEST_DEPTH)
+    }
+
+    /// Returns the digest of this error and at most `max_depth` levels of its causes. Deeper
+    /// causes are replaced by a single digest with the message `... N more`, where `N` is the
+    /// number of causes left out.
+    pub fn digest_with_depth(&self, max_depth: usize) -> ErrorDigest {
         let (message, detailed_msg) = self.to_error_parts();
-        let stack = self
-            .cause
-            .as_ref()
-            .map(|t| t.iter().map(|t| t.digest()).collect::<Vec<ErrorDigest>>());
+        let stack = digest_causes(self.cause.as_ref(), max_depth);
         ErrorDigest {
             message,
             detailed_msg,
@@ -474,6 +786,82 @@ impl DynTracerError {
     }
 }
 
+/// The number of cause levels `digest` includes, deeper causes are summarized.
+pub const DEFAULT_DIGEST_DEPTH: usize = 64;
+
+fn digest_causes(
+    cause: Option<&Vec<DynTracerError>>,
+    max_depth: usize,
+) -> Option<Vec<ErrorDigest>> {
+    let cause = cause?;
+    if max_depth > 0 || cause.is_empty() {
+        return Some(
+            cause
+                .iter()
+                .map(|t| t.digest_with_depth(max_depth.saturating_sub(1)))
+                .collect(),
+        );
+    }
+
+    // Counted without recursion, the chain may be too deep to recurse over.
+    let mut pending: Vec<&DynTracerError> = cause.iter().collect();
+    let mut count = 0;
+    while let Some(error) = pending.pop() {
+        count += 1;
+        pending.extend(error.cause.iter().flatten());
+    }
+    Some(vec![ErrorDigest::new(
+        format!("... {} more", count),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )])
+}
+
+/// Collects the values of `results`, or returns a single error with every failure as its cause.
+///
+/// All results are consumed, so the aggregate error holds every failure and not just the first
+/// one, e.g. to report all invalid fields of a form at once.
+///
+/// # Examples
+///
+/// ```
+/// use gearbox::error::{collect_errors, TracerError};
+///
+/// let inputs = ["1", "two", "3", "four"];
+/// let error = collect_errors(inputs.iter().map(|t| {
+///     t.parse::<u32>()
+///         .map_err(|_| TracerError::from(format!("{} is not a number", t)))
+/// }))
+/// .unwrap_err();
+/// assert_eq!(error.cause().map(|t| t.len()), Some(2));
+/// ```
+pub fn collect_errors<T, E: Into<DynTracerError>>(
+    results: impl IntoIterator<Item = Result<T, E>>,
+) -> Result<Vec<T>, DynTracerError> {
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+    for result in results {
+        match result {
+            Ok(value) => values.push(value),
+            Err(error) => errors.push(error.into()),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(DynTracerError::new(
+            Box::new(format!("{} errors occurred", errors.len())),
+            ErrorTracerExtInfo::default(),
+            Some(errors),
+        ))
+    }
+}
+
 // impl<T> From<T> for DynTracerError
 // where
 //     T: 'static + ErrorDebug,
@@ -483,6 +871,9 @@ impl DynTracerError {
 //     }
 // }
 
+/// Serializes the error like `TracerError` does. The error field is only written as a structured
+/// value if the `TracerError` it was converted from opted in with `with_structured_error`, and
+/// otherwise as the debug string of the error.
 #[cfg(feature = "with_serde")]
 impl Serialize for DynTracerError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -490,9 +881,10 @@ impl Serialize for DynTracerError {
         S: Serializer,
     {
         let mut state = serializer.serialize_struct("DynTracerError", 4)?;
-
-        // TODO! Serialize the error field properly
-        state.serialize_field("error", &format!("{:?}", self.error))?;
+        match self.serializer.and_then(|f| f(self.error.as_ref())) {
+            Some(error) => state.serialize_field("error", error)?,
+            None => state.serialize_field("error", &format!("{:?}", self.error))?,
+        }
         state.serialize_field("type_id", &self.type_name)?;
         state.serialize_field("info", &self.info)?;
         state.serialize_field("cause", &self.cause)?;
@@ -557,7 +949,7 @@ impl<'de> Deserialize<'de> for DynTracerError {
                             if cause.is_some() {
                                 return Err(de::Error::duplicate_field("cause"));
                             }
-                            cause = Some(map.next_value()?);
+                            cause = Some(map.next_value::<Option<Vec<DynTracerError>>>()?);
                         }
                         _ => {
                             let _ = map.next_value::<de::IgnoredAny>()?;
@@ -565,13 +957,21 @@ impl<'de> Deserialize<'de> for DynTracerError {
                     }
                 }
 
-                let error = error.ok_or_else(|| de::Error::missing_field("error"))?;
+                let error: ErrorValue = error.ok_or_else(|| de::Error::missing_field("error"))?;
                 let info = info.ok_or_else(|| de::Error::missing_field("info"))?;
+                // Errors serialized as debug strings are restored as such, structured errors are
+                // kept as generic values and serialized as structured values again.
+                let (error, serializer): (Box<dyn ErrorDebug>, Option<ErrorSerializer>) =
+                    match error {
+                        ErrorValue::String(error) => (Box::new(error), None),
+                        error => (Box::new(error), Some(serialize_as::<ErrorValue>)),
+                    };
                 Ok(DynTracerError {
-                    error: Box::new(error),
+                    error,
                     type_name,
                     info,
-                    cause,
+                    cause: cause.flatten(),
+                    serializer,
                 })
             }
         }
@@ -589,7 +989,7 @@ where
     T: 'static + ErrorDebug,
 {
     fn from((err, info): (T, ErrorTracerExtInfo)) -> Self {
-        #[cfg(feature = "type-registry")]
+        #[cfg(feature = "error-type-registry")]
         {
             use crate::error::type_registry::register_type;
             register_type::<T>();
@@ -600,10 +1000,31 @@ where
             type_name,
             info,
             cause: None,
+            #[cfg(feature = "with_serde")]
+            serializer: None,
         }
     }
 }
 
+/// Converts `std::io::Error` so it can be propagated with `?`. The file and line of the caller are
+/// recorded in the `ErrorTracerExtInfo`.
+impl From<std::io::Error> for DynTracerError {
+    #[track_caller]
+    fn from(err: std::io::Error) -> Self {
+        DynTracerError::from((err, ErrorTracerExtInfo::caller()))
+    }
+}
+
+/// Converts `reqwest::Error` so it can be propagated with `?`. The file and line of the caller are
+/// recorded in the `ErrorTracerExtInfo`.
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Error> for DynTracerError {
+    #[track_caller]
+    fn from(err: reqwest::Error) -> Self {
+        DynTracerError::from((err, ErrorTracerExtInfo::caller()))
+    }
+}
+
 impl Display for DynTracerError {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         let file = self.info.file().cloned().unwrap_or("<Unknown>".to_string());
@@ -642,7 +1063,13 @@ where
         let info = err.info.clone();
         let cause = err.cause;
 
-        DynTracerError::new(err.error, info, cause)
+        #[allow(unused_mut)]
+        let mut error = DynTracerError::new(err.error, info, cause);
+        #[cfg(feature = "with_serde")]
+        {
+            error.serializer = err.serializer;
+        }
+        error
     }
 }
 
@@ -714,11 +1141,7 @@ impl ErrorDigest {
             file = self.file.as_deref().unwrap_or("<Unknown>"),
             line = self.line.map(|t| t.to_string()).unwrap_or("-1".to_string()),
             subsystem = self.subsystem.as_deref().unwrap_or("<Unknown>"),
-            stack = self
-                .stack
-                .as_ref()
-                .map(|t| t.iter().map(|t| t.to_stack_line()).collect::<Vec<String>>().join("\n"))
-                .unwrap_or("".to_string())
+            stack = self.stack_lines(ErrorDigest::to_stack_line).join("\n")
         )
     }
 
@@ -730,12 +1153,50 @@ impl ErrorDigest {
             file = self.file.as_deref().unwrap_or("<Unknown>"),
             line = self.line.map(|t| t.to_string()).unwrap_or("-1".to_string()),
             subsystem = self.subsystem.as_deref().unwrap_or("<Unknown>"),
-            stack = self
+            stack = self.stack_lines(ErrorDigest::to_detailed).join("\n")
+        )
+    }
+
+    /// Returns the digest as a JSON object with the fields `message`, `code`, `file`, `line`,
+    /// `subsystem` and `stack`. The `stack` field holds the digests of the causes in the same
+    /// shape, or `null` if the error has no stack.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::error::tracer::ErrorDigest;
+    ///
+    /// let digest = ErrorDigest::new("failed".to_string(), None, Some(7), None, None, None, None);
+    /// let json = digest.to_json();
+    /// assert_eq!(json["message"], "failed");
+    /// assert_eq!(json["line"], 7);
+    /// ```
+    #[cfg(feature = "serde_json")]
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "message": self.message,
+            "code": self.code,
+            "file": self.file,
+            "line": self.line,
+            "subsystem": self.subsystem,
+            "stack": self
                 .stack
                 .as_ref()
-                .map(|t| t.iter().map(|t| t.to_detailed()).collect::<Vec<_>>().join("\n"))
-                .unwrap_or("".to_string())
-        )
+                .map(|t| t.iter().map(ErrorDigest::to_json).collect::<Vec<_>>()),
+        })
+    }
+
+    /// Renders every digest of the stack, depth first, so nested causes are included.
+    fn stack_lines(&self, render: fn(&ErrorDigest) -> String) -> Vec<String> {
+        self.stack
+            .iter()
+            .flatten()
+            .flat_map(|t| {
+                let mut lines = vec![render(t)];
+                lines.extend(t.stack_lines(render));
+                lines
+            })
+            .collect()
     }
 }
 
@@ -916,6 +1377,244 @@ mod tests {
         assert!(tracer_error.has_cause());
     }
 
+    #[test]
+    fn test_cause_chain() {
+        let error = TracerError::from(TestError::new("Root Error"))
+            .wrap(
+                TestError::new("Middle Error"),
+                ErrorTracerExtInfo::default().with_line(2),
+            )
+            .wrap(
+                TestError::new("Top Error"),
+                ErrorTracerExtInfo::default().with_line(3),
+            );
+        let error =
+            DynTracerError::from(error).caused_by(TracerError::from(TestError::new("Side Error")));
+
+        let digest = error.digest();
+        let mut depth = 1;
+        let mut level = &digest;
+        while let Some(next) = level.stack.as_ref().and_then(|t| t.first()) {
+            depth += 1;
+            level = next;
+        }
+        assert_eq!(depth, 3);
+        assert_eq!(digest.stack.as_ref().map(|t| t.len()), Some(2));
+
+        let stack = digest.to_stack();
+        assert!(stack.contains("Middle Error"));
+        assert!(stack.contains("Root Error"));
+        assert!(stack.contains("Side Error"));
+    }
+
+    #[derive(Debug)]
+    struct TimeoutError;
+
+    #[test]
+    fn test_downcast_cause() {
+        let error = DynTracerError::from(
+            TracerError::from(TimeoutError)
+                .wrap(
+                    TestError::new("Request Error"),
+                    ErrorTracerExtInfo::default(),
+                )
+                .wrap(TestError::new("Top Error"), ErrorTracerExtInfo::default()),
+        );
+
+        assert!(error.downcast_cause::<TimeoutError>().is_some());
+        assert_eq!(
+            error.downcast_cause::<TestError>().map(|t| t.message),
+            Some("Request Error")
+        );
+
+        let error = TracerError::from(TestError::new("Request Error"))
+            .wrap(TestError::new("Top Error"), ErrorTracerExtInfo::default());
+        assert!(error.downcast_cause::<TimeoutError>().is_none());
+        assert_eq!(
+            error.downcast_cause::<TestError>().map(|t| t.message),
+            Some("Request Error")
+        );
+    }
+
+    #[cfg(feature = "with_serde")]
+    #[derive(Debug, PartialEq, derive::Serialize, derive::Deserialize)]
+    struct StructuredError {
+        code: u16,
+        reason: String,
+    }
+
+    #[test]
+    #[cfg(all(feature = "with_serde", feature = "serde_json"))]
+    fn test_structured_error_round_trip() {
+        let structured = || StructuredError {
+            code: 404,
+            reason: "missing".to_string(),
+        };
+        let expected = serde_json::json!({"code": 404, "reason": "missing"});
+
+        let error = TracerError::from(structured()).with_structured_error();
+        assert_eq!(serde_json::to_value(&error).unwrap()["error"], expected);
+        let json = serde_json::to_string(&error).unwrap();
+        let decoded: TracerError<StructuredError> = serde_json::from_str(&json).unwrap();
+        assert_eq!(**decoded.kind(), structured());
+
+        let error = DynTracerError::from(TracerError::from(structured()).with_structured_error());
+        let json = serde_json::to_string(&error).unwrap();
+        let decoded: DynTracerError = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_value(&decoded).unwrap()["error"], expected);
+
+        let error = TracerError::from(TestError::new("Test Error"));
+        let json = serde_json::to_value(&error).unwrap();
+        assert!(json["error"].as_str().unwrap().contains("Test Error"));
+    }
+
+    #[test]
+    #[cfg(feature = "serde_json")]
+    fn test_digest_to_json() {
+        let error = TracerError::from(TestError::new("Root Error")).wrap(
+            TestError::new("Top Error"),
+            ErrorTracerExtInfo::default()
+                .with_code(500)
+                .with_file("top.rs")
+                .with_line(3)
+                .with_subsystem("top"),
+        );
+
+        let json = error.digest().to_json();
+        assert_eq!(json["code"], 500);
+        assert_eq!(json["file"], "top.rs");
+        assert_eq!(json["line"], 3);
+        assert_eq!(json["subsystem"], "top");
+        assert!(json["message"].as_str().unwrap().contains("Top Error"));
+
+        let stack = json["stack"].as_array().unwrap();
+        assert_eq!(stack.len(), 1);
+        assert!(stack[0]["message"].as_str().unwrap().contains("Root Error"));
+        assert!(stack[0]["code"].is_null());
+        assert!(stack[0]["stack"].is_null());
+    }
+
+    #[test]
+    fn test_err_with_code() {
+        let error = crate::tracer_err_with_code!(TestError::new("Not Found"), 404);
+        let digest = error.digest();
+        assert_eq!(digest.code, Some(404));
+        assert_eq!(digest.file.as_deref(), Some(file!()));
+        assert_eq!(digest.subsystem.as_deref(), Some(module_path!()));
+        assert_eq!(error.kind().message, "Not Found");
+
+        let error = crate::tracer_dyn_err_with_code!(TestError::new("Conflict"), 409u16);
+        assert_eq!(error.digest().code, Some(409));
+        assert_eq!(
+            error.downcast_ref::<TestError>().map(|t| t.message),
+            Some("Conflict")
+        );
+
+        // The other macros leave the code unset.
+        assert_eq!(
+            crate::tracer_err!(TestError::new("Plain")).digest().code,
+            None
+        );
+        assert_eq!(crate::tracer_dyn_err!("Plain").digest().code, None);
+    }
+
+    #[test]
+    fn test_collect_errors() {
+        let inputs = ["valid", "too short", "invalid"];
+        let results = inputs.iter().map(|t| match *t {
+            "valid" => Ok(t.len()),
+            message => Err(TracerError::from(TestError::new(message))),
+        });
+        let error = collect_errors(results).unwrap_err();
+        let causes = error.cause().unwrap();
+        assert_eq!(causes.len(), 2);
+        assert_eq!(
+            causes
+                .iter()
+                .map(|t| t.downcast_ref::<TestError>().unwrap().message)
+                .collect::<Vec<_>>(),
+            vec!["too short", "invalid"]
+        );
+        assert_eq!(
+            error.downcast_ref::<String>().map(String::as_str),
+            Some("2 errors occurred")
+        );
+
+        let values = collect_errors(["a", "bb"].iter().map(|t| Ok::<_, DynTracerError>(t.len())));
+        assert_eq!(values.unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_from_io_error() {
+        // The message of the io error is the line of the `?`.
+        fn read() -> Result<(), DynTracerError> {
+            use std::io::{Error, ErrorKind};
+            Err(Error::new(ErrorKind::NotFound, line!().to_string()))?;
+            Ok(())
+        }
+
+        let error = read().unwrap_err();
+        let io_error = error.downcast_ref::<std::io::Error>().unwrap();
+        assert_eq!(io_error.kind(), std::io::ErrorKind::NotFound);
+        assert_eq!(error.digest().file.as_deref(), Some(file!()));
+        assert_eq!(error.digest().line, io_error.to_string().parse().ok());
+        assert_eq!(error.digest().code, None);
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[test]
+    fn test_from_reqwest_error() {
+        fn parse() -> Result<reqwest::Url, DynTracerError> {
+            let client = reqwest::Client::new();
+            Ok(client.get("not a url").build()?.url().clone())
+        }
+
+        let error = parse().unwrap_err();
+        let reqwest_error = error.downcast_ref::<reqwest::Error>().unwrap();
+        assert!(reqwest_error.is_builder());
+        assert_eq!(error.digest().file.as_deref(), Some(file!()));
+    }
+
+    #[test]
+    fn test_digest_depth() {
+        let mut error = DynTracerError::from(TracerError::from(TestError::new("root")));
+        for _ in 1..200 {
+            error = error.wrap(TestError::new("wrapped"), ErrorTracerExtInfo::default());
+        }
+
+        // Follows the first cause of every level, returning the depth and the deepest digest.
+        fn deepest(digest: &ErrorDigest) -> (usize, &ErrorDigest) {
+            let mut depth = 0;
+            let mut digest = digest;
+            while let Some(next) = digest.stack.as_ref().and_then(|t| t.first()) {
+                depth += 1;
+                digest = next;
+            }
+            (depth, digest)
+        }
+
+        let digest = error.digest();
+        let (depth, last) = deepest(&digest);
+        assert_eq!(depth, DEFAULT_DIGEST_DEPTH + 1);
+        assert_eq!(
+            last.message,
+            format!("... {} more", 199 - DEFAULT_DIGEST_DEPTH)
+        );
+        assert!(last.stack.is_none());
+
+        let digest = error.digest_with_depth(2);
+        let (depth, last) = deepest(&digest);
+        assert_eq!(depth, 3);
+        assert_eq!(last.message, "... 197 more");
+        assert_eq!(digest.stack_lines(ErrorDigest::to_simple).len(), 3);
+
+        // Chains within the limit are not truncated.
+        let digest = error.digest_with_depth(500);
+        let (depth, last) = deepest(&digest);
+        assert_eq!(depth, 199);
+        assert!(last.message.contains("root"));
+    }
+
     // #[test]
     // fn test_cause() {
     //     let cause_error =
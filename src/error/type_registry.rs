@@ -20,6 +20,27 @@ pub fn get_type_name(id: &TypeId) -> Option<String> {
         .and_then(|t| t.get_type_name(id).map(|t| t.clone()))
 }
 
+/// Returns the names of all registered types, sorted.
+///
+/// Types are registered when a `TracerError` or `DynTracerError` is created from them, which is
+/// useful to check why a `downcast_ref` by name does not find a type.
+pub fn registered_types() -> Vec<&'static str> {
+    TYPE_REGISTRY
+        .read()
+        .as_ref()
+        .map(|t| t.registered_types())
+        .unwrap_or_default()
+}
+
+/// Returns whether a type with the full name `name`, as returned by `core::any::type_name`, is
+/// registered.
+pub fn is_registered(name: &str) -> bool {
+    TYPE_REGISTRY
+        .read()
+        .as_ref()
+        .is_some_and(|t| t.is_registered(name))
+}
+
 pub fn register_type<T: 'static>() {
     TYPE_REGISTRY
         .write()
@@ -28,13 +49,13 @@ pub fn register_type<T: 'static>() {
 }
 
 #[derive(Default)]
-pub struct TypeRegistry(HashMap<String, TypeId>, HashMap<TypeId, String>);
+pub struct TypeRegistry(HashMap<String, TypeId>, HashMap<TypeId, &'static str>);
 
 impl TypeRegistry {
     pub fn register_type<T: 'static>(&mut self) {
-        let type_name = type_name::<T>().to_string();
+        let type_name = type_name::<T>();
         let type_id = TypeId::of::<T>();
-        self.0.insert(type_name.clone(), type_id);
+        self.0.insert(type_name.to_string(), type_id);
         self.1.insert(type_id, type_name);
     }
 
@@ -42,7 +63,19 @@ impl TypeRegistry {
         self.0.get(type_name).cloned()
     }
     pub fn get_type_name(&self, type_id: &TypeId) -> Option<String> {
-        self.1.get(type_id).cloned()
+        self.1.get(type_id).map(|t| t.to_string())
+    }
+
+    /// Returns the names of all registered types, sorted.
+    pub fn registered_types(&self) -> Vec<&'static str> {
+        let mut types: Vec<&'static str> = self.1.values().copied().collect();
+        types.sort_unstable();
+        types
+    }
+
+    /// Returns whether a type with the full name `name` is registered.
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.0.contains_key(name)
     }
 }
 
@@ -59,3 +92,45 @@ impl DerefMut for TypeRegistry {
         &mut self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Registered;
+    struct NotRegistered;
+
+    #[test]
+    fn test_registered_types() {
+        let mut registry = TypeRegistry::default();
+        registry.register_type::<Registered>();
+        registry.register_type::<u8>();
+        assert_eq!(
+            registry.registered_types(),
+            vec![type_name::<Registered>(), "u8"]
+        );
+        assert!(registry.is_registered(type_name::<Registered>()));
+        assert!(!registry.is_registered(type_name::<NotRegistered>()));
+        assert!(!registry.is_registered("Registered"));
+    }
+
+    #[cfg(feature = "error-tracer")]
+    #[test]
+    fn test_dyn_tracer_error_registers_type() {
+        use crate::error::tracer::{DynTracerError, ErrorTracerExtInfo};
+
+        #[derive(Debug)]
+        struct ValidationError;
+
+        let name = type_name::<ValidationError>();
+        assert!(!is_registered(name));
+        let _error = DynTracerError::new(
+            Box::new(ValidationError),
+            ErrorTracerExtInfo::new(None, None, None, None),
+            None,
+        );
+        assert!(is_registered(name));
+        assert!(registered_types().contains(&name));
+        assert_eq!(get_type_id(name), Some(TypeId::of::<ValidationError>()));
+    }
+}
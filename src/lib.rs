@@ -323,7 +323,9 @@ extern crate std;
 //
 // Dependencies already in CORE
 //
-extern crate alloc;
+// Public so the exported macros can reach it through `$crate::alloc`.
+#[doc(hidden)]
+pub extern crate alloc;
 
 //
 // Optional Dependencies
@@ -338,6 +340,8 @@ extern crate bs58;
 extern crate bson;
 #[cfg(feature = "bytes")]
 extern crate bytes;
+#[cfg(feature = "csv")]
+extern crate csv;
 #[cfg(feature = "derive_more")]
 #[macro_use]
 extern crate derive_more;
@@ -565,10 +569,10 @@ pub mod prelude {
     };
 }
 
-fn lib_info() -> String {
+fn lib_info() -> alloc::string::String {
     const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-    CRATE_VERSION.to_string()
+    alloc::string::ToString::to_string(CRATE_VERSION)
 }
 
 #[cfg(test)]
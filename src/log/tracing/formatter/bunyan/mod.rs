@@ -1,7 +1,8 @@
+use crate::log::tracing::formatter::redact;
 use crate::log::tracing::layer::{LogLayer, Storage, Type};
 use crate::log::tracing::{LogFormatter, Value};
 use crate::time::{DateTime, SecondsFormat};
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use serde::ser::{SerializeMap, Serializer};
 use tracing::{Event, Level, Subscriber};
 use tracing_subscriber::fmt::MakeWriter;
@@ -41,6 +42,7 @@ pub struct Bunyan {
     bunyan_version: u8,
     name: String,
     default_fields: HashMap<String, Value>,
+    redacted_fields: HashSet<String>,
 }
 
 impl Bunyan {
@@ -59,9 +61,16 @@ impl Bunyan {
                 .into_owned(),
             bunyan_version: 0,
             default_fields,
+            redacted_fields: HashSet::new(),
         }
     }
 
+    /// Replaces the values of the given fields with `[REDACTED]` in every log entry.
+    pub fn with_redacted_fields(mut self, redacted_fields: HashSet<String>) -> Self {
+        self.redacted_fields = redacted_fields;
+        self
+    }
+
     fn serialize_bunyan_core_fields(
         &self,
         map_serializer: &mut impl SerializeMap<Error = serde_json::Error>,
@@ -102,7 +111,7 @@ impl Bunyan {
         // Add all default fields
         for (key, value) in self.default_fields.iter() {
             if !BUNYAN_RESERVED_FIELDS.contains(&key.as_str()) {
-                map_serializer.serialize_entry(key, &serde_json::Value::from(value))?;
+                map_serializer.serialize_entry(key, &redact(&self.redacted_fields, key, value))?;
             } else {
                 tracing::info!(
                     "{} is a reserved field in the bunyan log format. Skipping it.",
@@ -115,7 +124,8 @@ impl Bunyan {
         if let Some(visitor) = extensions.get::<Storage>() {
             for (key, value) in visitor.values() {
                 if !BUNYAN_RESERVED_FIELDS.contains(key) {
-                    map_serializer.serialize_entry(key, &serde_json::Value::from(value))?;
+                    map_serializer
+                        .serialize_entry(key, &redact(&self.redacted_fields, key, value))?;
                 } else {
                     tracing::info!(
                         "{} is a reserved field in the bunyan log format. Skipping it.",
@@ -137,6 +147,7 @@ impl Default for Bunyan {
             bunyan_version: 0,
             name: "".to_string(),
             default_fields: Default::default(),
+            redacted_fields: Default::default(),
         }
     }
 }
@@ -192,6 +203,7 @@ impl LogFormatter for Bunyan {
             bunyan_version: 0,
             name: layer.application().clone().unwrap_or("app".to_string()),
             default_fields: Default::default(),
+            redacted_fields: self.redacted_fields.clone(),
         }
     }
 
@@ -228,7 +240,7 @@ impl LogFormatter for Bunyan {
             for (key, value) in self.default_fields.iter().filter(|(key, _)| {
                 key.as_str() != "message" && !BUNYAN_RESERVED_FIELDS.contains(&key.as_str())
             }) {
-                map_serializer.serialize_entry(key, &serde_json::Value::from(value))?;
+                map_serializer.serialize_entry(key, &redact(&self.redacted_fields, key, value))?;
             }
 
             // Add all the other fields associated with the event, expect the message we already used.
@@ -237,7 +249,7 @@ impl LogFormatter for Bunyan {
                 .iter()
                 .filter(|(&key, _)| key != "message" && !BUNYAN_RESERVED_FIELDS.contains(&key))
             {
-                map_serializer.serialize_entry(key, &serde_json::Value::from(value))?;
+                map_serializer.serialize_entry(key, &redact(&self.redacted_fields, key, value))?;
             }
 
             // Add all the fields from the current span, if we have one.
@@ -246,7 +258,8 @@ impl LogFormatter for Bunyan {
                 if let Some(visitor) = extensions.get::<Storage>() {
                     for (key, value) in visitor.values() {
                         if !BUNYAN_RESERVED_FIELDS.contains(key) {
-                            map_serializer.serialize_entry(key, &serde_json::Value::from(value))?;
+                            map_serializer
+                                .serialize_entry(key, &redact(&self.redacted_fields, key, value))?;
                         } else {
                             tracing::info!(
                                 "{} is a reserved field in the bunyan log format. Skipping it.",
@@ -276,3 +289,39 @@ impl LogFormatter for Bunyan {
             .unwrap_or_else(|e| e)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log::tracing::formatter::test_util::Buffer;
+    use crate::log::tracing::layer::StorageLayer;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_redacted_fields() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let formatter = Bunyan::default().with_redacted_fields(HashSet::from([
+            "password".to_string(),
+            "authorization".to_string(),
+        ]));
+        let subscriber = tracing_subscriber::registry()
+            .with(StorageLayer)
+            .with(LogLayer::new(
+                Some("gearbox".to_string()),
+                move || writer.clone(),
+                formatter,
+            ));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(user = "alice", password = "hunter2", "login");
+        });
+
+        let output = buffer.contents();
+        let json: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(json["msg"], "login");
+        assert_eq!(json["user"], "alice");
+        assert_eq!(json["password"], "[REDACTED]");
+        assert!(!output.contains("hunter2"));
+    }
+}
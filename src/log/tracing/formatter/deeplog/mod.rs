@@ -5,6 +5,7 @@ use crate::{
     collections::HashMap,
     log::tracing::{
         entity::syslog::Severity,
+        formatter::REDACTED,
         layer::{LogLayer, Storage, Type},
         LogFormatter, Value,
     },
@@ -21,6 +22,7 @@ use core::{
     ops::{Deref, DerefMut},
 };
 use futures::StreamExt;
+use hashbrown::HashSet;
 use serde_derive::{Deserialize, Serialize};
 use tracing::{Event, Subscriber};
 use tracing_subscriber::{
@@ -33,6 +35,8 @@ pub enum LogStyleOutput {
     Full,
     Minimal,
     Human,
+    /// Space separated `key=value` pairs, as expected by Loki.
+    Logfmt,
 }
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct DeepLogFormatter {
@@ -41,6 +45,8 @@ pub struct DeepLogFormatter {
     #[serde(skip)]
     pub output_style: Option<LogStyleOutput>,
     #[serde(skip)]
+    pub redacted_fields: HashSet<String>,
+    #[serde(skip)]
     #[cfg(feature = "net-endpoint-config")]
     pub endpoint: Option<EndpointConfig>,
 }
@@ -55,6 +61,7 @@ impl DeepLogFormatter {
         Self {
             log: Some(DeepLog::default()),
             output_style: None,
+            redacted_fields: HashSet::new(),
             #[cfg(feature = "net-endpoint-config")]
             endpoint: None,
         }
@@ -65,6 +72,20 @@ impl DeepLogFormatter {
         self
     }
 
+    /// Replaces the values of the given payload fields with `[REDACTED]` in every log entry.
+    pub fn with_redacted_fields(mut self, redacted_fields: HashSet<String>) -> Self {
+        self.redacted_fields = redacted_fields;
+        self
+    }
+
+    fn redact_payload(&self, deeplog: &mut DeepLog) {
+        for (key, value) in deeplog.payload_data.iter_mut() {
+            if self.redacted_fields.contains(key) {
+                *value = serde_json::Value::from(REDACTED);
+            }
+        }
+    }
+
     fn format_event_message<
         S: Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
     >(
@@ -95,6 +116,64 @@ impl DeepLogFormatter {
         message
     }
 
+    /// Renders `deeplog` as a logfmt line: timestamp, severity, msg and caller first, followed by
+    /// the payload data in key order.
+    fn format_logfmt(deeplog: &DeepLog) -> String {
+        let mut pairs = vec![
+            (
+                "timestamp".to_string(),
+                deeplog
+                    .timestamps
+                    .as_ref()
+                    .and_then(|t| t.timestamp.as_ref())
+                    .map(|t| t.to_rfc3339())
+                    .unwrap_or_default(),
+            ),
+            (
+                "severity".to_string(),
+                deeplog
+                    .severity
+                    .clone()
+                    .unwrap_or(Severity::Error)
+                    .to_string(),
+            ),
+            (
+                "msg".to_string(),
+                deeplog.message.clone().unwrap_or("No message".to_string()),
+            ),
+        ];
+        if let Some(caller) = &deeplog.caller {
+            let file = caller.file.clone().unwrap_or_default();
+            let caller = match caller.line {
+                Some(line) => format!("{}:{}", file, line),
+                None => file,
+            };
+            pairs.push(("caller".to_string(), caller));
+        }
+        for (key, value) in &deeplog.payload_data {
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                value => value.to_string(),
+            };
+            pairs.push((key.clone(), value));
+        }
+
+        pairs
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, Self::logfmt_value(value)))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// Quotes a logfmt value if it is empty or contains spaces, quotes or `=`.
+    fn logfmt_value(value: &str) -> String {
+        if !value.is_empty() && !value.contains(|c: char| c.is_whitespace() || c == '"' || c == '=')
+        {
+            return value.to_string();
+        }
+        format!(r#""{}""#, value.replace('\\', r"\\").replace('"', r#"\""#))
+    }
+
     fn format_span_context<S: Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>>(
         &self,
         span: &SpanRef<S>,
@@ -109,6 +188,7 @@ impl Default for DeepLogFormatter {
         Self {
             log: Some(DeepLog::default()),
             output_style: None,
+            redacted_fields: HashSet::new(),
             #[cfg(feature = "net-endpoint-config")]
             endpoint: None,
         }
@@ -116,10 +196,15 @@ impl Default for DeepLogFormatter {
 }
 
 impl fmt::Display for DeepLogFormatter {
+    /// Writes the current log entry as JSON, the same representation as `LogStyleOutput::Full`.
     fn fmt(&self, f: &mut fmt::Formatter) -> core::fmt::Result {
-        //<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog - ID47 [exampleSDID@32473 iut="3" eventSource="Application" eventID="1011"] BOMAn application event log entry...
-        let default_string = "-".to_string();
-        write!(f, "")
+        match &self.log {
+            Some(deeplog) => {
+                let json = serde_json::to_string(deeplog).map_err(|_| fmt::Error)?;
+                write!(f, "{}", json)
+            }
+            None => write!(f, "{{}}"),
+        }
     }
 }
 
@@ -131,6 +216,7 @@ impl LogFormatter for DeepLogFormatter {
         Self {
             log: Some(DeepLog::default()),
             output_style: self.output_style.clone(),
+            redacted_fields: self.redacted_fields.clone(),
 
             #[cfg(feature = "net-endpoint-config")]
             endpoint: self.endpoint.clone(),
@@ -211,7 +297,8 @@ impl LogFormatter for DeepLogFormatter {
                 });
             }
 
-            deeplog.clone()
+            self.redact_payload(&mut deeplog);
+            deeplog
         } else {
             let mut deeplog = DeepLog::default();
             deeplog.message = Some("Internal Log error".to_string());
@@ -281,6 +368,7 @@ impl LogFormatter for DeepLogFormatter {
                 // Format the log message with aligned severity and fixed-length file path
                 format!("{} {:<15} {}:{} {}", timestamp, severity, file, line, msg)
             }
+            LogStyleOutput::Logfmt => Self::format_logfmt(&deeplog),
         }
     }
 
@@ -359,3 +447,78 @@ impl DerefMut for StructuredData {
         &mut self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let mut formatter = DeepLogFormatter::default();
+        if let Some(deeplog) = &mut formatter.log {
+            deeplog.message = Some("service started".to_string());
+            deeplog.severity = Some(Severity::Warning);
+        }
+
+        let output = formatter.to_string();
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(json["message"], "service started");
+        assert_eq!(json["severity"], "Warning");
+
+        formatter.log = None;
+        assert_eq!(formatter.to_string(), "{}");
+    }
+
+    #[test]
+    fn test_redacted_fields() {
+        let formatter = DeepLogFormatter::default()
+            .with_redacted_fields(HashSet::from(["password".to_string()]));
+        let mut deeplog = DeepLog::default();
+        deeplog
+            .payload_data
+            .insert("user".to_string(), serde_json::Value::from("alice"));
+        deeplog
+            .payload_data
+            .insert("password".to_string(), serde_json::Value::from("hunter2"));
+
+        formatter.redact_payload(&mut deeplog);
+        assert_eq!(deeplog.payload_data["user"], "alice");
+        assert_eq!(deeplog.payload_data["password"], "[REDACTED]");
+    }
+
+    #[test]
+    fn test_logfmt() {
+        let mut deeplog = DeepLog {
+            message: Some("service started".to_string()),
+            severity: Some(Severity::Warning),
+            timestamps: Some(Timestamps {
+                received_timestamp: None,
+                timestamp: Some(DateTime::from_secs(1700000000)),
+            }),
+            caller: Some(Caller {
+                function: None,
+                file: Some("src/main.rs".to_string()),
+                line: Some(12),
+            }),
+            ..Default::default()
+        };
+        deeplog
+            .payload_data
+            .insert("user".to_string(), serde_json::Value::from("alice"));
+        deeplog
+            .payload_data
+            .insert("attempts".to_string(), serde_json::Value::from(3));
+        deeplog.payload_data.insert(
+            "query".to_string(),
+            serde_json::Value::from(r#"name="a b""#),
+        );
+
+        assert_eq!(
+            DeepLogFormatter::format_logfmt(&deeplog),
+            format!(
+                r#"timestamp={} severity=Warning msg="service started" caller=src/main.rs:12 attempts=3 query="name=\"a b\"" user=alice"#,
+                DateTime::from_secs(1700000000).to_rfc3339()
+            )
+        );
+    }
+}
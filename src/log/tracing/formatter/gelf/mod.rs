@@ -0,0 +1,336 @@
+use crate::log::tracing::formatter::redact;
+use crate::log::tracing::layer::{LogLayer, Storage, Type};
+use crate::log::tracing::{LogFormatter, Value};
+use crate::time::DateTime;
+use hashbrown::{HashMap, HashSet};
+use serde::ser::{SerializeMap, Serializer};
+use tracing::{Event, Metadata, Subscriber};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::registry::{LookupSpan, SpanRef};
+
+/// Keys for the core fields of the GELF format (https://go2docs.graylog.org/current/getting_in_log_data/gelf.html)
+const GELF_VERSION: &str = "1.1";
+const VERSION: &str = "version";
+const HOST: &str = "host";
+const SHORT_MESSAGE: &str = "short_message";
+const FULL_MESSAGE: &str = "full_message";
+const TIMESTAMP: &str = "timestamp";
+const LEVEL: &str = "level";
+const FACILITY: &str = "facility";
+const LINE: &str = "line";
+const FILE: &str = "file";
+/// `_id` is not allowed as additional field.
+const ID: &str = "id";
+/// Additional fields written by the formatter itself, as `_application` and `_target`.
+const APPLICATION: &str = "application";
+const TARGET: &str = "target";
+
+const GELF_RESERVED_FIELDS: [&str; 12] = [
+    VERSION,
+    HOST,
+    SHORT_MESSAGE,
+    FULL_MESSAGE,
+    TIMESTAMP,
+    LEVEL,
+    FACILITY,
+    LINE,
+    FILE,
+    ID,
+    APPLICATION,
+    TARGET,
+];
+
+/// Convert from log levels to the syslog levels used by GELF.
+fn to_gelf_level(level: &tracing::Level) -> u8 {
+    match *level {
+        tracing::Level::ERROR => 3,
+        tracing::Level::WARN => 4,
+        tracing::Level::INFO => 6,
+        tracing::Level::DEBUG => 7,
+        tracing::Level::TRACE => 7,
+    }
+}
+
+/// This layer is exclusively concerned with formatting information using the [GELF format](https://go2docs.graylog.org/current/getting_in_log_data/gelf.html).
+/// It relies on the upstream `StorageLayer` to get access to the fields attached to each span,
+/// which are written as additional fields prefixed with `_`.
+#[derive(Debug, Clone)]
+pub struct Gelf {
+    hostname: String,
+    name: String,
+    default_fields: HashMap<String, Value>,
+    redacted_fields: HashSet<String>,
+}
+
+impl Gelf {
+    /// Create a new `Gelf` formatter.
+    pub fn new(name: String) -> Self {
+        Self::with_default_fields(name, HashMap::new())
+    }
+
+    pub fn with_default_fields(name: String, default_fields: HashMap<String, Value>) -> Self {
+        Self {
+            name,
+            hostname: crate::net::hostname::gethostname()
+                .to_string_lossy()
+                .into_owned(),
+            default_fields,
+            redacted_fields: HashSet::new(),
+        }
+    }
+
+    /// Replaces the values of the given fields with `[REDACTED]` in every log entry.
+    pub fn with_redacted_fields(mut self, redacted_fields: HashSet<String>) -> Self {
+        self.redacted_fields = redacted_fields;
+        self
+    }
+
+    fn serialize_gelf_core_fields(
+        &self,
+        map_serializer: &mut impl SerializeMap<Error = serde_json::Error>,
+        message: &str,
+        metadata: &Metadata,
+    ) -> Result<(), std::io::Error> {
+        map_serializer.serialize_entry(VERSION, GELF_VERSION)?;
+        map_serializer.serialize_entry(HOST, &self.hostname)?;
+        map_serializer.serialize_entry(SHORT_MESSAGE, &message)?;
+        map_serializer.serialize_entry(
+            TIMESTAMP,
+            &(DateTime::now_or_zero().as_millis_since_epoch() as f64 / 1000.0),
+        )?;
+        map_serializer.serialize_entry(LEVEL, &to_gelf_level(metadata.level()))?;
+        map_serializer.serialize_entry(&format!("_{}", APPLICATION), &self.name)?;
+        map_serializer.serialize_entry(&format!("_{}", TARGET), metadata.target())?;
+        map_serializer.serialize_entry("_line", &metadata.line())?;
+        map_serializer.serialize_entry("_file", &metadata.file())?;
+
+        // Add all default fields
+        for (key, value) in self.default_fields.iter() {
+            serialize_additional_field(map_serializer, &self.redacted_fields, key, value)?;
+        }
+        Ok(())
+    }
+
+    /// Given a span, it serialised it to a in-memory buffer (vector of bytes).
+    fn serialize_span<S: Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>>(
+        &self,
+        span: &SpanRef<S>,
+        ty: Type,
+    ) -> Result<Vec<u8>, std::io::Error> {
+        let mut buffer = Vec::new();
+        let mut serializer = serde_json::Serializer::new(&mut buffer);
+        let mut map_serializer = serializer.serialize_map(None)?;
+        let message = format_span_context(span, ty);
+        self.serialize_gelf_core_fields(&mut map_serializer, &message, span.metadata())?;
+
+        let extensions = span.extensions();
+        if let Some(visitor) = extensions.get::<Storage>() {
+            for (key, value) in visitor.values() {
+                serialize_additional_field(&mut map_serializer, &self.redacted_fields, key, value)?;
+            }
+        }
+        map_serializer.end()?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Gelf {
+    fn default() -> Self {
+        Self {
+            hostname: "".to_string(),
+            name: "".to_string(),
+            default_fields: Default::default(),
+            redacted_fields: Default::default(),
+        }
+    }
+}
+
+/// Writes `key` as `_`-prefixed additional field, skipping the fields reserved by GELF or written
+/// by the formatter itself, and redacting the values of `redacted_fields`.
+fn serialize_additional_field(
+    map_serializer: &mut impl SerializeMap<Error = serde_json::Error>,
+    redacted_fields: &HashSet<String>,
+    key: &str,
+    value: &Value,
+) -> Result<(), serde_json::Error> {
+    if GELF_RESERVED_FIELDS.contains(&key) {
+        return Ok(());
+    }
+    map_serializer.serialize_entry(&format!("_{}", key), &redact(redacted_fields, key, value))
+}
+
+/// Ensure consistent formatting of the span context.
+///
+/// Example: "[AN_INTERESTING_SPAN - START]"
+fn format_span_context<S: Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>>(
+    span: &SpanRef<S>,
+    ty: Type,
+) -> String {
+    format!("[{} - {}]", span.metadata().name().to_uppercase(), ty)
+}
+
+/// Ensure consistent formatting of event message.
+///
+/// Examples:
+/// - "[AN_INTERESTING_SPAN - EVENT] My event message" (for an event with a parent span)
+/// - "My event message" (for an event without a parent span)
+fn format_event_message<S: Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>>(
+    current_span: &Option<SpanRef<S>>,
+    event: &Event,
+    event_visitor: &Storage<'_>,
+) -> String {
+    // Extract the "message" field, if provided. Fallback to the target, if missing.
+    let mut message = event_visitor
+        .values()
+        .get("message")
+        .and_then(|v| match v {
+            Value::String(s) => Some(s.as_str()),
+            _ => None,
+        })
+        .unwrap_or_else(|| event.metadata().target())
+        .to_owned();
+
+    // If the event is in the context of a span, prepend the span name to the message.
+    if let Some(span) = &current_span {
+        message = format!("{} {}", format_span_context(span, Type::Event), message);
+    }
+
+    message
+}
+
+impl LogFormatter for Gelf {
+    fn log_layer_defaults<W: for<'a> MakeWriter<'a> + 'static, F: LogFormatter + Default>(
+        &self,
+        layer: &LogLayer<W, F>,
+    ) -> Self {
+        Self {
+            hostname: layer.hostname().clone().unwrap_or("localhost".to_string()),
+            name: layer.application().clone().unwrap_or("app".to_string()),
+            default_fields: self.default_fields.clone(),
+            redacted_fields: self.redacted_fields.clone(),
+        }
+    }
+
+    fn format_event<S: Subscriber + for<'a> LookupSpan<'a>>(
+        &mut self,
+        current_span: &Option<SpanRef<S>>,
+        event: &Event,
+        event_visitor: &Storage<'_>,
+    ) -> String {
+        // Opting for a closure to use the ? operator and get more linear code.
+        let format = || {
+            let mut buffer = Vec::new();
+
+            let mut serializer = serde_json::Serializer::new(&mut buffer);
+            let mut map_serializer = serializer.serialize_map(None)?;
+
+            let message = format_event_message(current_span, event, event_visitor);
+            self.serialize_gelf_core_fields(&mut map_serializer, &message, event.metadata())?;
+
+            // Add all the other fields associated with the event, expect the message we already used.
+            for (key, value) in event_visitor
+                .values()
+                .iter()
+                .filter(|(&key, _)| key != "message")
+            {
+                serialize_additional_field(&mut map_serializer, &self.redacted_fields, key, value)?;
+            }
+
+            // Add all the fields from the current span, if we have one.
+            if let Some(span) = &current_span {
+                let extensions = span.extensions();
+                if let Some(visitor) = extensions.get::<Storage>() {
+                    for (key, value) in visitor.values() {
+                        if !event_visitor.has(key) {
+                            serialize_additional_field(
+                                &mut map_serializer,
+                                &self.redacted_fields,
+                                key,
+                                value,
+                            )?;
+                        }
+                    }
+                }
+            }
+            map_serializer.end()?;
+            Ok(buffer)
+        };
+        format()
+            .map_err(|e: std::io::Error| e.to_string())
+            .and_then(|t| String::from_utf8(t).map_err(|e| e.to_string()))
+            .unwrap_or_else(|e| e)
+    }
+
+    fn format_span<S: Subscriber + for<'a> LookupSpan<'a>>(
+        &mut self,
+        span: &SpanRef<S>,
+        ty: Type,
+    ) -> String {
+        self.serialize_span(span, ty)
+            .map_err(|e: std::io::Error| e.to_string())
+            .and_then(|t| String::from_utf8(t).map_err(|e| e.to_string()))
+            .unwrap_or_else(|e| e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log::tracing::formatter::test_util::Buffer;
+    use crate::log::tracing::layer::StorageLayer;
+    use tracing::Level;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_to_gelf_level() {
+        assert_eq!(to_gelf_level(&Level::ERROR), 3);
+        assert_eq!(to_gelf_level(&Level::WARN), 4);
+        assert_eq!(to_gelf_level(&Level::INFO), 6);
+        assert_eq!(to_gelf_level(&Level::DEBUG), 7);
+        assert_eq!(to_gelf_level(&Level::TRACE), 7);
+    }
+
+    #[test]
+    fn test_serialize_event() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry()
+            .with(StorageLayer)
+            .with(LogLayer::new(
+                Some("gearbox".to_string()),
+                move || writer.clone(),
+                Gelf::default().with_redacted_fields(HashSet::from(["password".to_string()])),
+            ));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(
+                user = "alice",
+                password = "hunter2",
+                id = 7,
+                host = "other",
+                application = "spoofed",
+                target = "spoofed",
+                "login failed"
+            );
+        });
+
+        let output = buffer.contents();
+        let json: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(json["version"], "1.1");
+        assert_eq!(json["short_message"], "login failed");
+        assert_eq!(json["level"], 4);
+        assert_eq!(json["_application"], "gearbox");
+        assert_eq!(json["_user"], "alice");
+        assert_eq!(json["_password"], "[REDACTED]");
+        assert!(json["host"].is_string());
+        assert_ne!(json["host"], "other");
+        assert!(json["timestamp"].is_f64());
+        assert!(json.get("_id").is_none());
+        assert!(json.get("_host").is_none());
+        assert!(json.get("_timestamp").is_none());
+        assert!(json.get("_message").is_none());
+        assert_eq!(output.matches("\"_application\"").count(), 1);
+        assert_eq!(output.matches("\"_target\"").count(), 1);
+        assert_ne!(json["_target"], "spoofed");
+    }
+}
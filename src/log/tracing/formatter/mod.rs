@@ -2,8 +2,12 @@
 pub mod bunyan;
 #[cfg(feature = "log-tracing-deeplog")]
 pub mod deeplog;
+#[cfg(feature = "log-tracing-gelf")]
+pub mod gelf;
 #[cfg(feature = "log-tracing-syslog")]
 pub mod syslog;
+#[cfg(test)]
+mod test_util;
 
 use crate::log::tracing::layer::{LogLayer, Storage, Type};
 use alloc::string::String;
@@ -15,8 +19,27 @@ use tracing_subscriber::registry::SpanRef;
 pub use bunyan::Bunyan;
 #[cfg(feature = "log-tracing-deeplog")]
 pub use deeplog::DeepLogFormatter;
+#[cfg(feature = "log-tracing-gelf")]
+pub use gelf::Gelf;
 #[cfg(feature = "log-tracing-syslog")]
-pub use syslog::Syslog;
+pub use syslog::{Rfc5424, Syslog};
+
+/// The placeholder written instead of the value of a redacted field.
+pub const REDACTED: &str = "[REDACTED]";
+
+#[cfg(feature = "with_serde")]
+/// Converts `value` to JSON, replacing it with [`REDACTED`] if `key` is one of `redacted_fields`.
+pub(crate) fn redact(
+    redacted_fields: &hashbrown::HashSet<String>,
+    key: &str,
+    value: &crate::log::tracing::Value,
+) -> serde_json::Value {
+    if redacted_fields.contains(key) {
+        serde_json::Value::from(REDACTED)
+    } else {
+        serde_json::Value::from(value)
+    }
+}
 
 pub trait LogFormatter {
     fn log_layer_defaults<W: for<'a> MakeWriter<'a> + 'static, F: LogFormatter + Default>(
@@ -1,3 +1,5 @@
+pub mod rfc5424;
+
 use crate::log::tracing::entity::syslog::Facility;
 use crate::log::tracing::entity::syslog::Severity;
 use crate::log::tracing::layer::LogLayer;
@@ -19,6 +21,8 @@ use tracing::{Event, Subscriber};
 use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::registry::{LookupSpan, SpanRef};
 
+pub use rfc5424::Rfc5424;
+
 #[derive(Debug, Default, Clone)]
 pub struct Syslog {
     facility: Option<Facility>,
@@ -0,0 +1,298 @@
+use crate::log::tracing::entity::syslog::{Facility, Severity};
+use crate::log::tracing::layer::{LogLayer, Storage, Type};
+use crate::log::tracing::{LogFormatter, Value};
+use crate::time::DateTime;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::registry::{LookupSpan, SpanRef};
+
+/// The SD-ID used for the structured data element holding the event fields.
+const DEFAULT_SD_ID: &str = "fields@32473";
+/// The RFC5424 NILVALUE.
+const NIL: &str = "-";
+/// Fields that are part of the frame header and are not repeated as structured data.
+const RESERVED_FIELDS: [&str; 6] = [
+    "message",
+    "timestamp",
+    "log_level",
+    "level",
+    "log_facility",
+    "facility",
+];
+
+/// Formats events as RFC5424 syslog frames:
+///
+/// `<PRI>1 TIMESTAMP HOSTNAME APP-NAME PROCID MSGID [SD-ID key="value" ...] MSG`
+///
+/// The priority is computed from the configured facility (user-level messages by default) and
+/// the severity of the event. The fields of the event are emitted as a single structured data
+/// element, sorted by key.
+#[derive(Debug, Clone)]
+pub struct Rfc5424 {
+    facility: Facility,
+    sd_id: String,
+    severity: Option<Severity>,
+    timestamp: Option<DateTime>,
+    hostname: Option<String>,
+    application: Option<String>,
+    proc_id: Option<u32>,
+    message_id: Option<String>,
+    data: Vec<(String, String)>,
+    message: Option<String>,
+}
+
+impl Rfc5424 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the facility used to compute the priority.
+    pub fn facility(mut self, facility: Facility) -> Self {
+        self.facility = facility;
+        self
+    }
+
+    /// Sets the SD-ID of the structured data element holding the event fields.
+    pub fn sd_id(mut self, sd_id: &str) -> Self {
+        self.sd_id = sd_id.to_string();
+        self
+    }
+
+    /// Computes the PRI value, RFC5424 only defines severities up to debug (7).
+    fn priority(&self) -> u32 {
+        let severity = self
+            .severity
+            .as_ref()
+            .map(|severity| u32::from(severity).min(7))
+            .unwrap_or(6);
+        u32::from(&self.facility) * 8 + severity
+    }
+
+    fn structured_data(&self) -> String {
+        if self.data.is_empty() {
+            return NIL.to_string();
+        }
+        let params = self
+            .data
+            .iter()
+            .map(|(key, value)| format!(r#"{}="{}""#, param_name(key), escape_param_value(value)))
+            .collect::<Vec<String>>();
+        format!("[{} {}]", self.sd_id, params.join(" "))
+    }
+
+    fn set_data<'a>(&mut self, values: impl Iterator<Item = (&'a &'a str, &'a Value)>) {
+        let mut data = values
+            .filter(|(key, _)| !RESERVED_FIELDS.contains(key))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<Vec<(String, String)>>();
+        data.sort();
+        self.data = data;
+    }
+}
+
+impl Default for Rfc5424 {
+    fn default() -> Self {
+        Self {
+            facility: Facility::UserlevelMessages,
+            sd_id: DEFAULT_SD_ID.to_string(),
+            severity: None,
+            timestamp: None,
+            hostname: None,
+            application: None,
+            proc_id: None,
+            message_id: None,
+            data: Vec::new(),
+            message: None,
+        }
+    }
+}
+
+/// Strips the characters RFC5424 does not allow in a SD-NAME.
+fn param_name(key: &str) -> String {
+    key.chars()
+        .filter(|c| c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"'))
+        .take(32)
+        .collect()
+}
+
+/// Formats a header field, which RFC5424 restricts to printable US-ASCII without spaces and
+/// to `max_len` characters. Other characters, such as spaces, are replaced with `_`.
+fn header_field(value: Option<&str>, max_len: usize) -> String {
+    match value.filter(|t| !t.is_empty()) {
+        Some(value) => value
+            .chars()
+            .take(max_len)
+            .map(|c| if c.is_ascii_graphic() { c } else { '_' })
+            .collect(),
+        None => NIL.to_string(),
+    }
+}
+
+/// Escapes `"`, `\` and `]` in a PARAM-VALUE.
+fn escape_param_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+impl fmt::Display for Rfc5424 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "<{}>1 {} {} {} {} {} {}",
+            self.priority(),
+            self.timestamp
+                .as_ref()
+                .map(|t| t.to_rfc3339())
+                .unwrap_or_else(|| NIL.to_string()),
+            header_field(self.hostname.as_deref(), 255),
+            header_field(self.application.as_deref(), 48),
+            self.proc_id
+                .map(|t| t.to_string())
+                .unwrap_or_else(|| NIL.to_string()),
+            header_field(self.message_id.as_deref(), 32),
+            self.structured_data(),
+        )?;
+        match &self.message {
+            Some(message) => write!(f, " {}", message),
+            None => Ok(()),
+        }
+    }
+}
+
+impl LogFormatter for Rfc5424 {
+    fn log_layer_defaults<W: for<'a> MakeWriter<'a> + 'static, F: LogFormatter + Default>(
+        &self,
+        layer: &LogLayer<W, F>,
+    ) -> Self {
+        Self {
+            facility: self.facility.clone(),
+            sd_id: self.sd_id.clone(),
+            timestamp: Some(DateTime::now()),
+            hostname: layer.hostname().clone(),
+            application: layer.application().clone(),
+            proc_id: *layer.proc_id(),
+            ..Default::default()
+        }
+    }
+
+    fn format_event<S: Subscriber + for<'a> LookupSpan<'a>>(
+        &mut self,
+        current_span: &Option<SpanRef<S>>,
+        event: &Event,
+        event_visitor: &Storage<'_>,
+    ) -> String {
+        let mut message = event_visitor
+            .get("message")
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| event.metadata().target().to_string());
+        if let Some(span) = current_span {
+            message = format!("({}) {}", span.metadata().name(), message);
+        }
+
+        self.message = Some(message);
+        self.message_id = Some(Type::Event.to_string());
+        self.severity = event_visitor
+            .get("log_level")
+            .and_then(|t| t.try_into().ok())
+            .or_else(|| event_visitor.get("level").and_then(|t| t.try_into().ok()))
+            .or_else(|| Some(Severity::from(event.metadata().level())));
+        if let Some(facility) = event_visitor
+            .get("log_facility")
+            .or_else(|| event_visitor.get("facility"))
+            .and_then(|t| t.try_into().ok())
+        {
+            self.facility = facility;
+        }
+        self.set_data(event_visitor.iter());
+
+        self.to_string()
+    }
+
+    fn format_span<S: Subscriber + for<'a> LookupSpan<'a>>(
+        &mut self,
+        span: &SpanRef<S>,
+        ty: Type,
+    ) -> String {
+        self.message = Some(format!("({})", span.metadata().name()));
+        self.message_id = Some(ty.to_string());
+        self.severity = Some(Severity::from(span.metadata().level()));
+        if let Some(visitor) = span.extensions().get::<Storage>() {
+            self.set_data(visitor.iter());
+        }
+
+        self.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame() {
+        let frame = Rfc5424 {
+            facility: Facility::LocalUse4,
+            severity: Some(Severity::Notice),
+            timestamp: Some(DateTime::from_secs(1065910455)),
+            hostname: Some("mymachine.example.com".to_string()),
+            application: Some("evntslog".to_string()),
+            proc_id: Some(42),
+            message_id: Some("ID47".to_string()),
+            data: vec![
+                ("eventSource".to_string(), "Application".to_string()),
+                ("path".to_string(), r#"C:\logs\"all"]"#.to_string()),
+            ],
+            message: Some("An application event log entry".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            frame.to_string(),
+            r#"<165>1 2003-10-11T22:14:15Z mymachine.example.com evntslog 42 ID47 [fields@32473 eventSource="Application" path="C:\\logs\\\"all\"\]"] An application event log entry"#
+        );
+    }
+
+    #[test]
+    fn test_frame_sanitizes_header() {
+        let frame = Rfc5424 {
+            hostname: Some("my machine".to_string()),
+            application: Some("event log\tapp".to_string()),
+            message_id: Some(String::new()),
+            ..Default::default()
+        };
+        assert_eq!(frame.to_string(), "<14>1 - my_machine event_log_app - - -");
+
+        let frame = Rfc5424 {
+            application: Some("a".repeat(64)),
+            ..Default::default()
+        };
+        assert_eq!(
+            frame.to_string(),
+            format!("<14>1 - - {} - - -", "a".repeat(48))
+        );
+    }
+
+    #[test]
+    fn test_frame_nil_values() {
+        let frame = Rfc5424::new().facility(Facility::KernelMessages);
+        assert_eq!(frame.to_string(), "<6>1 - - - - - -");
+
+        let frame = Rfc5424 {
+            severity: Some(Severity::Trace),
+            ..Default::default()
+        };
+        assert_eq!(frame.priority(), 15);
+    }
+}
@@ -0,0 +1,25 @@
+//! Helpers shared by the formatter tests.
+use std::io;
+use std::sync::{Arc, Mutex};
+
+/// An in-memory writer whose clones share the written bytes, so a test can hand one clone to a
+/// `LogLayer` and read the output back through another.
+#[derive(Clone, Default)]
+pub(crate) struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Buffer {
+    /// Returns everything written so far.
+    pub(crate) fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl io::Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ pub mod index;
 pub mod layer;
 pub mod macros;
 pub mod value;
+pub mod writer;
 
 pub use formatter::LogFormatter;
 pub use index::Index;
@@ -0,0 +1,3 @@
+pub mod rotating_file;
+
+pub use rotating_file::{RotatingFileWriter, Rotation};
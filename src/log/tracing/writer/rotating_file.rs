@@ -0,0 +1,368 @@
+//! A file sink for the `LogLayer` that rotates the log file by size or by day.
+//!
+//! Writes are handed to a background thread, so the logging path only waits on the file system
+//! when more than `CHANNEL_CAPACITY` writes are pending. The active file keeps its configured
+//! name, rotated files get a numeric suffix where `.1` is the most recent one.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use gearbox::log::tracing::formatter::Bunyan;
+//! use gearbox::log::tracing::layer::LogLayer;
+//! use gearbox::log::tracing::writer::{Rotation, RotatingFileWriter};
+//!
+//! let writer = RotatingFileWriter::new("/var/log/app.log", Rotation::Size(10 * 1024 * 1024), 5)
+//!     .expect("failed to open log file");
+//! let layer = LogLayer::new(Some("app".to_string()), writer, Bunyan::default());
+//! ```
+use crate::time::DateTime;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread::{self, JoinHandle};
+use std::time::UNIX_EPOCH;
+use tracing_subscriber::fmt::MakeWriter;
+
+const MILLIS_PER_DAY: i64 = 86_400_000;
+/// The number of writes that may be queued for the background thread before writing blocks.
+pub const CHANNEL_CAPACITY: usize = 1024;
+
+/// Describes when the active log file is rotated.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Rotation {
+    /// Rotates before a write would grow the file beyond the given number of bytes.
+    Size(u64),
+    /// Rotates on the first write of a new (UTC) day. An existing file last modified on an
+    /// earlier day is rotated on the first write.
+    Daily,
+    /// Never rotates.
+    Never,
+}
+
+enum Message {
+    Write(Vec<u8>),
+    Flush(SyncSender<io::Result<()>>),
+    Shutdown,
+}
+
+/// A `MakeWriter` that appends to a log file on a background thread, rotating it according to
+/// the configured `Rotation` and keeping at most `max_files` rotated files.
+///
+/// Failed writes and rotations can not be reported to the logging call that caused them. The
+/// first failure is printed to stderr, and the most recent one is returned by the next `flush`.
+///
+/// Pending writes are flushed when the writer is dropped.
+#[derive(Debug)]
+pub struct RotatingFileWriter {
+    sender: SyncSender<Message>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl RotatingFileWriter {
+    /// Opens (or creates) the log file at `path` and starts the background writer.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the active log file.
+    /// * `rotation` - When to rotate the active file.
+    /// * `max_files` - The number of rotated files to keep, older ones are deleted.
+    pub fn new(path: impl Into<PathBuf>, rotation: Rotation, max_files: usize) -> io::Result<Self> {
+        let mut file = RotatingFile::open(path.into(), rotation, max_files)?;
+        let (sender, receiver) = mpsc::sync_channel(CHANNEL_CAPACITY);
+        let handle = thread::Builder::new()
+            .name("rotating-file-writer".to_string())
+            .spawn(move || file.run(receiver))?;
+
+        Ok(Self {
+            sender,
+            handle: Some(handle),
+        })
+    }
+
+    /// Blocks until all writes issued so far reached the file. Returns the last error a write or
+    /// rotation ran into since the previous flush, if any.
+    pub fn flush(&self) -> io::Result<()> {
+        let (ack, done) = mpsc::sync_channel(1);
+        self.sender
+            .send(Message::Flush(ack))
+            .map_err(|_| disconnected())?;
+        done.recv().map_err(|_| disconnected())?
+    }
+}
+
+impl Drop for RotatingFileWriter {
+    fn drop(&mut self) {
+        let _ = self.sender.send(Message::Shutdown);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for RotatingFileWriter {
+    type Writer = RotatingFileHandle;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RotatingFileHandle {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+/// The writer handed out by `RotatingFileWriter`, it forwards every write to the background
+/// thread.
+#[derive(Debug)]
+pub struct RotatingFileHandle {
+    sender: SyncSender<Message>,
+}
+
+impl Write for RotatingFileHandle {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.sender
+            .send(Message::Write(buf.to_vec()))
+            .map_err(|_| disconnected())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn disconnected() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::BrokenPipe,
+        "rotating file writer is not running",
+    )
+}
+
+/// The state owned by the background thread.
+struct RotatingFile {
+    path: PathBuf,
+    rotation: Rotation,
+    max_files: usize,
+    writer: BufWriter<File>,
+    size: u64,
+    day: i64,
+    /// The last error since the previous flush.
+    error: Option<io::Error>,
+    /// Whether an error has been printed to stderr already.
+    reported: bool,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf, rotation: Rotation, max_files: usize) -> io::Result<Self> {
+        let file = Self::open_file(&path)?;
+        let metadata = file.metadata()?;
+        let size = metadata.len();
+        // An existing file belongs to the day it was last written to, so daily rotation picks up
+        // a file left over from an earlier day.
+        let day = metadata
+            .modified()
+            .ok()
+            .filter(|_| size > 0)
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|t| t.as_millis() as i64 / MILLIS_PER_DAY)
+            .unwrap_or_else(current_day);
+        Ok(Self {
+            path,
+            rotation,
+            max_files,
+            writer: BufWriter::new(file),
+            size,
+            day,
+            error: None,
+            reported: false,
+        })
+    }
+
+    fn open_file(path: &Path) -> io::Result<File> {
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        OpenOptions::new().create(true).append(true).open(path)
+    }
+
+    fn run(&mut self, receiver: Receiver<Message>) {
+        while let Ok(message) = receiver.recv() {
+            match message {
+                Message::Write(buffer) => {
+                    if let Err(e) = self.write(&buffer) {
+                        self.record(e);
+                    }
+                }
+                Message::Flush(ack) => {
+                    if let Err(e) = self.writer.flush() {
+                        self.record(e);
+                    }
+                    let _ = ack.send(self.error.take().map_or(Ok(()), Err));
+                }
+                Message::Shutdown => break,
+            }
+        }
+        if let Err(e) = self.writer.flush() {
+            self.record(e);
+        }
+    }
+
+    /// Keeps `error` for the next flush. The first error is also printed to stderr, as there is
+    /// no other place to report it to.
+    fn record(&mut self, error: io::Error) {
+        if !self.reported {
+            self.reported = true;
+            eprintln!(
+                "rotating file writer failed to write to {}: {}",
+                self.path.display(),
+                error
+            );
+        }
+        self.error = Some(error);
+    }
+
+    fn write(&mut self, buffer: &[u8]) -> io::Result<()> {
+        if self.should_rotate(buffer.len() as u64) {
+            self.rotate()?;
+        }
+        self.writer.write_all(buffer)?;
+        self.size += buffer.len() as u64;
+        Ok(())
+    }
+
+    fn should_rotate(&self, len: u64) -> bool {
+        match self.rotation {
+            Rotation::Size(max) => self.size > 0 && self.size + len > max,
+            Rotation::Daily => self.day != current_day(),
+            Rotation::Never => false,
+        }
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        if self.max_files == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            let _ = fs::remove_file(self.rotated_path(self.max_files));
+            for index in (1..self.max_files).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    fs::rename(from, self.rotated_path(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+        }
+
+        self.writer = BufWriter::new(Self::open_file(&self.path)?);
+        self.size = 0;
+        self.day = current_day();
+        Ok(())
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}", index));
+        PathBuf::from(path)
+    }
+}
+
+fn current_day() -> i64 {
+    DateTime::now_or_zero().as_millis_since_epoch() / MILLIS_PER_DAY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "gearbox-rotating-{}-{}",
+            name,
+            crate::common::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn log_files(dir: &Path) -> Vec<String> {
+        let mut files = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn test_size_rotation() {
+        let dir = test_dir("size");
+        let path = dir.join("app.log");
+        {
+            let writer = RotatingFileWriter::new(&path, Rotation::Size(100), 3).unwrap();
+            writer.make_writer().write_all(&[b'a'; 60]).unwrap();
+            writer.make_writer().write_all(&[b'b'; 60]).unwrap();
+        }
+
+        assert_eq!(log_files(&dir), vec!["app.log", "app.log.1"]);
+        assert_eq!(fs::read(&path).unwrap(), vec![b'b'; 60]);
+        assert_eq!(fs::read(dir.join("app.log.1")).unwrap(), vec![b'a'; 60]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_max_files() {
+        let dir = test_dir("max-files");
+        let path = dir.join("app.log");
+        let writer = RotatingFileWriter::new(&path, Rotation::Size(10), 2).unwrap();
+        for byte in [b'a', b'b', b'c', b'd'] {
+            writer.make_writer().write_all(&[byte; 10]).unwrap();
+        }
+        writer.flush().unwrap();
+
+        assert_eq!(log_files(&dir), vec!["app.log", "app.log.1", "app.log.2"]);
+        assert_eq!(fs::read(&path).unwrap(), vec![b'd'; 10]);
+        assert_eq!(fs::read(dir.join("app.log.2")).unwrap(), vec![b'b'; 10]);
+        drop(writer);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_daily_rotation_of_existing_file() {
+        let dir = test_dir("daily");
+        let path = dir.join("app.log");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, b"yesterday").unwrap();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() - std::time::Duration::from_secs(86_400))
+            .unwrap();
+        {
+            let writer = RotatingFileWriter::new(&path, Rotation::Daily, 3).unwrap();
+            writer.make_writer().write_all(b"today").unwrap();
+        }
+
+        assert_eq!(fs::read(&path).unwrap(), b"today");
+        assert_eq!(fs::read(dir.join("app.log.1")).unwrap(), b"yesterday");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_flush_reports_errors() {
+        let dir = test_dir("errors");
+        let path = dir.join("app.log");
+        let writer = RotatingFileWriter::new(&path, Rotation::Size(10), 2).unwrap();
+        writer.make_writer().write_all(&[b'a'; 10]).unwrap();
+        writer.flush().unwrap();
+
+        // Rotating renames the active file, which fails once it is gone.
+        fs::remove_file(&path).unwrap();
+        writer.make_writer().write_all(&[b'b'; 10]).unwrap();
+        assert!(writer.flush().is_err());
+        assert!(writer.flush().is_ok());
+        drop(writer);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -3,6 +3,92 @@ pub fn gethostname() -> std::ffi::OsString {
     gethostname_impl()
 }
 
+/// Returns the fully-qualified domain name of this host, e.g. `web-1.example.com`.
+///
+/// On unix the hostname is resolved through `getaddrinfo` and the canonical name of the first
+/// result is returned. If the resolver has no canonical name the plain hostname is returned. On
+/// windows the DNS fully-qualified name of the computer is used.
+///
+/// On targets without a hostname, such as `wasm32`, this returns the stub `localhost`.
+#[cfg(feature = "std")]
+pub fn gethostname_fqdn() -> std::io::Result<String> {
+    gethostname_fqdn_impl()
+}
+
+/// Returns the hostname without its domain, e.g. `web-1` for `web-1.example.com`.
+///
+/// On targets without a hostname, such as `wasm32`, this returns the stub `localhost`.
+#[cfg(feature = "std")]
+pub fn short_hostname() -> String {
+    #[cfg(any(unix, windows))]
+    let hostname = gethostname().to_string_lossy().into_owned();
+    #[cfg(not(any(unix, windows)))]
+    let hostname = STUB_HOSTNAME.to_string();
+    strip_domain(&hostname).to_string()
+}
+
+/// The hostname reported on targets that have no notion of one.
+#[cfg(all(not(any(unix, windows)), feature = "std"))]
+const STUB_HOSTNAME: &str = "localhost";
+
+fn strip_domain(hostname: &str) -> &str {
+    hostname.split('.').next().unwrap_or(hostname)
+}
+
+#[cfg(all(unix, feature = "std"))]
+fn gethostname_fqdn_impl() -> std::io::Result<String> {
+    use std::ffi::{CStr, CString};
+    use std::io::{Error, ErrorKind};
+    use std::os::unix::ffi::OsStringExt;
+
+    let hostname = gethostname();
+    let host = CString::new(hostname.clone().into_vec())
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+
+    let mut hints: libc::addrinfo = unsafe { std::mem::zeroed() };
+    hints.ai_family = libc::AF_UNSPEC;
+    hints.ai_socktype = libc::SOCK_DGRAM;
+    hints.ai_flags = libc::AI_CANONNAME;
+
+    let mut result: *mut libc::addrinfo = std::ptr::null_mut();
+    let returncode =
+        unsafe { libc::getaddrinfo(host.as_ptr(), std::ptr::null(), &hints, &mut result) };
+    if returncode != 0 {
+        let message = unsafe { CStr::from_ptr(libc::gai_strerror(returncode)) };
+        return Err(Error::other(format!(
+            "getaddrinfo failed: {}",
+            message.to_string_lossy()
+        )));
+    }
+
+    // SAFETY: getaddrinfo succeeded, so `result` points to at least one entry until it is freed.
+    let canonical = unsafe {
+        let canonname = (*result).ai_canonname;
+        let canonical = (!canonname.is_null())
+            .then(|| CStr::from_ptr(canonname).to_string_lossy().into_owned());
+        libc::freeaddrinfo(result);
+        canonical
+    };
+
+    Ok(canonical
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| hostname.to_string_lossy().into_owned()))
+}
+
+#[cfg(all(windows, feature = "std"))]
+fn gethostname_fqdn_impl() -> std::io::Result<String> {
+    // The fully qualified DNS name that uniquely identifies the local computer.
+    pub const COMPUTER_NAME_DNS_FULLY_QUALIFIED: i32 = 3;
+    Ok(computer_name(COMPUTER_NAME_DNS_FULLY_QUALIFIED)
+        .to_string_lossy()
+        .into_owned())
+}
+
+#[cfg(all(not(any(unix, windows)), feature = "std"))]
+fn gethostname_fqdn_impl() -> std::io::Result<String> {
+    Ok(STUB_HOSTNAME.to_string())
+}
+
 #[cfg(all(unix, feature = "std"))]
 #[inline]
 fn gethostname_impl() -> std::ffi::OsString {
@@ -33,13 +119,17 @@ fn gethostname_impl() -> std::ffi::OsString {
 
 #[cfg(all(windows, feature = "std"))]
 #[inline]
-fn gethostname_impl() -> OsString {
-    use std::os::windows::ffi::OsStringExt;
-
+fn gethostname_impl() -> std::ffi::OsString {
     // The DNS host name of the local computer. If the local computer is a node
     // in a cluster, lpBuffer receives the DNS host name of the local computer,
     // not the name of the cluster virtual server.
     pub const COMPUTER_NAME_PHYSICAL_DNS_HOSTNAME: i32 = 5;
+    computer_name(COMPUTER_NAME_PHYSICAL_DNS_HOSTNAME)
+}
+
+#[cfg(all(windows, feature = "std"))]
+fn computer_name(name_type: i32) -> std::ffi::OsString {
+    use std::os::windows::ffi::OsStringExt;
 
     // https://learn.microsoft.com/en-us/windows/win32/api/sysinfoapi/nf-sysinfoapi-getcomputernameexw
     ::windows_targets::link!("kernel32.dll" "system" fn GetComputerNameExW(nametype: i32, lpbuffer: *mut u16, nsize: *mut u32) -> i32);
@@ -50,11 +140,7 @@ fn gethostname_impl() -> OsString {
         // This call always fails with ERROR_MORE_DATA, because we pass NULL to
         // get the required buffer size.  GetComputerNameExW then fills buffer_size with the size
         // of the host name string plus a trailing zero byte.
-        GetComputerNameExW(
-            COMPUTER_NAME_PHYSICAL_DNS_HOSTNAME,
-            std::ptr::null_mut(),
-            &mut buffer_size,
-        )
+        GetComputerNameExW(name_type, std::ptr::null_mut(), &mut buffer_size)
     };
     assert!(
         0 < buffer_size,
@@ -63,12 +149,7 @@ fn gethostname_impl() -> OsString {
 
     let mut buffer = vec![0_u16; buffer_size as usize];
     unsafe {
-        if GetComputerNameExW(
-            COMPUTER_NAME_PHYSICAL_DNS_HOSTNAME,
-            buffer.as_mut_ptr(),
-            &mut buffer_size,
-        ) == 0
-        {
+        if GetComputerNameExW(name_type, buffer.as_mut_ptr(), &mut buffer_size) == 0 {
             panic!(
                 "GetComputerNameExW failed to read hostname.
         Please report this issue to <https://github.com/swsnr/gethostname.rs/issues>!"
@@ -82,7 +163,7 @@ fn gethostname_impl() -> OsString {
     );
 
     let end = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
-    OsString::from_wide(&buffer[0..end])
+    std::ffi::OsString::from_wide(&buffer[0..end])
 }
 
 #[cfg(all(test, any(unix, windows), feature = "std"))]
@@ -114,6 +195,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn short_hostname_is_prefix_of_fqdn() {
+        let fqdn = super::gethostname_fqdn().expect("failed to resolve fqdn");
+        let short = super::short_hostname();
+        assert!(!short.is_empty());
+        assert!(!short.contains('.'));
+        assert!(
+            fqdn.to_lowercase().starts_with(&short.to_lowercase()),
+            "{} is not a prefix of {}",
+            short,
+            fqdn
+        );
+    }
+
+    #[test]
+    fn strip_domain_removes_everything_after_the_first_dot() {
+        assert_eq!(super::strip_domain("web-1.example.com"), "web-1");
+        assert_eq!(super::strip_domain("web-1"), "web-1");
+    }
+
     #[test]
     #[ignore]
     fn gethostname_matches_fixed_hostname() {
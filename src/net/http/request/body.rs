@@ -29,6 +29,7 @@ pub enum Body {
     Bytes(Vec<u8>),
     #[serde(skip)]
     Reference(Arc<Mutex<Option<reqwest::Response>>>),
+    Multipart(Multipart),
     Empty,
 }
 
@@ -38,6 +39,89 @@ impl Body {
     }
 }
 
+/// A `multipart/form-data` body. The parts are kept until the request is sent, where they are
+/// turned into a `reqwest::multipart::Form` with a generated boundary. As the parts are kept in
+/// memory, a multipart body can be sent again when a request is retried.
+#[derive(Debug, Clone, Default, DeriveSerialize, DeriveDeserialize)]
+pub struct Multipart {
+    parts: Vec<MultipartPart>,
+}
+
+/// A single part of a `Multipart` body.
+#[derive(Debug, Clone, DeriveSerialize, DeriveDeserialize)]
+pub struct MultipartPart {
+    name: String,
+    value: Vec<u8>,
+    filename: Option<String>,
+    mime: Option<String>,
+}
+
+impl Multipart {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a text field.
+    pub fn text(mut self, name: &str, value: &str) -> Self {
+        self.parts.push(MultipartPart {
+            name: name.to_string(),
+            value: value.as_bytes().to_vec(),
+            filename: None,
+            mime: None,
+        });
+        self
+    }
+
+    /// Adds a file field with the given file name and mime type.
+    pub fn file(mut self, name: &str, filename: &str, bytes: Vec<u8>, mime: &str) -> Self {
+        self.parts.push(MultipartPart {
+            name: name.to_string(),
+            value: bytes,
+            filename: Some(filename.to_string()),
+            mime: Some(mime.to_string()),
+        });
+        self
+    }
+
+    pub fn parts(&self) -> &[MultipartPart] {
+        &self.parts
+    }
+
+    /// Builds the `reqwest::multipart::Form` that is sent as the request body.
+    pub fn to_form(&self) -> Result<reqwest::multipart::Form, reqwest::Error> {
+        self.parts
+            .iter()
+            .try_fold(reqwest::multipart::Form::new(), |form, part| {
+                let mut raw = reqwest::multipart::Part::bytes(part.value.clone());
+                if let Some(filename) = &part.filename {
+                    raw = raw.file_name(filename.clone());
+                }
+                if let Some(mime) = &part.mime {
+                    raw = raw.mime_str(mime)?;
+                }
+                Ok(form.part(part.name.clone(), raw))
+            })
+    }
+}
+
+impl MultipartPart {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_deref()
+    }
+
+    pub fn mime(&self) -> Option<&str> {
+        self.mime.as_deref()
+    }
+}
+
 impl BodyTrait for Body {
     fn into_bytes(&mut self) -> BoxedFuture<Result<Bytes, DynTracerError>> {
         Box::pin(async move {
@@ -51,6 +135,9 @@ impl BodyTrait for Body {
                         .and_then(|t| async { t.bytes().await.map_dyn_tracer_err(error_info!()) })
                         .await
                 }
+                Body::Multipart(_) => Err(tracer_dyn_err!(
+                    "Multipart bodies are encoded when the request is sent"
+                )),
                 Body::Empty => Ok(Bytes::new()),
             }
             .map_err(|e| tracer_dyn_err!(e))
@@ -80,6 +167,9 @@ impl BodyTrait for Body {
         match self {
             Body::Bytes(ref b) => String::from_utf8(b.clone()).map_dyn_tracer_err(error_info!()),
             Body::Reference(r) => Err(tracer_dyn_err!()),
+            Body::Multipart(_) => Err(tracer_dyn_err!(
+                "Multipart bodies are encoded when the request is sent"
+            )),
             Body::Empty => Ok(String::new()),
         }
     }
@@ -96,6 +186,12 @@ impl From<Box<reqwest::Response>> for Body {
     }
 }
 
+impl From<Multipart> for Body {
+    fn from(m: Multipart) -> Self {
+        Body::Multipart(m)
+    }
+}
+
 impl From<String> for Body {
     fn from(s: String) -> Self {
         Body::Bytes(s.into_bytes())
@@ -0,0 +1,153 @@
+//! Decoding of compressed response bodies, keyed off the `Content-Encoding` header.
+use super::Error;
+use alloc::vec::Vec;
+use std::io::Read;
+
+/// A content coding that response bodies can be decoded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    Gzip,
+    Deflate,
+    Brotli,
+}
+
+impl ContentEncoding {
+    /// Parses a single content coding, e.g. `gzip` or `br`. `identity` and unknown codings return
+    /// `None`.
+    pub fn parse(coding: &str) -> Option<Self> {
+        match coding.trim().to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => Some(Self::Gzip),
+            "deflate" => Some(Self::Deflate),
+            "br" => Some(Self::Brotli),
+            _ => None,
+        }
+    }
+
+    /// Parses the value of a `Content-Encoding` header into the codings in the order they were
+    /// applied. `identity` entries are skipped.
+    ///
+    /// Returns `None` if any of the codings is unknown, as the body cannot be decoded then.
+    pub fn parse_header(value: &str) -> Option<Vec<Self>> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty() && !t.eq_ignore_ascii_case("identity"))
+            .map(Self::parse)
+            .collect()
+    }
+
+    /// Decodes `body`, which was encoded with this coding.
+    ///
+    /// `deflate` is specified as zlib wrapped deflate data, but some servers send raw deflate
+    /// data. Both are accepted.
+    pub fn decode(self, body: &[u8]) -> std::io::Result<Vec<u8>> {
+        self.read_to_end(body, u64::MAX)
+    }
+
+    /// Decodes `body` like `ContentEncoding::decode`, but fails with
+    /// `Error::DecompressedTooLarge` once the decoded data exceeds `limit` bytes. This protects
+    /// against small payloads that decompress to huge bodies.
+    pub fn decode_limited(self, body: &[u8], limit: u64) -> Result<Vec<u8>, Error> {
+        let decoded = self.read_to_end(body, limit.saturating_add(1))?;
+        match decoded.len() as u64 > limit {
+            true => Err(Error::DecompressedTooLarge(limit)),
+            false => Ok(decoded),
+        }
+    }
+
+    /// Decodes at most `limit` bytes of `body`.
+    fn read_to_end(self, body: &[u8], limit: u64) -> std::io::Result<Vec<u8>> {
+        let mut decoded = Vec::new();
+        match self {
+            Self::Gzip => flate2::read::MultiGzDecoder::new(body)
+                .take(limit)
+                .read_to_end(&mut decoded),
+            Self::Deflate => flate2::read::ZlibDecoder::new(body)
+                .take(limit)
+                .read_to_end(&mut decoded)
+                .or_else(|_| {
+                    decoded.clear();
+                    flate2::read::DeflateDecoder::new(body)
+                        .take(limit)
+                        .read_to_end(&mut decoded)
+                }),
+            Self::Brotli => brotli::Decompressor::new(body, 4096)
+                .take(limit)
+                .read_to_end(&mut decoded),
+        }
+        .map(|_| decoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_parse_header() {
+        assert_eq!(
+            ContentEncoding::parse_header("gzip"),
+            Some(vec![ContentEncoding::Gzip])
+        );
+        assert_eq!(
+            ContentEncoding::parse_header("deflate, identity, BR"),
+            Some(vec![ContentEncoding::Deflate, ContentEncoding::Brotli])
+        );
+        assert_eq!(ContentEncoding::parse_header("identity"), Some(vec![]));
+        assert_eq!(ContentEncoding::parse_header("gzip, zstd"), None);
+    }
+
+    #[test]
+    fn test_decode() {
+        let text = b"compressed response body";
+
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gzip.write_all(text).unwrap();
+        let gzip = gzip.finish().unwrap();
+        assert_eq!(ContentEncoding::Gzip.decode(&gzip).unwrap(), text);
+
+        let mut zlib = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        zlib.write_all(text).unwrap();
+        let zlib = zlib.finish().unwrap();
+        assert_eq!(ContentEncoding::Deflate.decode(&zlib).unwrap(), text);
+
+        let mut raw =
+            flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        raw.write_all(text).unwrap();
+        let raw = raw.finish().unwrap();
+        assert_eq!(ContentEncoding::Deflate.decode(&raw).unwrap(), text);
+
+        let mut br = Vec::new();
+        {
+            let mut writer = brotli::CompressorWriter::new(&mut br, 4096, 5, 22);
+            writer.write_all(text).unwrap();
+        }
+        assert_eq!(ContentEncoding::Brotli.decode(&br).unwrap(), text);
+
+        assert!(ContentEncoding::Gzip.decode(b"not gzip").is_err());
+    }
+
+    #[test]
+    fn test_decode_limited() {
+        let text = vec![b'a'; 1024 * 1024];
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        gzip.write_all(&text).unwrap();
+        let gzip = gzip.finish().unwrap();
+        assert!(gzip.len() < 4096);
+
+        let limit = text.len() as u64;
+        assert_eq!(
+            ContentEncoding::Gzip.decode_limited(&gzip, limit).unwrap(),
+            text
+        );
+        assert!(matches!(
+            ContentEncoding::Gzip.decode_limited(&gzip, limit - 1),
+            Err(Error::DecompressedTooLarge(t)) if t == limit - 1
+        ));
+        assert!(matches!(
+            ContentEncoding::Gzip.decode_limited(b"not gzip", limit),
+            Err(Error::Io(_))
+        ));
+    }
+}
@@ -1,3 +1,5 @@
+use super::redirect::RedirectError;
+use super::Url;
 use crate::error::{DynTracerError, TracerError};
 use alloc::string::String;
 use url::ParseError;
@@ -16,10 +18,32 @@ pub enum Error {
     NoPath,
     NoHeaders,
     NoBody,
+    Io(std::io::Error),
+    RedirectBlocked(Url),
+    TooManyRedirects,
+    QueryEncoding(serde_qs::Error),
+    /// The JSON pointer passed to `Response::pointer` does not point to a value in the body.
+    PointerNotFound(String),
+    /// The decoded response body exceeds the limit set with `Client::with_max_decompressed_size`.
+    DecompressedTooLarge(u64),
+    /// The body could not be decoded in the format of its `Content-Type`.
+    Decode(crate::serde::dynamic::Error),
 }
 
 impl From<reqwest::Error> for Error {
     fn from(e: reqwest::Error) -> Self {
+        if e.is_redirect() {
+            let mut source = std::error::Error::source(&e);
+            while let Some(inner) = source {
+                match inner.downcast_ref::<RedirectError>() {
+                    Some(RedirectError::Blocked(url)) => {
+                        return Error::RedirectBlocked(url.clone())
+                    }
+                    Some(RedirectError::TooMany) => return Error::TooManyRedirects,
+                    None => source = inner.source(),
+                }
+            }
+        }
         Error::Request(e)
     }
 }
@@ -36,6 +60,24 @@ impl From<reqwest::header::InvalidHeaderValue> for Error {
     }
 }
 
+impl From<serde_qs::Error> for Error {
+    fn from(e: serde_qs::Error) -> Self {
+        Error::QueryEncoding(e)
+    }
+}
+
+impl From<crate::serde::dynamic::Error> for Error {
+    fn from(e: crate::serde::dynamic::Error) -> Self {
+        Error::Decode(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
 impl From<DynTracerError> for Error {
     fn from(e: DynTracerError) -> Self {
         Error::BodyError(e)
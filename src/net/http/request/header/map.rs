@@ -1,6 +1,9 @@
 use super::{Header, Name, Value};
 use crate::net::http::request::header::values::Values;
-use alloc::{string::ToString, vec::Vec};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 use core::fmt;
 use core::slice::Iter;
 use crate_serde::de::{MapAccess, Visitor};
@@ -17,7 +20,9 @@ pub struct HeaderMap {
 impl HeaderMap {
     pub fn get<K: Into<Name>>(&self, key: K) -> Option<Header> {
         let key = key.into();
-        self.inner.get(&key).map(|t| Header(key.into(), t.clone()))
+        self.inner
+            .get_key_value(&key)
+            .map(|(name, t)| Header(name.clone(), t.clone()))
     }
 
     pub fn get_mut<K: Into<Name>>(&mut self, key: K) -> Option<&mut Values> {
@@ -40,6 +45,83 @@ impl HeaderMap {
         });
         self
     }
+
+    /// Returns the values of the header `name` as a string. Multiple values are joined with `, `,
+    /// which is equivalent to sending them as separate headers for all request headers.
+    ///
+    /// Returns `None` if the header is missing or one of its values is not valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request::HeaderMap;
+    ///
+    /// let mut headers = HeaderMap::default();
+    /// headers.insert_str("Accept", "application/json");
+    /// assert_eq!(headers.get_str("accept"), Some("application/json".to_string()));
+    /// ```
+    pub fn get_str(&self, name: &str) -> Option<String> {
+        self.inner
+            .get(&Name::from(name))?
+            .iter()
+            .map(|t| core::str::from_utf8(t.as_bytes()).ok())
+            .collect::<Option<Vec<&str>>>()
+            .map(|t| t.join(", "))
+    }
+
+    /// Returns every value of the header `name` as a separate string, in the order they were
+    /// added.
+    ///
+    /// Returns `None` if the header is missing or one of its values is not valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request::HeaderMap;
+    ///
+    /// let mut headers = HeaderMap::default();
+    /// headers.append("Accept", "text/html").append("Accept", "application/json");
+    /// assert_eq!(
+    ///     headers.get_all("accept"),
+    ///     Some(vec!["text/html".to_string(), "application/json".to_string()])
+    /// );
+    /// ```
+    pub fn get_all(&self, name: &str) -> Option<Vec<String>> {
+        self.inner
+            .get(&Name::from(name))?
+            .iter()
+            .map(|t| String::from_utf8(t.to_vec()).ok())
+            .collect()
+    }
+
+    /// Adds `value` to the header `name`, keeping the values the header already has.
+    pub fn append(&mut self, name: &str, value: &str) -> &mut Self {
+        match self.inner.get_mut(&Name::from(name)) {
+            Some(values) => values.push(Value::from(value)),
+            None => {
+                self.insert((name, value).into());
+            }
+        }
+        self
+    }
+
+    /// Sets the header `name` to the single value `value`, replacing all previous values of the
+    /// header.
+    pub fn insert_str(&mut self, name: &str, value: &str) -> &mut Self {
+        self.remove(name);
+        self.insert((name, value).into())
+    }
+
+    /// Returns the `Content-Type` header, including parameters like `charset`.
+    pub fn content_type(&self) -> Option<String> {
+        self.get_str("content-type")
+    }
+
+    /// Removes the header `name` and returns its values.
+    pub fn remove(&mut self, name: &str) -> Option<Values> {
+        self.inner.remove(&Name::from(name))
+    }
+
     pub fn iter(&self) -> hashbrown::hash_map::Iter<'_, Name, Values> {
         self.inner.iter()
     }
@@ -157,7 +239,7 @@ impl TryFrom<&HeaderMap> for reqwest::header::HeaderMap {
         for (key, values) in &map.inner {
             let key: reqwest::header::HeaderName = key.into();
             for value in values.iter() {
-                headers.insert(key.clone(), reqwest::header::HeaderValue::try_from(value)?);
+                headers.append(key.clone(), reqwest::header::HeaderValue::try_from(value)?);
             }
         }
 
@@ -192,3 +274,79 @@ impl From<&HeaderMap> for HeaderMap {
         map.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_str_round_trip() {
+        let mut headers = HeaderMap::default();
+        headers.insert_str("Content-Type", "application/json; charset=utf-8");
+        assert_eq!(
+            headers.get_str("content-type"),
+            Some("application/json; charset=utf-8".to_string())
+        );
+        assert_eq!(
+            headers.content_type(),
+            Some("application/json; charset=utf-8".to_string())
+        );
+
+        // Inserting replaces the header, whatever the case of its name.
+        headers.insert_str("CONTENT-TYPE", "text/plain");
+        assert_eq!(headers.content_type(), Some("text/plain".to_string()));
+        assert_eq!(headers.iter().count(), 1);
+
+        assert_eq!(
+            headers.remove("content-type").map(Vec::<String>::from),
+            Some(vec!["text/plain".to_string()])
+        );
+        assert_eq!(headers.get_str("Content-Type"), None);
+        assert!(headers.remove("Content-Type").is_none());
+    }
+
+    #[test]
+    fn test_multi_value_headers() {
+        let mut headers = HeaderMap::default();
+        headers.insert(Header(
+            "Accept".into(),
+            vec!["text/html", "application/json"].into(),
+        ));
+        assert_eq!(
+            headers.get_str("accept"),
+            Some("text/html, application/json".to_string())
+        );
+
+        headers.insert(Header("X-Binary".into(), Value(vec![0xff, 0xfe]).into()));
+        assert_eq!(headers.get_str("x-binary"), None);
+
+        let removed = headers.remove("ACCEPT").unwrap();
+        assert_eq!(removed.len(), 2);
+        assert!(headers.get("Accept").is_none());
+    }
+
+    #[test]
+    fn test_append_and_get_all() {
+        let mut headers = HeaderMap::default();
+        assert_eq!(headers.get_all("accept"), None);
+
+        headers
+            .append("Accept", "text/html")
+            .append("ACCEPT", "application/json");
+        assert_eq!(
+            headers.get_all("accept"),
+            Some(vec![
+                "text/html".to_string(),
+                "application/json".to_string()
+            ])
+        );
+
+        // Every value is sent as a separate header line.
+        let converted = reqwest::header::HeaderMap::try_from(&headers).unwrap();
+        assert_eq!(converted.get_all("accept").iter().count(), 2);
+
+        headers.append("X-Binary", "valid");
+        headers.get_mut("X-Binary").unwrap().push(Value(vec![0xff]));
+        assert_eq!(headers.get_all("x-binary"), None);
+    }
+}
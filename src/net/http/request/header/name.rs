@@ -1,10 +1,30 @@
 use alloc::string::{String, ToString};
+use core::hash::{Hash, Hasher};
 use core::ops::Deref;
 use serde_derive::{Deserialize, Serialize};
 
-#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+/// The name of a header. Header names are case-insensitive, so names that only differ in case
+/// are equal and hash the same, while the name keeps the case it was created with.
+#[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Name<T = String>(pub T);
 
+impl<T: AsRef<str>> PartialEq for Name<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_ref().eq_ignore_ascii_case(other.0.as_ref())
+    }
+}
+
+impl<T: AsRef<str>> Eq for Name<T> {}
+
+impl<T: AsRef<str>> Hash for Name<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let name = self.0.as_ref();
+        state.write_usize(name.len());
+        name.bytes()
+            .for_each(|t| state.write_u8(t.to_ascii_lowercase()));
+    }
+}
+
 impl Deref for Name {
     type Target = String;
 
@@ -48,3 +68,22 @@ impl From<&reqwest::header::HeaderName> for Name {
         Name(name.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::http::request::header::HeaderMap;
+
+    #[test]
+    fn test_case_insensitive_names() {
+        assert_eq!(Name::from("Content-Type"), Name::from("content-type"));
+        assert_ne!(Name::from("Content-Type"), Name::from("Content-Length"));
+
+        let mut headers = HeaderMap::default();
+        headers.insert(("Content-Type", "application/json").into());
+        let header = headers.get("content-type").unwrap();
+        assert_eq!(header.0 .0, "Content-Type");
+        assert_eq!(header.1.to_header_string(), "application/json");
+        assert!(headers.get_mut("CONTENT-TYPE").is_some());
+    }
+}
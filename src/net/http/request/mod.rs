@@ -1,21 +1,27 @@
 pub mod body;
 pub mod client;
+pub mod encoding;
 pub mod error;
 pub mod header;
+pub mod redirect;
 pub mod request_builder;
 pub mod response;
+pub mod retry;
 pub mod status_code;
 pub mod url;
 pub mod utils;
 
 pub use {
-    body::Body,
-    client::Client,
+    body::{Body, Multipart, MultipartPart},
+    client::{Client, CookieJar},
+    encoding::ContentEncoding,
     error::Error,
     header::Header,
     header::HeaderMap,
+    redirect::RedirectPolicy,
     request_builder::{Builder, Method},
     response::Response,
+    retry::{Backoff, RetryConditions, RetryPolicy, RetryableError},
     status_code::StatusCode,
     url::Url,
 };
@@ -23,8 +29,15 @@ pub use {
 #[cfg(test)]
 mod tests {
 
-    use crate::net::http::request::Builder;
+    use crate::common::TryDefault;
+    use crate::net::http::request::{
+        Backoff, Builder, Client, ContentEncoding, Error, HeaderMap, RedirectPolicy, Response,
+        RetryConditions, RetryPolicy, Url,
+    };
     use crate::net::http::test::test_server::start_test_server;
+    use std::collections::HashMap;
+    use std::net::SocketAddr;
+    use std::sync::Arc;
     use tokio::time::{sleep, Duration};
 
     #[tokio::test]
@@ -94,4 +107,530 @@ mod tests {
         // Shut down the server
         tx.send(()).unwrap();
     }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let (addr, tx) = start_test_server().await;
+
+        let url = format!("http://{}/slow", addr);
+        let result = Builder::GET
+            .url(&url)
+            .timeout(Duration::from_millis(200))
+            .send()
+            .await;
+        assert!(matches!(result, Err(super::Error::Request(e)) if e.is_timeout()));
+
+        tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_request_retry() {
+        let (addr, tx) = start_test_server().await;
+
+        let url = format!("http://{}/flaky/retry", addr);
+        let response = Builder::GET
+            .url(&url)
+            .retry(RetryPolicy::new(3).backoff(Backoff::Fixed(Duration::from_millis(10))))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(response.body().into_str().await.unwrap(), "recovered");
+
+        tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_request_retry_non_idempotent() {
+        let (addr, tx) = start_test_server().await;
+
+        let policy = RetryPolicy::new(3).backoff(Backoff::Fixed(Duration::from_millis(10)));
+        let url = format!("http://{}/flaky/post", addr);
+        let response = Builder::POST
+            .url(&url)
+            .retry(policy.clone())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 503);
+
+        let url = format!("http://{}/flaky/post-opt-in", addr);
+        let response = Builder::POST
+            .url(&url)
+            .retry(policy)
+            .retry_on(RetryConditions::default().retry_non_idempotent(true))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+
+        tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_request_without_retry() {
+        let (addr, tx) = start_test_server().await;
+
+        let url = format!("http://{}/flaky/no-retry", addr);
+        let response = Builder::GET.url(&url).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 503);
+
+        tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_response_byte_stream() {
+        use futures::StreamExt;
+
+        let (addr, tx) = start_test_server().await;
+
+        let url = format!("http://{}", addr);
+        let response = Builder::GET.url(&url).send().await.unwrap();
+        let chunks = response
+            .into_byte_stream()
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(chunks.concat(), b"GET response");
+
+        tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_response_copy_to() {
+        let (addr, tx) = start_test_server().await;
+
+        let url = format!("http://{}", addr);
+        let response = Builder::POST.url(&url).send().await.unwrap();
+        let mut buffer = Vec::new();
+        let written = response.copy_to(&mut buffer).await.unwrap();
+        assert_eq!(written, 13);
+        assert_eq!(buffer, b"POST response");
+
+        tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_multipart_request() {
+        let (addr, tx) = start_test_server().await;
+
+        let url = format!("http://{}/multipart", addr);
+        let response = Builder::POST
+            .url(&url)
+            .content_type("application/json")
+            .multipart_text("title", "holiday")
+            .multipart_file("photo", "beach.txt", b"sand and sea".to_vec(), "text/plain")
+            .send()
+            .await
+            .unwrap();
+        let echo: serde_json::Value =
+            serde_json::from_str(&response.body().into_str().await.unwrap()).unwrap();
+
+        assert!(echo["content_type"]
+            .as_str()
+            .unwrap()
+            .starts_with("multipart/form-data; boundary="));
+        assert_eq!(
+            echo["parts"],
+            serde_json::json!([
+                {"name": "title", "filename": null, "content_type": null, "content": "holiday"},
+                {"name": "photo", "filename": "beach.txt", "content_type": "text/plain", "content": "sand and sea"},
+            ])
+        );
+
+        tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_redirect_default() {
+        let (addr, tx) = start_test_server().await;
+
+        let url = format!("http://{}/redirect/2", addr);
+        let response = Builder::GET.url(&url).send().await.unwrap();
+        assert_eq!(response.body().into_str().await.unwrap(), "GET response");
+
+        tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_redirect_policy_none() {
+        let (addr, tx) = start_test_server().await;
+
+        let client = Client::with_redirect_policy(RedirectPolicy::None).unwrap();
+        let response = client
+            .get(&format!("http://{}/redirect/0", addr))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 302);
+
+        tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_redirect_policy_limited() {
+        let (addr, tx) = start_test_server().await;
+
+        let client = Client::with_redirect_policy(RedirectPolicy::Limited(2)).unwrap();
+        let result = client
+            .get(&format!("http://{}/redirect/2", addr))
+            .send()
+            .await;
+        assert!(matches!(result, Err(Error::TooManyRedirects)));
+
+        let client = Client::with_redirect_policy(RedirectPolicy::Limited(3)).unwrap();
+        let response = client
+            .get(&format!("http://{}/redirect/2", addr))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+
+        tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_redirect_policy_custom() {
+        let (addr, tx) = start_test_server().await;
+
+        let same_origin = RedirectPolicy::Custom(Arc::new(|url: &Url| {
+            url::Url::from(url).host_str() == Some("127.0.0.1")
+        }));
+
+        let client = Client::with_redirect_policy(same_origin.clone()).unwrap();
+        let response = client
+            .get(&format!("http://{}/redirect/1", addr))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+
+        let client = Client::with_redirect_policy(same_origin).unwrap();
+        let result = client
+            .get(&format!("http://{}/redirect/external", addr))
+            .send()
+            .await;
+        match result {
+            Err(Error::RedirectBlocked(url)) => {
+                assert_eq!(
+                    url.to_string(),
+                    format!("http://localhost:{}/", addr.port())
+                )
+            }
+            other => panic!("expected a blocked redirect, got {:?}", other),
+        }
+
+        tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_client_try_default() {
+        let (addr, tx) = start_test_server().await;
+
+        let client = Client::try_default().unwrap();
+        let response = client
+            .get(&format!("http://{}", addr))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.body().into_str().await.unwrap(), "GET response");
+
+        tx.send(()).unwrap();
+    }
+
+    #[cfg(feature = "tls")]
+    #[tokio::test]
+    async fn test_mtls_identity() {
+        use crate::net::http::test::test_server::start_mtls_test_server;
+
+        let (addr, tx) = start_mtls_test_server().await;
+        let url = format!("https://localhost:{}", addr.port());
+        let ca = include_bytes!("../test/fixtures/ca.pem");
+        let cert = include_bytes!("../test/fixtures/client.pem");
+
+        let client = Client::new().with_root_ca(ca).unwrap();
+        assert!(matches!(
+            client.get(&url).send().await,
+            Err(Error::Request(_))
+        ));
+
+        for key in [
+            &include_bytes!("../test/fixtures/client.key")[..],
+            &include_bytes!("../test/fixtures/client-pkcs1.key")[..],
+        ] {
+            let client = Client::new()
+                .with_root_ca(ca)
+                .unwrap()
+                .with_identity(cert, key)
+                .unwrap();
+            let response = client.get(&url).send().await.unwrap();
+            assert_eq!(response.body().into_str().await.unwrap(), "GET response");
+        }
+
+        assert!(Client::new().with_identity(cert, b"not a key").is_err());
+
+        tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_pool_and_http2_options() {
+        let (addr, tx) = start_test_server().await;
+
+        let url = format!("http://{}", addr);
+        let client = Client::new().with_pool(4, Duration::from_secs(30)).unwrap();
+        let response = client.get(&url).send().await.unwrap();
+        assert_eq!(response.body().into_str().await.unwrap(), "GET response");
+
+        let client = Client::new()
+            .with_pool(4, Duration::from_secs(30))
+            .unwrap()
+            .http2_prior_knowledge(true)
+            .unwrap();
+        let response = client.get(&url).send().await.unwrap();
+        assert_eq!(response.body().into_str().await.unwrap(), "GET response");
+
+        tx.send(()).unwrap();
+    }
+
+    #[cfg(feature = "net-rate-limit")]
+    #[tokio::test]
+    async fn test_rate_limited_client() {
+        use crate::net::rate_limit::RateLimiter;
+
+        let (addr, tx) = start_test_server().await;
+
+        let url = format!("http://{}", addr);
+        let client = Arc::new(Client::new().with_rate_limiter(Arc::new(RateLimiter::new(1.0, 1))));
+        let start = std::time::Instant::now();
+        for _ in 0..3 {
+            let response = Builder::GET
+                .client(client.clone())
+                .url(&url)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status().as_u16(), 200);
+        }
+        // The first request is sent right away, the other two wait a second each.
+        assert!(start.elapsed() >= Duration::from_secs(2));
+
+        tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_auto_decompress() {
+        let (addr, tx) = start_test_server().await;
+
+        let url = format!("http://{}/gzip", addr);
+        let response = Builder::GET.url(&url).send().await.unwrap();
+        assert!(response.headers().get("content-encoding").is_none());
+        assert_eq!(
+            response.body().into_str().await.unwrap(),
+            "decompressed response"
+        );
+
+        let client = Client::new().with_auto_decompress(false);
+        let response = client.get(&url).send().await.unwrap();
+        assert!(response.headers().get("content-encoding").is_some());
+        let raw = response.body().into_bytes().await.unwrap();
+        assert_eq!(
+            ContentEncoding::Gzip.decode(&raw).unwrap(),
+            b"decompressed response"
+        );
+
+        let client = Client::new().with_max_decompressed_size(8);
+        assert!(matches!(
+            client.get(&url).send().await,
+            Err(Error::DecompressedTooLarge(8))
+        ));
+
+        tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_default_headers() {
+        let (addr, tx) = start_test_server().await;
+
+        let mut defaults = HeaderMap::default();
+        defaults.insert(("User-Agent", "gearbox-test/1.0").into());
+        defaults.insert(("X-Tenant", "default").into());
+        let client = Arc::new(Client::new().with_default_headers(defaults));
+
+        let url = format!("http://{}/headers", addr);
+        let response = Builder::GET
+            .client(client.clone())
+            .url(&url)
+            .send()
+            .await
+            .unwrap();
+        let headers: HashMap<String, String> =
+            serde_json::from_str(&response.body().into_str().await.unwrap()).unwrap();
+        assert_eq!(headers["user-agent"], "gearbox-test/1.0");
+        assert_eq!(headers["x-tenant"], "default");
+
+        let response = Builder::GET
+            .client(client)
+            .url(&url)
+            .header(("x-tenant", "acme"))
+            .send()
+            .await
+            .unwrap();
+        let headers: HashMap<String, String> =
+            serde_json::from_str(&response.body().into_str().await.unwrap()).unwrap();
+        assert_eq!(headers["user-agent"], "gearbox-test/1.0");
+        assert_eq!(headers["x-tenant"], "acme");
+
+        tx.send(()).unwrap();
+    }
+
+    /// Sends a request that makes the test server answer with `payload`.
+    async fn echo(addr: SocketAddr, payload: &str) -> Response {
+        let body = serde_json::json!({ "status": 200, "payload": payload, "headers": {} });
+        Builder::POST
+            .url(format!("http://{}", addr))
+            .body(body.to_string())
+            .send()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_pointer() {
+        let (addr, tx) = start_test_server().await;
+
+        let response = echo(
+            addr,
+            r#"{"user": {"name": "alice", "roles": ["admin", "dev"]}}"#,
+        )
+        .await;
+        assert_eq!(response.pointer("/user/name").await.unwrap(), "alice");
+        assert_eq!(response.pointer("/user/roles/1").await.unwrap(), "dev");
+        assert!(matches!(
+            response.pointer("/user/email").await,
+            Err(Error::PointerNotFound(pointer)) if pointer == "/user/email"
+        ));
+        // The body is still available after evaluating pointers.
+        assert!(response.body().into_str().await.unwrap().contains("alice"));
+
+        let response = echo(addr, "plain text").await;
+        assert!(matches!(
+            response.pointer("/user").await,
+            Err(Error::DeserializeJson(_))
+        ));
+
+        tx.send(()).unwrap();
+    }
+
+    #[cfg(all(feature = "serde-cbor", feature = "serde-yaml"))]
+    #[tokio::test]
+    async fn test_decode_by_content_type() {
+        #[derive(Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+        struct User {
+            name: String,
+            age: u8,
+        }
+
+        let (addr, tx) = start_test_server().await;
+        let url = format!("http://{}/echo", addr);
+        let alice = User {
+            name: "alice".to_string(),
+            age: 30,
+        };
+
+        let response = Builder::POST
+            .url(&url)
+            .content_type("application/yaml; charset=utf-8")
+            .body(serde_yaml::to_string(&alice).unwrap())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.decode::<User>().await.unwrap(), alice);
+
+        let response = Builder::POST
+            .url(&url)
+            .content_type("application/cbor")
+            .body(serde_cbor::to_vec(&alice).unwrap())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.decode::<User>().await.unwrap(), alice);
+
+        // Without a content type the body is decoded as JSON.
+        let response = Builder::POST
+            .url(&url)
+            .body(serde_json::to_string(&alice).unwrap())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.decode::<User>().await.unwrap(), alice);
+
+        let response = Builder::POST
+            .url(&url)
+            .content_type("application/unknown")
+            .body("alice")
+            .send()
+            .await
+            .unwrap();
+        assert!(matches!(
+            response.decode::<User>().await,
+            Err(Error::Decode(_))
+        ));
+
+        tx.send(()).unwrap();
+    }
+
+    #[derive(serde_derive::Serialize)]
+    struct QueryParams {
+        a: u32,
+        b: String,
+    }
+
+    #[test]
+    fn test_query() {
+        let params = QueryParams {
+            a: 1,
+            b: "two".to_string(),
+        };
+        let builder = Builder::GET
+            .url("http://example.com/items")
+            .query(&params)
+            .unwrap();
+        assert_eq!(
+            builder.get_uri().unwrap().to_string(),
+            "http://example.com/items?a=1&b=two"
+        );
+
+        let builder = Builder::GET
+            .url("http://example.com/items?limit=10#top")
+            .query(&params)
+            .unwrap();
+        assert_eq!(
+            builder.get_uri().unwrap().to_string(),
+            "http://example.com/items?limit=10&a=1&b=two#top"
+        );
+    }
+
+    #[test]
+    fn test_query_encoding() {
+        let params = QueryParams {
+            a: 1,
+            b: "x&y=z/ü".to_string(),
+        };
+        let builder = Builder::GET
+            .url("http://example.com/")
+            .query(&params)
+            .unwrap()
+            .query_pair("c d", "e+f&g");
+        let url = url::Url::from(builder.get_uri().unwrap());
+        assert_eq!(
+            url.query_pairs().into_owned().collect::<Vec<_>>(),
+            vec![
+                ("a".to_string(), "1".to_string()),
+                ("b".to_string(), "x&y=z/ü".to_string()),
+                ("c d".to_string(), "e+f&g".to_string()),
+            ]
+        );
+    }
 }
@@ -0,0 +1,92 @@
+//! Redirect policies for the HTTP client.
+//!
+//! By default redirects are followed up to 10 times. A `RedirectPolicy` passed to
+//! `Client::with_redirect_policy` replaces that behaviour. Redirects rejected by a policy surface
+//! as `Error::RedirectBlocked` or `Error::TooManyRedirects` instead of returning the 3xx response.
+//!
+//! # Examples
+//!
+//! Only following redirects that stay on the same host:
+//!
+//! ```
+//! use gearbox::net::http::request::{Client, RedirectPolicy, Url};
+//! use std::sync::Arc;
+//!
+//! let policy = RedirectPolicy::Custom(Arc::new(|url: &Url| {
+//!     url::Url::from(url).host_str() == Some("example.com")
+//! }));
+//! let client = Client::with_redirect_policy(policy).unwrap();
+//! ```
+use super::Url;
+use alloc::sync::Arc;
+use core::fmt;
+
+/// The maximum number of redirects followed by a `RedirectPolicy::Custom` policy.
+const CUSTOM_REDIRECT_LIMIT: usize = 10;
+
+/// Describes which redirects the client follows.
+#[derive(Clone)]
+pub enum RedirectPolicy {
+    /// Never follows redirects, the 3xx response is returned as is.
+    None,
+    /// Follows up to the given number of redirects.
+    Limited(usize),
+    /// Follows a redirect only if the predicate accepts its target URL. At most 10 redirects are
+    /// followed.
+    Custom(Arc<dyn Fn(&Url) -> bool + Send + Sync>),
+}
+
+impl RedirectPolicy {
+    pub(crate) fn into_reqwest(self) -> reqwest::redirect::Policy {
+        match self {
+            RedirectPolicy::None => reqwest::redirect::Policy::none(),
+            RedirectPolicy::Limited(max) => reqwest::redirect::Policy::custom(move |attempt| {
+                if attempt.previous().len() > max {
+                    attempt.error(RedirectError::TooMany)
+                } else {
+                    attempt.follow()
+                }
+            }),
+            RedirectPolicy::Custom(predicate) => {
+                reqwest::redirect::Policy::custom(move |attempt| {
+                    let url = Url::from(attempt.url());
+                    if !predicate(&url) {
+                        attempt.error(RedirectError::Blocked(url))
+                    } else if attempt.previous().len() > CUSTOM_REDIRECT_LIMIT {
+                        attempt.error(RedirectError::TooMany)
+                    } else {
+                        attempt.follow()
+                    }
+                })
+            }
+        }
+    }
+}
+
+impl fmt::Debug for RedirectPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RedirectPolicy::None => write!(f, "None"),
+            RedirectPolicy::Limited(max) => f.debug_tuple("Limited").field(max).finish(),
+            RedirectPolicy::Custom(_) => write!(f, "Custom(<predicate>)"),
+        }
+    }
+}
+
+/// The reason a redirect was rejected, carried as the source of the `reqwest::Error`.
+#[derive(Debug)]
+pub(crate) enum RedirectError {
+    Blocked(Url),
+    TooMany,
+}
+
+impl fmt::Display for RedirectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RedirectError::Blocked(url) => write!(f, "redirect to {} blocked by policy", url),
+            RedirectError::TooMany => write!(f, "too many redirects"),
+        }
+    }
+}
+
+impl std::error::Error for RedirectError {}
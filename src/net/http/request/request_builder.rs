@@ -50,6 +50,7 @@
 
 use super::{Body, Client};
 use crate::net::http::request::body::BodyOwned;
+use crate::net::http::request::client::DEFAULT_MAX_DECOMPRESSED_SIZE;
 #[cfg(feature = "net-signature")]
 use crate::net::http::request::client::GLOBAL_SIGNING;
 use crate::net::http::request::{Error, Header, HeaderMap, Response, Url};
@@ -60,10 +61,15 @@ use crate::net::signature::Signature;
 use crate::error::DynTracerError;
 use crate::net::http::request::header::values::Values;
 use crate::net::http::request::header::Name;
+use crate::net::http::request::{Multipart, RetryConditions, RetryPolicy};
+use crate::rails::ext::future::retry_if;
 use crate::rails::ext::syn::{RailsMapErrInto, RailsMapInto};
-use alloc::{string::String, sync::Arc};
+use alloc::{format, string::String, sync::Arc};
+use base64::Engine;
+use bytes::Bytes;
 use core::fmt;
 use core::future::Future;
+use core::time::Duration;
 use crate_serde::ser::{self, SerializeStruct};
 use crate_serde::{Deserializer, Serializer};
 use serde_derive::{Deserialize, Serialize};
@@ -81,6 +87,9 @@ pub struct Builder {
     headers: Option<HeaderMap>,
     body: Option<BodyOwned>,
     content_type: Option<String>,
+    timeout: Option<Duration>,
+    retry: Option<RetryPolicy>,
+    retry_on: Option<RetryConditions>,
     signature: Option<Signature>,
 }
 
@@ -95,6 +104,9 @@ pub struct Builder {
     headers: Option<HeaderMap>,
     body: Option<BodyOwned>,
     content_type: Option<String>,
+    timeout: Option<Duration>,
+    retry: Option<RetryPolicy>,
+    retry_on: Option<RetryConditions>,
 }
 
 impl Builder {
@@ -220,6 +232,9 @@ impl Builder {
             headers: None,
             body: None,
             content_type: None,
+            timeout: None,
+            retry: None,
+            retry_on: None,
             #[cfg(feature = "net-signature")]
             signature: None,
         }
@@ -251,6 +266,9 @@ impl Builder {
             headers: None,
             body: None,
             content_type: None,
+            timeout: None,
+            retry: None,
+            retry_on: None,
             #[cfg(feature = "net-signature")]
             signature: GLOBAL_SIGNING.read().clone(),
         }
@@ -276,6 +294,45 @@ impl Builder {
         self
     }
 
+    /// Sets the `Authorization` header to a bearer token, replacing any earlier authorization.
+    ///
+    /// The header is part of the request headers right away, so a request signature covers it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request::Builder;
+    ///
+    /// let builder = Builder::GET.bearer_auth("my-token");
+    /// ```
+    pub fn bearer_auth(mut self, token: &str) -> Self {
+        self.headers
+            .get_or_insert(HeaderMap::default())
+            .insert(("Authorization", format!("Bearer {}", token).as_str()).into());
+        self
+    }
+
+    /// Sets the `Authorization` header to HTTP basic authentication, replacing any earlier
+    /// authorization. Without a password the credentials are encoded as `user:`.
+    ///
+    /// The header is part of the request headers right away, so a request signature covers it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request::Builder;
+    ///
+    /// let builder = Builder::GET.basic_auth("alice", Some("s3cr3t"));
+    /// ```
+    pub fn basic_auth(mut self, user: &str, password: Option<&str>) -> Self {
+        let credentials = format!("{}:{}", user, password.unwrap_or_default());
+        let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+        self.headers
+            .get_or_insert(HeaderMap::default())
+            .insert(("Authorization", format!("Basic {}", encoded).as_str()).into());
+        self
+    }
+
     /// Enables signing for the request with a default signature.
     ///
     /// # Examples
@@ -312,6 +369,88 @@ impl Builder {
         self
     }
 
+    /// Sets the client used to send the request. Without a client, every request is sent with a
+    /// new default client.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `Client` to send the request with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request::{Builder, Client};
+    ///
+    /// let builder = Builder::GET.client(Client::new());
+    /// ```
+    pub fn client<C: Into<Arc<Client>>>(mut self, client: C) -> Self {
+        self.client = Some(client.into());
+        self
+    }
+
+    /// Sets a timeout for the request. The timeout covers everything from connecting until the
+    /// response body has been read, and applies to every attempt separately.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The maximum duration of a single attempt.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::time::Duration;
+    /// use gearbox::net::http::request::Builder;
+    ///
+    /// let builder = Builder::GET.timeout(Duration::from_secs(5));
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the retry policy of the request. Without a policy the request is attempted once.
+    /// Which outcomes are retried is set with `retry_on`.
+    ///
+    /// The request body is read into memory before the first attempt, so every retry sends the
+    /// same body again instead of re-running the body source.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry` - The `RetryPolicy` to apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request::{Builder, RetryPolicy};
+    ///
+    /// let builder = Builder::GET.retry(RetryPolicy::new(3));
+    /// ```
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// Sets which outcomes of the request are retried by the retry policy. Without conditions
+    /// the defaults of `RetryConditions` apply.
+    ///
+    /// # Arguments
+    ///
+    /// * `conditions` - The `RetryConditions` to apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request::{Builder, RetryConditions, RetryPolicy};
+    ///
+    /// let builder = Builder::POST
+    ///     .retry(RetryPolicy::new(3))
+    ///     .retry_on(RetryConditions::default().retry_non_idempotent(true));
+    /// ```
+    pub fn retry_on(mut self, conditions: RetryConditions) -> Self {
+        self.retry_on = Some(conditions);
+        self
+    }
+
     /// Sends the constructed request and returns the response.
     ///
     /// # Errors
@@ -337,28 +476,98 @@ impl Builder {
     /// ```
     pub async fn send(mut self) -> Result<Response, Error> {
         let uri: reqwest::Url = self.url.as_ref().map(|t| t.into()).ok_or(Error::NoUrl)?;
-        let request = (&self.method).into();
-        let headers = self
+        let request: reqwest::Method = (&self.method).into();
+        let mut headers: reqwest::header::HeaderMap = self
             .headers
             .get_or_insert(HeaderMap::default())
             .clone()
             .try_into()
             .map_err_into::<Error>()?;
-        let body = self
-            .body
-            .get_or_insert(BodyOwned::default())
-            .into_bytes()
-            .await
-            .map_err(Error::BodyError)?;
-
-        reqwest::Client::new()
-            .request(request, uri)
-            .headers(headers)
-            .body(body)
-            .send()
-            .await
-            .map_err_into()
-            .map_into()
+        if let Some(client) = &self.client {
+            let defaults: reqwest::header::HeaderMap = client
+                .default_headers
+                .clone()
+                .try_into()
+                .map_err_into::<Error>()?;
+            for name in defaults.keys() {
+                if !headers.contains_key(name) {
+                    for value in defaults.get_all(name) {
+                        headers.append(name.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        let multipart = match self.body.as_ref().map(|t| t.body.lock().as_ref().clone()) {
+            Some(Body::Multipart(multipart)) => Some(multipart),
+            _ => None,
+        };
+        let body = match multipart {
+            // The multipart content type carries the generated boundary, so it always replaces
+            // a manually set content type.
+            Some(_) => {
+                headers.remove(reqwest::header::CONTENT_TYPE);
+                Bytes::new()
+            }
+            None => self
+                .body
+                .get_or_insert(BodyOwned::default())
+                .into_bytes()
+                .await
+                .map_err(Error::BodyError)?,
+        };
+
+        let client = self
+            .client
+            .as_ref()
+            .map(|t| t.client.clone())
+            .unwrap_or_default();
+        let auto_decompress = self.client.as_ref().is_none_or(|t| t.auto_decompress);
+        let max_decompressed_size = self
+            .client
+            .as_ref()
+            .map_or(DEFAULT_MAX_DECOMPRESSED_SIZE, |t| t.max_decompressed_size);
+        #[cfg(feature = "net-rate-limit")]
+        let rate_limiter = self.client.as_ref().and_then(|t| t.rate_limiter.clone());
+        let retry = self.retry.take().unwrap_or_else(RetryPolicy::never);
+        let conditions = self.retry_on.take().unwrap_or_default();
+        let timeout = self.timeout;
+        let mut attempts = 0;
+        let outcome = retry_if(
+            || {
+                attempts += 1;
+                let builder = client
+                    .request(request.clone(), uri.clone())
+                    .headers(headers.clone());
+                let builder = match &multipart {
+                    Some(multipart) => multipart.to_form().map(|form| builder.multipart(form)),
+                    None => Ok(builder.body(body.clone())),
+                };
+                #[cfg(feature = "net-rate-limit")]
+                let rate_limiter = rate_limiter.clone();
+                async move {
+                    let mut builder = builder?;
+                    if let Some(timeout) = timeout {
+                        builder = builder.timeout(timeout);
+                    }
+                    #[cfg(feature = "net-rate-limit")]
+                    if let Some(limiter) = &rate_limiter {
+                        limiter.acquire().await;
+                    }
+                    builder.send().await
+                }
+            },
+            retry,
+            |_, outcome| conditions.should_retry(&request, outcome),
+        )
+        .await;
+
+        let raw = outcome.map_err_into::<Error>()?;
+        let response = if auto_decompress {
+            Response::decompressed(raw, max_decompressed_size).await?
+        } else {
+            Response::from(raw)
+        };
+        Ok(response.with_attempts(attempts))
     }
 }
 
@@ -495,6 +704,60 @@ impl Builder {
         self
     }
 
+    /// Adds a text field to a `multipart/form-data` body. A body that is not multipart yet is
+    /// replaced by a multipart body.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the field.
+    /// * `value` - The value of the field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request::Builder;
+    ///
+    /// let builder = Builder::POST.multipart_text("title", "holiday");
+    /// ```
+    pub fn multipart_text(self, name: &str, value: &str) -> Self {
+        self.update_multipart(|multipart| multipart.text(name, value))
+    }
+
+    /// Adds a file field to a `multipart/form-data` body. A body that is not multipart yet is
+    /// replaced by a multipart body.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the field.
+    /// * `filename` - The file name sent with the field.
+    /// * `bytes` - The content of the file.
+    /// * `mime` - The mime type of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request::Builder;
+    ///
+    /// let builder = Builder::POST.multipart_file(
+    ///     "photo",
+    ///     "beach.png",
+    ///     vec![0x89, 0x50, 0x4e, 0x47],
+    ///     "image/png",
+    /// );
+    /// ```
+    pub fn multipart_file(self, name: &str, filename: &str, bytes: Vec<u8>, mime: &str) -> Self {
+        self.update_multipart(|multipart| multipart.file(name, filename, bytes, mime))
+    }
+
+    fn update_multipart<F: FnOnce(Multipart) -> Multipart>(mut self, f: F) -> Self {
+        let multipart = match self.body.take().map(|t| *t.body.into_inner()) {
+            Some(Body::Multipart(multipart)) => multipart,
+            _ => Multipart::new(),
+        };
+        self.body = Some(BodyOwned::from(Body::Multipart(f(multipart))));
+        self
+    }
+
     /// Gets a mutable reference to the body of the request.
     ///
     /// # Examples
@@ -560,6 +823,91 @@ impl Builder {
     pub fn url_mut(&mut self) -> &mut Option<Url> {
         &mut self.url
     }
+
+    /// Appends the query string encoded from `params` to the URL of the request. Parameters
+    /// already present in the URL are kept, the new parameters are added after them.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - A serializable value, usually a struct or a map, encoded with `serde_qs`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::QueryEncoding` if `params` can not be encoded as a query string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request::Builder;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let params = BTreeMap::from([("page", "2"), ("sort", "name")]);
+    /// let builder = Builder::GET
+    ///     .url("https://example.com/items?limit=10")
+    ///     .query(&params)
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     builder.get_uri().unwrap().to_string(),
+    ///     "https://example.com/items?limit=10&page=2&sort=name"
+    /// );
+    /// ```
+    pub fn query<T: crate_serde::Serialize>(mut self, params: &T) -> Result<Self, Error> {
+        let encoded = serde_qs::to_string(params)?;
+        self.append_query(&encoded);
+        Ok(self)
+    }
+
+    /// Appends a single percent-encoded query parameter to the URL of the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The name of the parameter.
+    /// * `value` - The value of the parameter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request::Builder;
+    ///
+    /// let builder = Builder::GET
+    ///     .url("https://example.com/search")
+    ///     .query_pair("q", "rust & serde")
+    ///     .query_pair("page", "1");
+    /// assert_eq!(
+    ///     builder.get_uri().unwrap().to_string(),
+    ///     "https://example.com/search?q=rust+%26+serde&page=1"
+    /// );
+    /// ```
+    pub fn query_pair(mut self, key: &str, value: &str) -> Self {
+        let encoded = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair(key, value)
+            .finish();
+        self.append_query(&encoded);
+        self
+    }
+
+    /// Appends an already encoded query string to the URL, keeping any fragment at the end.
+    fn append_query(&mut self, encoded: &str) {
+        if encoded.is_empty() {
+            return;
+        }
+        let url = self.url.as_ref().map(|t| t.to_string()).unwrap_or_default();
+        let (url, fragment) = match url.split_once('#') {
+            Some((url, fragment)) => (url, Some(fragment)),
+            None => (url.as_str(), None),
+        };
+        let separator = match url.find('?') {
+            None => "?",
+            Some(index) if index == url.len() - 1 || url.ends_with('&') => "",
+            Some(_) => "&",
+        };
+        let mut updated = format!("{}{}{}", url, separator, encoded);
+        if let Some(fragment) = fragment {
+            updated.push('#');
+            updated.push_str(fragment);
+        }
+        self.url = Some(Url::from(updated));
+    }
 }
 
 impl Builder {
@@ -797,7 +1145,10 @@ impl fmt::Debug for Builder {
             .field("uri", &self.url)
             .field("headers", &self.headers)
             .field("body", &"<Body>")
-            .field("content_type", &self.content_type);
+            .field("content_type", &self.content_type)
+            .field("timeout", &self.timeout)
+            .field("retry", &self.retry)
+            .field("retry_on", &self.retry_on);
         #[cfg(feature = "net-signature")]
         debug_struct.field("signature", &self.signature);
 
@@ -826,6 +1177,9 @@ impl Default for Builder {
             headers: None,
             body: None,
             content_type: None,
+            timeout: None,
+            retry: None,
+            retry_on: None,
             #[cfg(feature = "net-signature")]
             signature: GLOBAL_SIGNING.read().clone(),
         }
@@ -870,6 +1224,18 @@ impl ser::Serialize for Builder {
             state.serialize_field("content_type", &content_type)?;
         }
 
+        if let Some(ref timeout) = self.timeout {
+            state.serialize_field("timeout", timeout)?;
+        }
+
+        if let Some(ref retry) = self.retry {
+            state.serialize_field("retry", retry)?;
+        }
+
+        if let Some(ref retry_on) = self.retry_on {
+            state.serialize_field("retry_on", retry_on)?;
+        }
+
         #[cfg(feature = "net-signature")]
         if let Some(ref signature) = self.signature {
             state.serialize_field("signature", &signature)?;
@@ -906,6 +1272,12 @@ impl<'de> crate_serde::Deserialize<'de> for Builder {
             headers: Option<HeaderMap>,
             body: Option<String>,
             content_type: Option<String>,
+            #[serde(default)]
+            timeout: Option<Duration>,
+            #[serde(default)]
+            retry: Option<RetryPolicy>,
+            #[serde(default)]
+            retry_on: Option<RetryConditions>,
             #[cfg(feature = "net-signature")]
             signature: Option<Signature>,
         }
@@ -924,6 +1296,9 @@ impl<'de> crate_serde::Deserialize<'de> for Builder {
             headers: data.headers,
             body,
             content_type: data.content_type,
+            timeout: data.timeout,
+            retry: data.retry,
+            retry_on: data.retry_on,
             #[cfg(feature = "net-signature")]
             signature: data.signature,
         })
@@ -1095,6 +1470,33 @@ pub mod test {
     use super::Builder;
     use serde_json;
 
+    #[test]
+    fn test_auth_headers() {
+        let authorization = |builder: Builder| {
+            builder
+                .get_header("Authorization")
+                .map(|t| t.1.to_header_string())
+        };
+
+        assert_eq!(
+            authorization(Builder::GET.bearer_auth("abc.def")),
+            Some("Bearer abc.def".to_string())
+        );
+        assert_eq!(
+            authorization(Builder::GET.basic_auth("Aladdin", Some("open sesame"))),
+            Some("Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==".to_string())
+        );
+        assert_eq!(
+            authorization(Builder::GET.basic_auth("alice", None)),
+            Some("Basic YWxpY2U6".to_string())
+        );
+        // A later call replaces the earlier authorization.
+        assert_eq!(
+            authorization(Builder::GET.basic_auth("alice", None).bearer_auth("abc")),
+            Some("Bearer abc".to_string())
+        );
+    }
+
     #[test]
     fn test_builder_serialization() {
         let builder = Builder::GET;
@@ -1,7 +1,9 @@
-use super::{Error, HeaderMap, StatusCode, Url};
+use super::{Body, ContentEncoding, Error, HeaderMap, StatusCode, Url};
 use crate::net::http::request::body::BodyOwned;
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::ToString, vec, vec::Vec};
+use bytes::Bytes;
 use core::result::Result;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
 use serde::de::DeserializeOwned;
 
 #[derive(Debug)]
@@ -15,6 +17,7 @@ pub struct Response {
     url: Url,
     #[allow(unused)]
     body: BodyOwned,
+    attempts: u32,
 }
 
 impl Response {
@@ -37,12 +40,206 @@ impl Response {
     pub fn body(&self) -> &BodyOwned {
         &self.body
     }
+
+    /// Returns how many attempts were made to receive this response, including the first one.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub(crate) fn with_attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts;
+        self
+    }
     pub fn to<T>(self) -> Result<T, Error>
     where
         T: DeserializeOwned,
     {
         todo!();
     }
+
+    /// Parses the body as JSON and returns the value the RFC 6901 JSON pointer `json_pointer`
+    /// points to. This avoids deserializing the whole body when only a single field is needed.
+    ///
+    /// Returns `Error::DeserializeJson` if the body is not JSON and `Error::PointerNotFound` if
+    /// there is no value at `json_pointer`. The body stays available after this call.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gearbox::net::http::request::Builder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), gearbox::net::http::request::Error> {
+    ///     let response = Builder::GET.url("https://example.com/users/1").send().await?;
+    ///     let city = response.pointer("/address/city").await?;
+    ///     println!("city: {}", city);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn pointer(&self, json_pointer: &str) -> Result<serde_json::Value, Error> {
+        let body = self.body.into_bytes().await?;
+        let mut value: serde_json::Value = serde_json::from_slice(&body)?;
+        value
+            .pointer_mut(json_pointer)
+            .map(serde_json::Value::take)
+            .ok_or_else(|| Error::PointerNotFound(json_pointer.to_string()))
+    }
+
+    /// Deserializes the body in the format named by the `Content-Type` header, e.g.
+    /// `application/cbor` or `application/yaml`. Parameters such as `charset` are ignored and a
+    /// missing header is treated as JSON.
+    ///
+    /// Returns `Error::Decode` if the content type is not supported by the enabled `serde-*`
+    /// features or the body cannot be decoded. The body stays available after this call.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gearbox::net::http::request::Builder;
+    /// use serde_derive::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct User {
+    ///     name: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), gearbox::net::http::request::Error> {
+    ///     let response = Builder::GET.url("https://example.com/users/1").send().await?;
+    ///     let user: User = response.decode().await?;
+    ///     println!("name: {}", user.name);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn decode<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        use crate::serde::dynamic::{ContentType, Decoded, SimpleDecoder};
+
+        let content_type = self.headers.content_type();
+        let body = self.body.into_bytes().await?;
+        match content_type {
+            Some(content_type) => {
+                let media_type = content_type.split(';').next().unwrap_or_default().trim();
+                let content_type = ContentType::try_from(media_type)?;
+                let decoded: Decoded<T> = body.as_ref().decode(content_type)?;
+                Ok(decoded.into())
+            }
+            None => Ok(serde_json::from_slice(&body)?),
+        }
+    }
+
+    /// Takes the response body as a stream of chunks instead of buffering it in memory. This is
+    /// meant for large downloads; `body()` and `to()` keep buffering the complete body.
+    ///
+    /// The body can only be taken once. After the stream is taken, the body is no longer
+    /// available through `body()` or `to()`, including on clones of the body.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use gearbox::net::http::request::Builder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), gearbox::net::http::request::Error> {
+    ///     let response = Builder::GET.url("https://example.com/large").send().await?;
+    ///     let mut stream = Box::pin(response.into_byte_stream());
+    ///     while let Some(chunk) = stream.next().await {
+    ///         println!("received {} bytes", chunk?.len());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn into_byte_stream(self) -> impl Stream<Item = Result<Bytes, Error>> {
+        let body = self.body.body.lock().as_ref().clone();
+        let chunks: Vec<Result<Bytes, Error>> = match body {
+            Body::Reference(reference) => match reference.lock().take() {
+                Some(raw) => return raw.bytes_stream().map_err(Error::from).left_stream(),
+                None => vec![Err(Error::NoBody)],
+            },
+            Body::Bytes(bytes) => vec![Ok(Bytes::from(bytes))],
+            Body::Multipart(_) | Body::Empty => Vec::new(),
+        };
+        stream::iter(chunks).right_stream()
+    }
+
+    /// Streams the response body into `writer` without buffering it in memory and returns the
+    /// number of bytes written. Like `into_byte_stream`, this takes the body.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gearbox::net::http::request::Builder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), gearbox::net::http::request::Error> {
+    ///     let mut file = tokio::fs::File::create("download.bin").await?;
+    ///     let written = Builder::GET
+    ///         .url("https://example.com/large")
+    ///         .send()
+    ///         .await?
+    ///         .copy_to(&mut file)
+    ///         .await?;
+    ///     println!("downloaded {} bytes", written);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn copy_to<W>(self, writer: &mut W) -> Result<u64, Error>
+    where
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        use tokio::io::AsyncWriteExt;
+
+        let mut stream = Box::pin(self.into_byte_stream());
+        let mut written = 0;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        writer.flush().await?;
+        Ok(written)
+    }
+}
+
+impl Response {
+    /// Converts `raw` like `Response::from`, but decodes the body if the response has a
+    /// `Content-Encoding` header with known codings. Decoding buffers the complete body, and fails
+    /// once the decoded body exceeds `limit` bytes.
+    pub(crate) async fn decompressed(raw: reqwest::Response, limit: u64) -> Result<Self, Error> {
+        let encodings = match raw
+            .headers()
+            .get(reqwest::header::CONTENT_ENCODING)
+            .and_then(|t| t.to_str().ok())
+            .and_then(ContentEncoding::parse_header)
+        {
+            Some(encodings) if !encodings.is_empty() => encodings,
+            _ => return Ok(Self::from(raw)),
+        };
+
+        let mut headers = raw.headers().clone();
+        headers.remove(reqwest::header::CONTENT_ENCODING);
+        headers.remove(reqwest::header::CONTENT_LENGTH);
+        let status = raw.status().into();
+        let url = raw.url().as_ref().into();
+        let encoded = raw.bytes().await?;
+        // Codings are listed in the order they were applied, so they are undone in reverse.
+        let body = encodings
+            .iter()
+            .rev()
+            .try_fold(encoded.to_vec(), |body, encoding| {
+                encoding.decode_limited(&body, limit)
+            })?;
+
+        Ok(Self {
+            headers: (&headers).into(),
+            status,
+            content_length: Some(body.len() as u64),
+            url,
+            body: BodyOwned::from(body),
+            attempts: 1,
+        })
+    }
 }
 
 impl From<reqwest::Response> for Response {
@@ -59,6 +256,7 @@ impl From<reqwest::Response> for Response {
             content_length,
             url,
             body,
+            attempts: 1,
         }
     }
 }
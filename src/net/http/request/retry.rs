@@ -0,0 +1,168 @@
+//! Retry conditions for HTTP requests.
+//!
+//! How often a request is attempted and how long to wait between attempts is described by the
+//! `RetryPolicy` of `rails::ext::future`, attached to a request with `Builder::retry`. Which
+//! outcomes are considered transient is described by `RetryConditions`, attached with
+//! `Builder::retry_on`.
+//!
+//! # Examples
+//!
+//! ```
+//! use core::time::Duration;
+//! use gearbox::net::http::request::{
+//!     Backoff, Builder, RetryConditions, RetryPolicy, RetryableError,
+//! };
+//!
+//! let policy = RetryPolicy::new(5)
+//!     .backoff(Backoff::exponential(Duration::from_millis(50), Duration::from_secs(2)));
+//! let conditions = RetryConditions::default()
+//!     .retry_on_status(409)
+//!     .retry_on_error(RetryableError::Body);
+//!
+//! let builder = Builder::GET
+//!     .url("https://example.com")
+//!     .timeout(Duration::from_secs(10))
+//!     .retry(policy)
+//!     .retry_on(conditions);
+//! ```
+pub use crate::rails::ext::future::{Backoff, RetryPolicy};
+use alloc::{vec, vec::Vec};
+use serde_derive::{Deserialize, Serialize};
+
+/// Transport level failures of `reqwest` that can be retried.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RetryableError {
+    /// The request or the connection timed out.
+    Timeout,
+    /// The connection to the remote host could not be established.
+    Connect,
+    /// The request could not be sent.
+    Request,
+    /// The request or response body failed.
+    Body,
+}
+
+impl RetryableError {
+    fn matches(&self, error: &reqwest::Error) -> bool {
+        match self {
+            RetryableError::Timeout => error.is_timeout(),
+            RetryableError::Connect => error.is_connect(),
+            RetryableError::Request => error.is_request(),
+            RetryableError::Body => error.is_body(),
+        }
+    }
+}
+
+/// Describes which outcomes of a request are retried.
+///
+/// The default conditions retry on timeouts, connection failures and the status codes 408, 429,
+/// 500, 502, 503 and 504.
+///
+/// Requests with a non-idempotent method (`POST`, `PATCH` and `CONNECT`) may already have been
+/// processed by the server when they fail, so they are only retried when the connection could
+/// not be established, unless [`RetryConditions::retry_non_idempotent`] is enabled.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RetryConditions {
+    statuses: Vec<u16>,
+    errors: Vec<RetryableError>,
+    #[serde(default)]
+    non_idempotent: bool,
+}
+
+impl RetryConditions {
+    /// Adds a status code that is considered transient.
+    pub fn retry_on_status(mut self, status: u16) -> Self {
+        if !self.statuses.contains(&status) {
+            self.statuses.push(status);
+        }
+        self
+    }
+
+    /// Replaces the status codes that are considered transient.
+    pub fn retry_on_statuses(mut self, statuses: Vec<u16>) -> Self {
+        self.statuses = statuses;
+        self
+    }
+
+    /// Adds a kind of transport failure that is considered transient.
+    pub fn retry_on_error(mut self, error: RetryableError) -> Self {
+        if !self.errors.contains(&error) {
+            self.errors.push(error);
+        }
+        self
+    }
+
+    /// Replaces the kinds of transport failures that are considered transient.
+    pub fn retry_on_errors(mut self, errors: Vec<RetryableError>) -> Self {
+        self.errors = errors;
+        self
+    }
+
+    /// Sets whether requests with a non-idempotent method are retried like any other request.
+    pub fn retry_non_idempotent(mut self, enabled: bool) -> Self {
+        self.non_idempotent = enabled;
+        self
+    }
+
+    /// Checks whether the outcome of an attempt should be retried.
+    pub(crate) fn should_retry(
+        &self,
+        method: &reqwest::Method,
+        outcome: &Result<reqwest::Response, reqwest::Error>,
+    ) -> bool {
+        let idempotent = self.non_idempotent || is_idempotent(method);
+        match outcome {
+            Ok(response) => idempotent && self.statuses.contains(&response.status().as_u16()),
+            Err(error) => self.errors.iter().any(|kind| {
+                (idempotent || *kind == RetryableError::Connect) && kind.matches(error)
+            }),
+        }
+    }
+}
+
+impl Default for RetryConditions {
+    fn default() -> Self {
+        Self {
+            statuses: vec![408, 429, 500, 502, 503, 504],
+            errors: vec![RetryableError::Timeout, RetryableError::Connect],
+            non_idempotent: false,
+        }
+    }
+}
+
+/// Checks whether repeating a request with the given method has the same effect as sending it
+/// once.
+fn is_idempotent(method: &reqwest::Method) -> bool {
+    !matches!(
+        *method,
+        reqwest::Method::POST | reqwest::Method::PATCH | reqwest::Method::CONNECT
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conditions_builder() {
+        let conditions = RetryConditions::default()
+            .retry_on_statuses(vec![503])
+            .retry_on_status(409)
+            .retry_on_status(409)
+            .retry_on_errors(Vec::new())
+            .retry_on_error(RetryableError::Body);
+        assert_eq!(conditions.statuses, vec![503, 409]);
+        assert_eq!(conditions.errors, vec![RetryableError::Body]);
+        assert!(!conditions.non_idempotent);
+        assert!(conditions.retry_non_idempotent(true).non_idempotent);
+    }
+
+    #[test]
+    fn test_idempotent_methods() {
+        assert!(is_idempotent(&reqwest::Method::GET));
+        assert!(is_idempotent(&reqwest::Method::PUT));
+        assert!(is_idempotent(&reqwest::Method::DELETE));
+        assert!(!is_idempotent(&reqwest::Method::POST));
+        assert!(!is_idempotent(&reqwest::Method::PATCH));
+    }
+}
@@ -7,19 +7,30 @@ use crate::net::http::request;
 use crate::net::http::request::body::BodyTrait;
 use crate::rails::ext::fut::ext::result::Map;
 use crate::rails::ext::future::FutureResult;
+use crate::serde::dynamic::{
+    ContentType, Decoded, Encoded, Error as DynamicError, SimpleDecoder, SimpleEncoder,
+};
 #[cfg(target_arch = "wasm32")]
 use crate::serde::wasm_bindgen as serde_wasm_bindgen;
 use crate::template::engine::TemplateContext;
 use crate::template::{PipelineValue, TemplateEngine};
+#[cfg(target_arch = "wasm32")]
+use crate::time::DateTime;
 use crate::{tracer_dyn_err, tracer_err};
 use alloc::{string::String, sync::Arc, vec::Vec};
 use core::future::Future;
+use futures::stream::{FuturesUnordered, StreamExt};
+use regex::Regex;
 use serde::{de, ser, Deserializer, Serializer};
 use spin::Mutex;
 use std::ops::{Deref, DerefMut};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
+mod path;
+
 /// The `RequestChain` structure allows for creating a sequence of HTTP requests where the response
 /// from one request can be used to populate and forward data to the next request. This is useful
 /// for scenarios such as calling a REST API where an initial request is needed to obtain a nonce
@@ -33,6 +44,8 @@ use wasm_bindgen::prelude::*;
 pub struct RequestChain {
     template_requests: HashMap<String, RequestNode>,
     call_structures: HashMap<String, CallStructure>,
+    #[serde(default)]
+    cookie_store: bool,
 }
 
 impl RequestChain {
@@ -49,9 +62,69 @@ impl RequestChain {
         RequestChain {
             template_requests: HashMap::new(),
             call_structures: HashMap::new(),
+            cookie_store: false,
         }
     }
 
+    /// Enables or disables the cookie store. With the cookie store enabled, every run of a call
+    /// structure shares one cookie jar, so cookies set by a response are sent with the following
+    /// requests of the same run.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether cookies are kept between the requests of a run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request_chaining::*;
+    ///
+    /// let chain = RequestChain::new().with_cookie_store(true);
+    /// ```
+    pub fn with_cookie_store(mut self, enabled: bool) -> RequestChain {
+        self.cookie_store = enabled;
+        self
+    }
+
+    /// Encodes the chain in one of the formats of `serde::dynamic`, e.g. YAML or TOML, so it can
+    /// be stored and loaded again with `RequestChain::from_bytes`.
+    ///
+    /// # Arguments
+    ///
+    /// * `content_type` - The format to encode the chain in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request_chaining::*;
+    /// use gearbox::serde::dynamic::ContentType;
+    ///
+    /// let chain = RequestChain::new();
+    /// let encoded = chain.to_encoded(ContentType::Json).unwrap();
+    /// let decoded = RequestChain::from_bytes(&encoded, ContentType::Json).unwrap();
+    /// ```
+    pub fn to_encoded<F: TryInto<ContentType, Error = impl Into<DynamicError>>>(
+        &self,
+        content_type: F,
+    ) -> crate::serde::dynamic::Result<Encoded> {
+        self.encode(content_type)
+    }
+
+    /// Decodes a chain that was written in one of the formats of `serde::dynamic`. This allows
+    /// authoring chains in YAML or TOML and loading them at runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The encoded chain.
+    /// * `content_type` - The format the chain is encoded in.
+    pub fn from_bytes<F: TryInto<ContentType, Error = impl Into<DynamicError>>>(
+        bytes: &[u8],
+        content_type: F,
+    ) -> crate::serde::dynamic::Result<RequestChain> {
+        let decoded: Decoded<RequestChain> = bytes.decode(content_type)?;
+        Ok(decoded.into())
+    }
+
     /// Adds a `RequestNode` to the template requests.
     ///
     /// # Arguments
@@ -191,6 +264,34 @@ impl RequestChain {
             )
             .await
     }
+
+    /// Runs a call structure, dispatching request nodes that do not depend on each other
+    /// concurrently. See `RequestProcessor::process_concurrent` for how captured variables are
+    /// merged.
+    ///
+    /// # Arguments
+    ///
+    /// * `chain_name` - The name of the call structure to run.
+    /// * `variables` - The variables available to the templates of the requests.
+    /// * `max_parallel` - The maximum number of request nodes in flight at once.
+    pub async fn run_concurrent(
+        &self,
+        chain_name: &str,
+        variables: Vec<WasmVariable>,
+        max_parallel: usize,
+    ) -> Result<ChainResponses, DynTracerError> {
+        let mut processor = RequestProcessor::new(self.clone());
+        processor
+            .process_concurrent(
+                chain_name,
+                variables
+                    .into_iter()
+                    .map(|t| t.into())
+                    .collect::<HashMap<String, String>>(),
+                max_parallel,
+            )
+            .await
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
@@ -224,6 +325,8 @@ pub struct RequestNodeBuilder {
     requests: Vec<Builder>,
     captures: VariableCaptures,
     name: String,
+    #[serde(default)]
+    depends_on: Vec<String>,
 }
 
 /// These are extensive implementations that is not allowed under WASM. But functions that improve
@@ -271,6 +374,45 @@ impl RequestNodeBuilder {
         self
     }
 
+    /// Adds a variable capture on the response headers to the `RequestNodeBuilder`. The capture
+    /// id names the header (case-insensitive) and the matcher is applied to its value.
+    ///
+    /// # Arguments
+    ///
+    /// * `capture` - The variable capture to add.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request_chaining::*;
+    ///
+    /// let builder = RequestNodeBuilder::default().add_header_capture(VariableCapture::default());
+    /// ```
+    pub fn add_header_capture(mut self, capture: VariableCapture) -> RequestNodeBuilder {
+        self.captures.headers.push(capture);
+        self
+    }
+
+    /// Adds a variable capture on the query string of the request URL to the
+    /// `RequestNodeBuilder`. The capture id names the query parameter and the matcher is applied
+    /// to its value.
+    ///
+    /// # Arguments
+    ///
+    /// * `capture` - The variable capture to add.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request_chaining::*;
+    ///
+    /// let builder = RequestNodeBuilder::default().add_query_capture(VariableCapture::default());
+    /// ```
+    pub fn add_query_capture(mut self, capture: VariableCapture) -> RequestNodeBuilder {
+        self.captures.query.push(capture);
+        self
+    }
+
     /// Sets the name of the `RequestNodeBuilder`.
     ///
     /// # Arguments
@@ -289,6 +431,28 @@ impl RequestNodeBuilder {
         self
     }
 
+    /// Adds a dependency on another request node. The node is only executed once every node it
+    /// depends on has been executed. Dependencies that are not part of the processed call
+    /// structure are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the request node this node depends on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request_chaining::*;
+    ///
+    /// let builder = RequestNodeBuilder::default()
+    ///     .name("authorized_call")
+    ///     .depends_on("login");
+    /// ```
+    pub fn depends_on(mut self, name: &str) -> RequestNodeBuilder {
+        self.depends_on.push(name.to_string());
+        self
+    }
+
     /// Builds and returns a `RequestNode`.
     ///
     /// # Examples
@@ -303,7 +467,7 @@ impl RequestNodeBuilder {
             name: self.name,
             matcher: self.captures,
             children: self.requests,
-            depends_on: Vec::new(),
+            depends_on: self.depends_on,
         }
     }
 }
@@ -538,7 +702,8 @@ struct CaptureWrapper {
     default: Option<String>,
 }
 
-/// Captures a single variable from a response.
+/// Captures a single variable from a response. If the matcher finds nothing, the `default` value
+/// is captured instead.
 ///
 /// # Examples
 ///
@@ -575,6 +740,9 @@ pub struct Matcher {
     xpath: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     all: Option<bool>,
+    /// The compiled `regexp`, shared between clones so it is only compiled once.
+    #[serde(skip)]
+    compiled: Arc<spin::Once<Result<Regex, regex::Error>>>,
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
@@ -599,6 +767,7 @@ impl Matcher {
             regexp: None,
             xpath: None,
             all: None,
+            compiled: Arc::default(),
         }
     }
 
@@ -621,10 +790,37 @@ impl Matcher {
             regexp: Some(regexp),
             xpath: None,
             all: None,
+            compiled: Arc::default(),
+        }
+    }
+
+    /// Creates a matcher for capturing a value by its path in the response document. JSON
+    /// responses are addressed with JSON Pointer syntax, XML responses with a minimal XPath
+    /// subset (`/root/child`, `//descendant` and `@attribute`).
+    ///
+    /// # Arguments
+    ///
+    /// * `xpath` - The path of the value to capture.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request_chaining::*;
+    ///
+    /// let matcher = Matcher::xpath("/data/token".to_string());
+    /// ```
+    pub fn xpath(xpath: String) -> Matcher {
+        Matcher {
+            between: None,
+            regexp: None,
+            xpath: Some(xpath),
+            all: None,
+            compiled: Arc::default(),
         }
     }
 
-    /// Creates a matcher for capturing all text.
+    /// Creates a matcher for capturing all text. Combined with a regular expression (see
+    /// [`Matcher::capture_all`]) it captures every match instead of the entire text.
     ///
     /// # Arguments
     ///
@@ -643,8 +839,40 @@ impl Matcher {
             regexp: None,
             all: Some(all),
             xpath: None,
+            compiled: Arc::default(),
         }
     }
+
+    /// Sets whether all matches should be captured. For a regular expression matcher every match
+    /// is collected and joined with a `,`.
+    ///
+    /// # Arguments
+    ///
+    /// * `all` - Whether to capture all matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request_chaining::*;
+    ///
+    /// let matcher = Matcher::regexp(r"\d+".to_string()).capture_all(true);
+    /// ```
+    pub fn capture_all(mut self, all: bool) -> Matcher {
+        self.all = Some(all);
+        self
+    }
+}
+
+impl Matcher {
+    /// Returns the compiled regular expression of the matcher, compiling it on first use.
+    fn compiled_regexp(&self) -> Option<Result<&Regex, DynTracerError>> {
+        self.regexp.as_ref().map(|regexp| {
+            self.compiled
+                .call_once(|| Regex::new(regexp))
+                .as_ref()
+                .map_err(|e| tracer_dyn_err!(e.clone()))
+        })
+    }
 }
 
 /// Processes a request chain, capturing variables and responses.
@@ -691,7 +919,8 @@ impl RequestProcessor {
         }
     }
 
-    /// Processes a call structure in the request chain.
+    /// Processes a call structure in the request chain. The calls are executed in their declared
+    /// order, except that a request node is always executed after the nodes it depends on.
     ///
     /// # Arguments
     ///
@@ -721,9 +950,11 @@ impl RequestProcessor {
             .get(chain_name)
             .map(Clone::clone)
         {
-            for call in calls.calls() {
+            let client = self.client()?;
+            for call in self.order_calls(&calls)? {
                 if let Some(request_node) = self.request_chain.template_requests.get(&call) {
-                    self.execute_request(request_node.clone()).await?;
+                    self.execute_request(request_node.clone(), client.clone())
+                        .await?;
                 }
             }
             if self.response.responses.is_empty() {
@@ -737,12 +968,226 @@ impl RequestProcessor {
         }
     }
 
+    /// Processes a call structure in the request chain, executing request nodes concurrently once
+    /// all the nodes they depend on have finished. At most `max_parallel` nodes are in flight at
+    /// once, ready nodes are started in their declared order.
+    ///
+    /// Every node is rendered with the given variables plus the variables captured by the nodes
+    /// it (transitively) depends on. Captures of different nodes are merged in the order of their
+    /// names, so when two nodes capture the same variable the node whose name sorts last wins.
+    /// The same rule applies to the variables left on the processor after the run.
+    ///
+    /// The responses are returned in the order the nodes would have been executed sequentially.
+    ///
+    /// # Arguments
+    ///
+    /// * `chain_name` - The name of the call structure to process.
+    /// * `variables` - The variables available to the templates of the requests.
+    /// * `max_parallel` - The maximum number of request nodes in flight at once, at least 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::net::http::request_chaining::*;
+    /// use gearbox::collections::HashMap;
+    ///
+    /// let chain = RequestChain::new();
+    /// let mut processor = RequestProcessor::new(chain);
+    /// processor.process_concurrent("example_chain", HashMap::new(), 4);
+    /// ```
+    pub async fn process_concurrent(
+        &mut self,
+        chain_name: &str,
+        variables: HashMap<String, String>,
+        max_parallel: usize,
+    ) -> Result<ChainResponses, DynTracerError> {
+        let calls = self
+            .request_chain
+            .call_structures
+            .get(chain_name)
+            .cloned()
+            .ok_or_else(|| tracer_dyn_err!("Call structure not found."))?;
+        let ordered = self
+            .order_calls(&calls)?
+            .into_iter()
+            .filter(|call| self.request_chain.template_requests.contains_key(call))
+            .collect::<Vec<String>>();
+        let client = self.client()?;
+
+        let mut base = self.variables.clone();
+        variables.into_iter().for_each(|(k, v)| {
+            base.insert(k, v);
+        });
+
+        let execute = |name: String, variables: HashMap<String, String>| {
+            let request_node = self.request_chain.template_requests.get(&name).cloned();
+            let client = client.clone();
+            async move {
+                let mut processor = RequestProcessor {
+                    request_chain: RequestChain::new(),
+                    variables: variables.clone(),
+                    response: ChainResponses::default(),
+                };
+                if let Some(request_node) = request_node {
+                    processor.execute_request(request_node, client).await?;
+                }
+                let captured = processor
+                    .variables
+                    .into_iter()
+                    .filter(|(k, v)| variables.get(k) != Some(v))
+                    .collect::<HashMap<String, String>>();
+                Ok::<_, DynTracerError>((name, captured, processor.response.responses))
+            }
+        };
+
+        let mut captures: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut responses: HashMap<String, Vec<ChainResponse>> = HashMap::new();
+        let mut pending = ordered.clone();
+        let mut running = FuturesUnordered::new();
+        loop {
+            while running.len() < max_parallel.max(1) {
+                let Some(index) = pending.iter().position(|call| {
+                    self.dependencies(call, &ordered)
+                        .iter()
+                        .all(|dependency| captures.contains_key(*dependency))
+                }) else {
+                    break;
+                };
+                let call = pending.remove(index);
+                let mut ancestors = self.ancestors(&call, &ordered);
+                ancestors.sort();
+                let mut variables = base.clone();
+                for ancestor in ancestors {
+                    if let Some(captured) = captures.get(&ancestor) {
+                        captured.iter().for_each(|(k, v)| {
+                            variables.insert(k.clone(), v.clone());
+                        });
+                    }
+                }
+                running.push(execute(call, variables));
+            }
+
+            match running.next().await {
+                Some(result) => {
+                    let (name, captured, node_responses) = result?;
+                    captures.insert(name.clone(), captured);
+                    responses.insert(name, node_responses);
+                }
+                None => break,
+            }
+        }
+        drop(running);
+
+        let mut names = captures.keys().cloned().collect::<Vec<String>>();
+        names.sort();
+        for name in names {
+            if let Some(captured) = captures.remove(&name) {
+                captured.into_iter().for_each(|(k, v)| {
+                    base.insert(k, v);
+                });
+            }
+        }
+        self.variables = base;
+
+        self.response.responses = ordered
+            .iter()
+            .filter_map(|call| responses.remove(call))
+            .flatten()
+            .collect();
+        match self.response.responses.last() {
+            Some(last) => {
+                self.response.last = last.clone();
+                Ok(self.response.clone())
+            }
+            None => Err(tracer_dyn_err!("No responses were generated.")),
+        }
+    }
+
+    /// Creates the client shared by all requests of a run, if the chain requires one.
+    fn client(&self) -> Result<Option<Arc<request::Client>>, DynTracerError> {
+        if self.request_chain.cookie_store {
+            let jar = Arc::new(request::CookieJar::default());
+            Ok(Some(Arc::new(
+                request::Client::with_cookie_store(jar).map_err(|e| tracer_dyn_err!(e))?,
+            )))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns the dependencies of a request node that are part of `calls`.
+    fn dependencies<'a>(&self, call: &String, calls: &'a [String]) -> Vec<&'a String> {
+        self.request_chain
+            .template_requests
+            .get(call)
+            .map(|node| {
+                calls
+                    .iter()
+                    .filter(|t| node.depends_on.contains(t))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns all direct and indirect dependencies of a request node that are part of `calls`.
+    fn ancestors(&self, call: &String, calls: &[String]) -> Vec<String> {
+        let mut ancestors: Vec<String> = Vec::new();
+        let mut queue = self.dependencies(call, calls);
+        while let Some(dependency) = queue.pop() {
+            if !ancestors.contains(dependency) {
+                ancestors.push(dependency.clone());
+                queue.extend(self.dependencies(dependency, calls));
+            }
+        }
+        ancestors
+    }
+
+    /// Orders the calls of a call structure so that every request node comes after the nodes it
+    /// depends on. Nodes without pending dependencies keep their declared order.
+    ///
+    /// # Arguments
+    ///
+    /// * `calls` - The request names of the call structure.
+    ///
+    /// # Returns
+    ///
+    /// The ordered request names, or an error if the dependencies contain a cycle.
+    fn order_calls(&self, calls: &[String]) -> Result<Vec<String>, DynTracerError> {
+        let mut ordered: Vec<String> = Vec::with_capacity(calls.len());
+        let mut pending: Vec<&String> = calls.iter().collect();
+        while !pending.is_empty() {
+            match pending.iter().position(|call| {
+                self.dependencies(call, calls)
+                    .iter()
+                    .all(|dependency| ordered.contains(dependency))
+            }) {
+                Some(index) => ordered.push(pending.remove(index).clone()),
+                None => {
+                    return Err(tracer_dyn_err!(format!(
+                        "Dependency cycle detected between request nodes: {}",
+                        pending
+                            .iter()
+                            .map(|call| call.as_str())
+                            .collect::<Vec<&str>>()
+                            .join(", ")
+                    )))
+                }
+            }
+        }
+        Ok(ordered)
+    }
+
     /// Executes a request node and captures variables.
     ///
     /// # Arguments
     ///
     /// * `request_node` - The `RequestNode` to execute.
-    async fn execute_request(&mut self, request_node: RequestNode) -> Result<(), DynTracerError> {
+    /// * `client` - The client shared by all requests of the run, if any.
+    async fn execute_request(
+        &mut self,
+        request_node: RequestNode,
+        client: Option<Arc<request::Client>>,
+    ) -> Result<(), DynTracerError> {
         let mut context = TemplateContext::new();
 
         self.variables.iter().for_each(|(k, v)| {
@@ -751,25 +1196,13 @@ impl RequestProcessor {
 
         let context = &context.clone();
         for mut request in request_node.children {
-            // Updating headers, if the headers are using templating we are going to render them into
-            // the variables and then update the headers with the rendered values
-            request.headers_mut().iter_mut().for_each(|(k, v)| {
-                v.iter_mut().for_each(|t| {
-                    String::from_utf8(t.0.clone())
-                        .map(|s| {
-                            TemplateEngine::new()
-                                .render(&s, &context)
-                                .map(|r| {
-                                    t.0 = r.into_bytes();
-                                })
-                                .ok();
-                        })
-                        .ok();
-                });
-            });
+            render_headers(request.headers_mut(), context);
 
             request
                 .update_body(|mut t| async move {
+                    if let Body::Multipart(_) = *t {
+                        return Ok(t);
+                    }
                     let output = t.into_string().await;
                     output
                         .and_then(|r| TemplateEngine::new().render(&r, &context))
@@ -786,13 +1219,27 @@ impl RequestProcessor {
                     .ok();
             });
 
-            let response = request
+            if let Some(client) = &client {
+                request = request.client(client.clone());
+            }
+
+            // `Instant` is not available on wasm32, which measures with the wall clock instead.
+            #[cfg(not(target_arch = "wasm32"))]
+            let started = Instant::now();
+            #[cfg(target_arch = "wasm32")]
+            let started = DateTime::now();
+            let mut response = request
                 .send()
                 .map_err(|e| async { tracer_dyn_err!(e) })
                 .and_then(|t| async { ChainResponse::try_from_response(t).await })
                 .await?;
+            #[cfg(not(target_arch = "wasm32"))]
+            let elapsed = started.elapsed().as_millis();
+            #[cfg(target_arch = "wasm32")]
+            let elapsed = DateTime::now().as_millis_since_epoch() - started.as_millis_since_epoch();
+            response.duration_ms = elapsed.try_into().unwrap_or_default();
 
-            self.capture_variables(&response, &request_node.matcher);
+            self.capture_variables(&response, &request_node.matcher)?;
             self.response.responses.push(response.clone());
             self.response.last = response;
         }
@@ -800,19 +1247,61 @@ impl RequestProcessor {
         Ok(())
     }
 
-    /// Captures variables from a response based on the provided captures.
+    /// Captures variables from a response based on the provided captures. When a capture does not
+    /// match, its default value is used instead, if any.
     ///
     /// # Arguments
     ///
     /// * `response` - The `Response` object to capture variables from.
     /// * `captures` - The `VariableCaptures` defining what to capture.
-    fn capture_variables(&mut self, response: &ChainResponse, captures: &VariableCaptures) {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a capture uses an invalid regular expression.
+    fn capture_variables(
+        &mut self,
+        response: &ChainResponse,
+        captures: &VariableCaptures,
+    ) -> Result<(), DynTracerError> {
         for capture in &captures.body {
-            if let Some(value) = self.match_response(&response.body, &capture.matcher) {
+            if let Some(value) = self
+                .match_response(&response.body, &capture.matcher)?
+                .or_else(|| capture.default.clone())
+            {
                 self.variables.insert(capture.id.clone(), value);
             }
         }
-        // Similarly handle captures from headers and query
+        for capture in &captures.headers {
+            if let Some(value) = response
+                .headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(&capture.id))
+                .map(|(_, value)| self.match_response(value, &capture.matcher))
+                .transpose()?
+                .flatten()
+                .or_else(|| capture.default.clone())
+            {
+                self.variables.insert(capture.id.clone(), value);
+            }
+        }
+        if !captures.query.is_empty() {
+            let query = url::Url::parse(&response.url)
+                .map(|url| url.query_pairs().into_owned().collect::<Vec<_>>())
+                .unwrap_or_default();
+            for capture in &captures.query {
+                if let Some(value) = query
+                    .iter()
+                    .find(|(name, _)| name == &capture.id)
+                    .map(|(_, value)| self.match_response(value, &capture.matcher))
+                    .transpose()?
+                    .flatten()
+                    .or_else(|| capture.default.clone())
+                {
+                    self.variables.insert(capture.id.clone(), value);
+                }
+            }
+        }
+        Ok(())
     }
 
     /// Matches a response based on the provided matcher.
@@ -824,22 +1313,45 @@ impl RequestProcessor {
     ///
     /// # Returns
     ///
-    /// An optional string representing the matched value.
-    fn match_response(&self, response: &str, matcher: &Matcher) -> Option<String> {
+    /// An optional string representing the matched value, or an error if the matcher uses an
+    /// invalid regular expression.
+    fn match_response(
+        &self,
+        response: &str,
+        matcher: &Matcher,
+    ) -> Result<Option<String>, DynTracerError> {
         if let Some((from, to)) = &matcher.between {
             if let Some(start) = response.find(from) {
                 if let Some(end) = response[start..].find(to) {
-                    return Some(response[start + from.len()..start + end].to_string());
+                    return Ok(Some(response[start + from.len()..start + end].to_string()));
                 }
             }
         }
-        if let Some(regexp) = &matcher.regexp {
-            // Apply regexp matching logic
+        if let Some(re) = matcher.compiled_regexp() {
+            // The first capture group is used when the expression defines one, otherwise the
+            // entire match is returned.
+            let re = re?;
+            let capture = |c: regex::Captures| {
+                c.get(1)
+                    .or_else(|| c.get(0))
+                    .map(|m| m.as_str().to_string())
+            };
+            if matcher.all.unwrap_or(false) {
+                let matches = re
+                    .captures_iter(response)
+                    .filter_map(capture)
+                    .collect::<Vec<String>>();
+                return Ok((!matches.is_empty()).then(|| matches.join(",")));
+            }
+            return Ok(re.captures(response).and_then(capture));
+        }
+        if let Some(xpath) = &matcher.xpath {
+            return Ok(path::evaluate(response, xpath));
         }
         if matcher.all.unwrap_or(false) {
-            return Some(response.to_string());
+            return Ok(Some(response.to_string()));
         }
-        None
+        Ok(None)
     }
 }
 
@@ -859,6 +1371,13 @@ pub struct ChainResponses {
     last: ChainResponse,
 }
 
+impl ChainResponses {
+    /// Returns the summed duration of all processed requests in milliseconds.
+    pub fn total_duration_ms(&self) -> u64 {
+        self.responses.iter().map(|t| t.duration_ms).sum()
+    }
+}
+
 /// Represents a single response from a processed request.
 ///
 /// # Examples
@@ -871,15 +1390,32 @@ pub struct ChainResponses {
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct ChainResponse {
+    #[serde(default)]
+    url: String,
     body: String,
     headers: HashMap<String, String>,
     status: u16,
     status_msg: String,
     variables_state: HashMap<String, String>,
+    #[serde(default)]
+    duration_ms: u64,
+    #[serde(default)]
+    attempts: u32,
 }
 
 impl ChainResponse {
+    /// Returns the time from sending the request until its body was received, in milliseconds.
+    pub fn duration_ms(&self) -> u64 {
+        self.duration_ms
+    }
+
+    /// Returns how many attempts were made to receive the response, including the first one.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
     pub async fn try_from_response(response: request::Response) -> Result<Self, DynTracerError> {
+        let url = response.url().to_string();
         let body = response.body().into_str().await?;
         let headers = response
             .headers()
@@ -889,23 +1425,53 @@ impl ChainResponse {
         let status = response.status().as_u16();
         let status_msg = response.status().as_str().to_string();
         let variables_state = HashMap::new();
+        let attempts = response.attempts();
         Ok(ChainResponse {
+            url,
             body,
             headers,
             status,
             status_msg,
             variables_state,
+            duration_ms: 0,
+            attempts,
         })
     }
 }
 
+/// Renders the templates in the header values with the captured variables. Every value of a
+/// multi-value header is rendered on its own, so the values are still sent as separate headers.
+fn render_headers(headers: &mut request::HeaderMap, context: &TemplateContext) {
+    let names: Vec<String> = headers.iter().map(|(k, _)| k.0.clone()).collect();
+    for name in names {
+        let Some(values) = headers.get_all(&name) else {
+            continue;
+        };
+        let rendered = values
+            .iter()
+            .map(|t| {
+                TemplateEngine::new()
+                    .render(t, context)
+                    .unwrap_or_else(|_| t.clone())
+            })
+            .collect::<Vec<String>>();
+        if rendered != values {
+            headers.remove(&name);
+            for value in &rendered {
+                headers.append(&name, value);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::collections::HashMap;
-    use crate::net::http::request::Method;
+    use crate::net::http::request::{Backoff, Method, RetryPolicy};
     use crate::net::http::test::test_server::start_test_server;
     use crate::rails::ext::future::*;
+    use core::time::Duration;
 
     #[tokio::test]
     async fn test_complete_request_chain_functionality() {
@@ -980,6 +1546,58 @@ mod tests {
         shutdown_tx.send(()).unwrap();
     }
 
+    #[cfg(feature = "serde-yaml")]
+    #[test]
+    fn test_request_chain_yaml_round_trip() {
+        let capture = |id: &str, matcher: Matcher| VariableCapture {
+            id: id.to_string(),
+            matcher,
+            default: Some("none".to_string()),
+        };
+        let node = RequestNodeBuilder::default()
+            .name("login")
+            .add_request(
+                Builder::default()
+                    .method(Method::Post)
+                    .url("http://localhost/login?session=abc")
+                    .body(r#"{"user": "{{ user }}"}"#)
+                    .content_type("application/json"),
+            )
+            .add_capture(capture(
+                "token",
+                Matcher::between("\"token\":\"".to_string(), "\"".to_string()),
+            ))
+            .add_header_capture(capture("x-request-id", Matcher::all(true)))
+            .add_query_capture(capture("session", Matcher::regexp(r"\w+".to_string())))
+            .build();
+        let mut chain = RequestChain::new().with_cookie_store(true);
+        chain.add_template_request(node);
+        chain.add_call_structure("test_chain", vec!["login".to_string()]);
+
+        let encoded = chain.to_encoded(ContentType::Yaml).unwrap();
+        let yaml = String::from_utf8(encoded.to_vec()).unwrap();
+        assert!(yaml.contains("type: headers"));
+        assert!(yaml.contains("type: query"));
+
+        let decoded = RequestChain::from_bytes(&encoded, ContentType::Yaml).unwrap();
+        let captures = &decoded.template_requests.get("login").unwrap().matcher;
+        assert_eq!(captures.body[0].id, "token");
+        assert_eq!(
+            captures.body[0].matcher.between,
+            Some(("\"token\":\"".to_string(), "\"".to_string()))
+        );
+        assert_eq!(captures.headers[0].id, "x-request-id");
+        assert_eq!(captures.headers[0].matcher.all, Some(true));
+        assert_eq!(captures.query[0].id, "session");
+        assert_eq!(captures.query[0].matcher.regexp, Some(r"\w+".to_string()));
+        assert_eq!(captures.query[0].default, Some("none".to_string()));
+        assert!(decoded.cookie_store);
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(&chain).unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn test_request_chain_new() {
         let chain = RequestChain::new();
@@ -1158,6 +1776,320 @@ mod tests {
         shutdown_tx.send(()).unwrap();
     }
 
+    #[tokio::test]
+    async fn test_request_node_builder_depends_on() {
+        let node = RequestNodeBuilder::default()
+            .name("example_node")
+            .depends_on("first")
+            .depends_on("second")
+            .build();
+        assert_eq!(
+            node.depends_on,
+            vec!["first".to_string(), "second".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_depends_on_ordering_with_server() {
+        let (addr, shutdown_tx) = start_test_server().await;
+
+        let node = |name: &str, depends_on: &[&str]| {
+            depends_on
+                .iter()
+                .fold(RequestNodeBuilder::default().name(name), |builder, t| {
+                    builder.depends_on(t)
+                })
+                .add_request(
+                    Builder::default()
+                        .body(format!(
+                            r#"{{"status":200, "payload":"{}", "headers":{{}}}}"#,
+                            name
+                        ))
+                        .content_type("application/json")
+                        .method(Method::Post)
+                        .url(format!("http://{}/", addr)),
+                )
+                .build()
+        };
+
+        let mut chain = RequestChain::new();
+        chain.add_template_request(node("fan_in", &["left", "right"]));
+        chain.add_template_request(node("left", &["root"]));
+        chain.add_template_request(node("right", &["root"]));
+        chain.add_template_request(node("root", &[]));
+        chain.add_call_structure(
+            "test_chain",
+            vec![
+                "fan_in".to_string(),
+                "right".to_string(),
+                "left".to_string(),
+                "root".to_string(),
+            ],
+        );
+
+        let responses = chain.run("test_chain", Vec::new()).await.unwrap();
+        let order = responses
+            .responses
+            .iter()
+            .map(|t| t.body.as_str())
+            .collect::<Vec<&str>>();
+
+        assert_eq!(order, vec!["root", "right", "left", "fan_in"]);
+
+        shutdown_tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_depends_on_cycle() {
+        let mut chain = RequestChain::new();
+        chain.add_template_request(
+            RequestNodeBuilder::default()
+                .name("a")
+                .depends_on("b")
+                .build(),
+        );
+        chain.add_template_request(
+            RequestNodeBuilder::default()
+                .name("b")
+                .depends_on("a")
+                .build(),
+        );
+        chain.add_call_structure("test_chain", vec!["a".to_string(), "b".to_string()]);
+
+        let error = chain.run("test_chain", Vec::new()).await.unwrap_err();
+        assert!(error.err_to_string().contains("Dependency cycle"));
+    }
+
+    #[tokio::test]
+    async fn test_cookie_store_with_server() {
+        let (addr, shutdown_tx) = start_test_server().await;
+
+        let chain = |cookie_store: bool| {
+            let mut chain = RequestChain::new().with_cookie_store(cookie_store);
+            chain.add_template_request(
+                RequestNodeBuilder::default()
+                    .name("login")
+                    .add_request(
+                        Builder::default()
+                            .body(r#"{"status":200, "payload":"ok", "headers":{"Set-Cookie":"session=abc123; Path=/"}}"#)
+                            .content_type("application/json")
+                            .method(Method::Post)
+                            .url(format!("http://{}/", addr)),
+                    )
+                    .build(),
+            );
+            chain.add_template_request(
+                RequestNodeBuilder::default()
+                    .name("call")
+                    .add_request(
+                        Builder::default()
+                            .method(Method::Get)
+                            .url(format!("http://{}/headers", addr)),
+                    )
+                    .build(),
+            );
+            chain.add_call_structure("test_chain", vec!["login".to_string(), "call".to_string()]);
+            chain
+        };
+
+        let responses = chain(true).run("test_chain", Vec::new()).await.unwrap();
+        let echoed: serde_json::Value = serde_json::from_str(&responses.responses[1].body).unwrap();
+        assert_eq!(echoed["cookie"], "session=abc123");
+
+        let responses = chain(false).run("test_chain", Vec::new()).await.unwrap();
+        let echoed: serde_json::Value = serde_json::from_str(&responses.responses[1].body).unwrap();
+        assert!(echoed.get("cookie").is_none());
+
+        shutdown_tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_response_metrics() {
+        let (addr, shutdown_tx) = start_test_server().await;
+
+        let mut chain = RequestChain::new();
+        chain.add_template_request(
+            RequestNodeBuilder::default()
+                .name("flaky")
+                .add_request(
+                    Builder::default()
+                        .method(Method::Get)
+                        .url(format!("http://{}/flaky/chain-metrics", addr))
+                        .retry(
+                            RetryPolicy::new(2).backoff(Backoff::Fixed(Duration::from_millis(200))),
+                        ),
+                )
+                .build(),
+        );
+        chain.add_template_request(
+            RequestNodeBuilder::default()
+                .name("plain")
+                .add_request(
+                    Builder::default()
+                        .method(Method::Get)
+                        .url(format!("http://{}/", addr)),
+                )
+                .build(),
+        );
+        chain.add_call_structure("test_chain", vec!["flaky".to_string(), "plain".to_string()]);
+
+        let responses = chain.run("test_chain", Vec::new()).await.unwrap();
+        let flaky = &responses.responses[0];
+        let plain = &responses.responses[1];
+        assert_eq!(flaky.body, "recovered");
+        assert_eq!(flaky.attempts(), 2);
+        assert!(flaky.duration_ms() >= 200);
+        assert_eq!(plain.attempts(), 1);
+        assert_eq!(
+            responses.total_duration_ms(),
+            flaky.duration_ms() + plain.duration_ms()
+        );
+
+        shutdown_tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_run_concurrent_merges_captures() {
+        let (addr, shutdown_tx) = start_test_server().await;
+
+        let mut chain = RequestChain::new();
+        for name in ["b", "a"] {
+            chain.add_template_request(
+                RequestNodeBuilder::default()
+                    .name(name)
+                    .add_request(
+                        Builder::default()
+                            .body(format!(
+                                r#"{{"status":200, "payload":"from-{}", "headers":{{}}}}"#,
+                                name
+                            ))
+                            .content_type("application/json")
+                            .method(Method::Post)
+                            .url(format!("http://{}/", addr)),
+                    )
+                    .add_capture(VariableCapture {
+                        id: "token".to_string(),
+                        matcher: Matcher::all(true),
+                        default: None,
+                    })
+                    .build(),
+            );
+        }
+        chain.add_template_request(
+            RequestNodeBuilder::default()
+                .name("c")
+                .depends_on("a")
+                .depends_on("b")
+                .add_request(
+                    Builder::default()
+                        .method(Method::Get)
+                        .header(("x-token", "{{ token }}"))
+                        .url(format!("http://{}/headers", addr)),
+                )
+                .build(),
+        );
+        chain.add_call_structure(
+            "test_chain",
+            vec!["c".to_string(), "b".to_string(), "a".to_string()],
+        );
+
+        let responses = chain
+            .run_concurrent("test_chain", Vec::new(), 4)
+            .await
+            .unwrap();
+        let bodies = responses
+            .responses
+            .iter()
+            .map(|t| t.body.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(bodies[..2], ["from-b", "from-a"]);
+
+        let echoed: serde_json::Value = serde_json::from_str(&responses.last.body).unwrap();
+        assert_eq!(echoed["x-token"], "from-b");
+
+        shutdown_tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_run_concurrent_limits_parallelism() {
+        let (addr, shutdown_tx) = start_test_server().await;
+
+        let names = ["concurrent_1", "concurrent_2", "concurrent_3"];
+        let mut chain = RequestChain::new();
+        for name in names {
+            chain.add_template_request(
+                RequestNodeBuilder::default()
+                    .name(name)
+                    .add_request(
+                        Builder::default()
+                            .method(Method::Get)
+                            .url(format!("http://{}/concurrent", addr)),
+                    )
+                    .build(),
+            );
+        }
+        chain.add_call_structure("test_chain", names.map(String::from).to_vec());
+
+        // The server answers with the number of requests it handled at the same time.
+        for max_parallel in [2, 1] {
+            let responses = chain
+                .run_concurrent("test_chain", Vec::new(), max_parallel)
+                .await
+                .unwrap();
+            assert_eq!(responses.responses.len(), 3);
+            let peak = responses
+                .responses
+                .iter()
+                .map(|t| t.body.parse::<usize>().unwrap())
+                .max();
+            assert_eq!(peak, Some(max_parallel));
+        }
+
+        shutdown_tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_capture_default_with_server() {
+        let (addr, shutdown_tx) = start_test_server().await;
+
+        let mut chain = RequestChain::new();
+        chain.add_template_request(
+            RequestNodeBuilder::default()
+                .name("login")
+                .add_request(
+                    Builder::default()
+                        .body(r#"{"status":200, "payload":"no user here", "headers":{}}"#)
+                        .content_type("application/json")
+                        .method(Method::Post)
+                        .url(format!("http://{}/", addr)),
+                )
+                .add_capture(VariableCapture {
+                    id: "user".to_string(),
+                    matcher: Matcher::between("user=".to_string(), ";".to_string()),
+                    default: Some("anon".to_string()),
+                })
+                .build(),
+        );
+        chain.add_template_request(
+            RequestNodeBuilder::default()
+                .name("greet")
+                .add_request(
+                    Builder::default()
+                        .body(r#"{"status":200, "payload":"hello {{ user }}", "headers":{}}"#)
+                        .content_type("application/json")
+                        .method(Method::Post)
+                        .url(format!("http://{}/", addr)),
+                )
+                .build(),
+        );
+        chain.add_call_structure("test_chain", vec!["login".to_string(), "greet".to_string()]);
+
+        let responses = chain.run("test_chain", Vec::new()).await.unwrap();
+        assert_eq!(responses.last.body, "hello anon");
+
+        shutdown_tx.send(()).unwrap();
+    }
+
     #[tokio::test]
     async fn test_matcher_between() {
         let matcher = Matcher::between("start".to_string(), "end".to_string());
@@ -1167,7 +2099,8 @@ mod tests {
             variables: HashMap::new(),
             response: ChainResponses::default(),
         }
-        .match_response(response, &matcher);
+        .match_response(response, &matcher)
+        .unwrap();
         assert_eq!(value, Some(" of the match ".to_string()));
     }
 
@@ -1180,9 +2113,235 @@ mod tests {
             variables: HashMap::new(),
             response: ChainResponses::default(),
         }
-        .match_response(response, &matcher);
-        // Assuming the implementation of regex matching is added
-        // assert_eq!(value, Some("42".to_string()));
+        .match_response(response, &matcher)
+        .unwrap();
+        assert_eq!(value, Some("42".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_matcher_regexp_capture_group() {
+        let matcher = Matcher::regexp(r"token=(\w+);".to_string());
+        let response = "session: token=abc123; expires=never";
+        let value = RequestProcessor {
+            request_chain: RequestChain::new(),
+            variables: HashMap::new(),
+            response: ChainResponses::default(),
+        }
+        .match_response(response, &matcher)
+        .unwrap();
+        assert_eq!(value, Some("abc123".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_matcher_regexp_all() {
+        let matcher = Matcher::regexp(r"\d+".to_string()).capture_all(true);
+        let response = "1 apple, 22 pears and 333 plums";
+        let processor = RequestProcessor {
+            request_chain: RequestChain::new(),
+            variables: HashMap::new(),
+            response: ChainResponses::default(),
+        };
+        assert_eq!(
+            processor.match_response(response, &matcher).unwrap(),
+            Some("1,22,333".to_string())
+        );
+        assert_eq!(
+            processor.match_response("no numbers", &matcher).unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_matcher_regexp_invalid() {
+        let matcher = Matcher::regexp(r"(\d+".to_string());
+        let processor = RequestProcessor {
+            request_chain: RequestChain::new(),
+            variables: HashMap::new(),
+            response: ChainResponses::default(),
+        };
+        assert!(processor.match_response("42", &matcher).is_err());
+        // The compile error is cached and reported again for clones of the matcher.
+        assert!(processor.match_response("42", &matcher.clone()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_matcher_xpath() {
+        let matcher = Matcher::xpath("/data/token".to_string());
+        let response = r#"{"data":{"token":"abc123"}}"#;
+        let value = RequestProcessor {
+            request_chain: RequestChain::new(),
+            variables: HashMap::new(),
+            response: ChainResponses::default(),
+        }
+        .match_response(response, &matcher)
+        .unwrap();
+        assert_eq!(value, Some("abc123".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_xpath_capture_forwarded_with_server() {
+        let (addr, shutdown_tx) = start_test_server().await;
+
+        let mut chain = RequestChain::new();
+        chain.add_template_request(
+            RequestNodeBuilder::default()
+                .name("login")
+                .add_request(
+                    Builder::default()
+                        .body(r#"{"status":200, "payload":"{\"data\":{\"token\":\"abc123\"}}", "headers":{}}"#)
+                        .content_type("application/json")
+                        .method(Method::Post)
+                        .url(format!("http://{}/", addr)),
+                )
+                .add_capture(VariableCapture {
+                    id: "token".to_string(),
+                    matcher: Matcher::xpath("/data/token".to_string()),
+                    default: None,
+                })
+                .build(),
+        );
+        chain.add_template_request(
+            RequestNodeBuilder::default()
+                .name("call")
+                .add_request(
+                    Builder::default()
+                        .body(r#"{"status":200, "payload":"token is {{ token }}", "headers":{}}"#)
+                        .content_type("application/json")
+                        .method(Method::Post)
+                        .url(format!("http://{}/", addr)),
+                )
+                .build(),
+        );
+        chain.add_call_structure("test_chain", vec!["login".to_string(), "call".to_string()]);
+
+        let responses = chain.run("test_chain", Vec::new()).await.unwrap();
+
+        assert_eq!(responses.responses.len(), 2);
+        assert_eq!(responses.responses[1].body, "token is abc123");
+
+        shutdown_tx.send(()).unwrap();
+    }
+
+    #[test]
+    fn test_render_multi_value_headers() {
+        let mut context = TemplateContext::new();
+        context.insert("token", Box::new("abc".to_string()));
+
+        let mut headers = request::HeaderMap::default();
+        headers
+            .append("Accept", "text/html")
+            .append("Accept", "application/json")
+            .append("X-Token", "first {{ token }}")
+            .append("X-Token", "second {{ token }}");
+        render_headers(&mut headers, &context);
+
+        assert_eq!(
+            headers.get_all("accept"),
+            Some(vec![
+                "text/html".to_string(),
+                "application/json".to_string()
+            ])
+        );
+        assert_eq!(
+            headers.get_all("x-token"),
+            Some(vec!["first abc".to_string(), "second abc".to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_header_capture_forwarded_with_server() {
+        let (addr, shutdown_tx) = start_test_server().await;
+
+        let mut chain = RequestChain::new();
+        chain.add_template_request(
+            RequestNodeBuilder::default()
+                .name("login")
+                .add_request(
+                    Builder::default()
+                        .body(r#"{"status":200, "payload":"ok", "headers":{"X-Auth-Token":"secret"}}"#)
+                        .content_type("application/json")
+                        .method(Method::Post)
+                        .url(format!("http://{}/", addr)),
+                )
+                .add_header_capture(VariableCapture {
+                    id: "X-Auth-Token".to_string(),
+                    matcher: Matcher::all(true),
+                    default: None,
+                })
+                .build(),
+        );
+        chain.add_template_request(
+            RequestNodeBuilder::default()
+                .name("call")
+                .add_request(
+                    Builder::default()
+                        .method(Method::Get)
+                        .header(("x-forwarded-token", "{{ X-Auth-Token }}"))
+                        .url(format!("http://{}/headers", addr)),
+                )
+                .build(),
+        );
+        chain.add_call_structure("test_chain", vec!["login".to_string(), "call".to_string()]);
+
+        let responses = chain.run("test_chain", Vec::new()).await.unwrap();
+        let echoed: serde_json::Value = serde_json::from_str(&responses.responses[1].body).unwrap();
+
+        assert_eq!(echoed["x-forwarded-token"], "secret");
+
+        shutdown_tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_query_capture_forwarded_with_server() {
+        let (addr, shutdown_tx) = start_test_server().await;
+
+        let mut chain = RequestChain::new();
+        chain.add_template_request(
+            RequestNodeBuilder::default()
+                .name("start")
+                .add_request(
+                    Builder::default()
+                        .method(Method::Get)
+                        .url(format!("http://{}/?session=s%201&other=x", addr)),
+                )
+                .add_query_capture(VariableCapture {
+                    id: "session".to_string(),
+                    matcher: Matcher::all(true),
+                    default: None,
+                })
+                .build(),
+        );
+        chain.add_template_request(
+            RequestNodeBuilder::default()
+                .name("call")
+                .add_request(
+                    Builder::default()
+                        .body(r#"{"status":200, "payload":"session {{ session }}", "headers":{}}"#)
+                        .content_type("application/json")
+                        .method(Method::Post)
+                        .url(format!("http://{}/", addr)),
+                )
+                .build(),
+        );
+        chain.add_call_structure("test_chain", vec!["start".to_string(), "call".to_string()]);
+
+        let responses = chain.run("test_chain", Vec::new()).await.unwrap();
+
+        assert_eq!(responses.responses[1].body, "session s 1");
+
+        shutdown_tx.send(()).unwrap();
+    }
+
+    #[test]
+    fn test_chain_response_deserialize_legacy() {
+        let response: ChainResponse = serde_json::from_str(
+            r#"{"body":"ok","headers":{},"status":200,"status_msg":"200","variables_state":{}}"#,
+        )
+        .unwrap();
+        assert_eq!(response.url, "");
+        assert_eq!(response.body, "ok");
+        assert_eq!(response.duration_ms(), 0);
+        assert_eq!(response.attempts(), 0);
     }
 
     #[tokio::test]
@@ -1194,7 +2353,8 @@ mod tests {
             variables: HashMap::new(),
             response: ChainResponses::default(),
         }
-        .match_response(response, &matcher);
+        .match_response(response, &matcher)
+        .unwrap();
         assert_eq!(value, Some(response.to_string()));
     }
 }
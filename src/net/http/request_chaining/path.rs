@@ -0,0 +1,167 @@
+//! Path based lookups used by the `xpath` matcher.
+//!
+//! JSON documents are addressed with JSON Pointer syntax (`/data/token`). Documents that are not
+//! JSON but parse as XML are addressed with a minimal XPath subset: absolute child paths
+//! (`/root/data/token`), descendant lookups (`//token`) and attributes (`/root/item/@id`).
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::Value;
+
+/// Evaluates `path` against `document` and returns the stringified leaf value.
+pub(crate) fn evaluate(document: &str, path: &str) -> Option<String> {
+    match serde_json::from_str::<Value>(document) {
+        Ok(value) => json_pointer(&value, path),
+        Err(_) => xml_path(document, path),
+    }
+}
+
+fn json_pointer(value: &Value, path: &str) -> Option<String> {
+    value.pointer(path).map(|leaf| match leaf {
+        Value::String(s) => s.clone(),
+        leaf => leaf.to_string(),
+    })
+}
+
+fn xml_path(document: &str, path: &str) -> Option<String> {
+    let root = serde_xml_rs::from_str::<XmlNode>(document).ok()?;
+    if let Some(name) = path.strip_prefix("//") {
+        return root.descendant(name).map(XmlNode::text);
+    }
+    // serde_xml_rs does not expose the name of the root element, so the first segment of an
+    // absolute path is accepted as is.
+    let mut segments = path.trim_start_matches('/').split('/').skip(1);
+    segments
+        .try_fold(&root, |node, segment| {
+            node.child(segment.trim_start_matches('@'))
+        })
+        .map(XmlNode::text)
+}
+
+/// Generic element tree produced by `serde_xml_rs`. Attributes and child elements are both
+/// represented as named children.
+#[derive(Debug)]
+enum XmlNode {
+    Text(String),
+    Element(Vec<(String, XmlNode)>),
+}
+
+impl XmlNode {
+    fn child(&self, name: &str) -> Option<&XmlNode> {
+        match self {
+            XmlNode::Element(children) => children
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, node)| node),
+            XmlNode::Text(_) => None,
+        }
+    }
+
+    fn descendant(&self, name: &str) -> Option<&XmlNode> {
+        match self {
+            XmlNode::Element(children) => children.iter().find_map(|(key, node)| {
+                if key == name {
+                    Some(node)
+                } else {
+                    node.descendant(name)
+                }
+            }),
+            XmlNode::Text(_) => None,
+        }
+    }
+
+    fn text(&self) -> String {
+        match self {
+            XmlNode::Text(text) => text.clone(),
+            XmlNode::Element(children) => children
+                .iter()
+                .filter(|(key, _)| key == "$value")
+                .map(|(_, node)| node.text())
+                .collect(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for XmlNode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(XmlNodeVisitor)
+    }
+}
+
+struct XmlNodeVisitor;
+
+impl<'de> Visitor<'de> for XmlNodeVisitor {
+    type Value = XmlNode;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an xml element or text")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(XmlNode::Text(v.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(XmlNode::Text(v))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(XmlNode::Text(String::new()))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut children = Vec::new();
+        while let Some(node) = seq.next_element::<XmlNode>()? {
+            children.push(("$value".to_string(), node));
+        }
+        Ok(XmlNode::Element(children))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut children = Vec::new();
+        while let Some((key, node)) = map.next_entry::<String, XmlNode>()? {
+            children.push((key, node));
+        }
+        Ok(XmlNode::Element(children))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_pointer() {
+        let document = r#"{"data":{"token":"abc","count":3,"items":[{"id":7}]}}"#;
+        assert_eq!(evaluate(document, "/data/token"), Some("abc".to_string()));
+        assert_eq!(evaluate(document, "/data/count"), Some("3".to_string()));
+        assert_eq!(
+            evaluate(document, "/data/items/0/id"),
+            Some("7".to_string())
+        );
+        assert_eq!(evaluate(document, "/data/missing"), None);
+    }
+
+    #[test]
+    fn test_xml_path() {
+        let document = r#"<response><data><token>abc</token></data><item id="7"/></response>"#;
+        assert_eq!(
+            evaluate(document, "/response/data/token"),
+            Some("abc".to_string())
+        );
+        assert_eq!(evaluate(document, "//token"), Some("abc".to_string()));
+        assert_eq!(
+            evaluate(document, "/response/item/@id"),
+            Some("7".to_string())
+        );
+        assert_eq!(evaluate(document, "/response/data/missing"), None);
+    }
+
+    #[test]
+    fn test_plain_text() {
+        assert_eq!(evaluate("just some text", "/data/token"), None);
+    }
+}
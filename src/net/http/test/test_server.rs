@@ -3,16 +3,24 @@ use futures::{StreamExt, TryStreamExt};
 use http_body_util::{BodyExt, Full};
 use hyper::body::Bytes;
 use hyper::body::Incoming;
-use hyper::server::conn::http1;
 use hyper::service::service_fn;
 use hyper::{body::Body, header::HeaderName, header::HeaderValue, Request, Response};
-use hyper_util::rt::TokioIo;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
 use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 use tokio::net::{TcpListener as TokioTcpListener, TcpListener};
 use tokio::sync::oneshot;
 use tokio::sync::oneshot::Receiver;
 
+/// Paths below `/flaky/` that already failed once.
+static FLAKY: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Number of requests to `/concurrent` currently being handled.
+static CONCURRENT: AtomicUsize = AtomicUsize::new(0);
+
 #[derive(Debug, Deserialize, Serialize)]
 struct ReturnToMe {
     status: u16,
@@ -22,6 +30,101 @@ struct ReturnToMe {
 
 async fn handle_request(req: Request<Incoming>) -> Result<Response<Full<Bytes>>, hyper::Error> {
     let method = req.method().clone();
+
+    if let Some(hops) = req.uri().path().strip_prefix("/redirect/") {
+        let location = match hops {
+            "external" => {
+                let port = req
+                    .headers()
+                    .get("host")
+                    .and_then(|t| t.to_str().ok())
+                    .and_then(|t| t.rsplit(':').next())
+                    .unwrap_or_default();
+                format!("http://localhost:{}/", port)
+            }
+            "0" => "/".to_string(),
+            hops => format!("/redirect/{}", hops.parse::<u32>().unwrap_or(1) - 1),
+        };
+        return Ok(Response::builder()
+            .status(302)
+            .header("location", location)
+            .body(Full::new(Bytes::new()))
+            .unwrap());
+    }
+
+    if req.uri().path() == "/slow" {
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        return Ok(Response::new(Full::new(Bytes::from("slow response"))));
+    }
+
+    if req.uri().path() == "/concurrent" {
+        // Responds with the number of requests in flight while this one was handled.
+        let entered = CONCURRENT.fetch_add(1, Ordering::SeqCst) + 1;
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        let running = CONCURRENT.fetch_sub(1, Ordering::SeqCst).max(entered);
+        return Ok(Response::new(Full::new(Bytes::from(running.to_string()))));
+    }
+
+    if req.uri().path().starts_with("/flaky/") {
+        let mut flaky = FLAKY.lock().unwrap();
+        let path = req.uri().path().to_string();
+        if flaky.contains(&path) {
+            return Ok(Response::new(Full::new(Bytes::from("recovered"))));
+        }
+        flaky.push(path);
+        return Ok(Response::builder()
+            .status(503)
+            .body(Full::new(Bytes::from("unavailable")))
+            .unwrap());
+    }
+
+    if req.uri().path() == "/multipart" {
+        let content_type = req
+            .headers()
+            .get("content-type")
+            .and_then(|t| t.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let body = req.into_body().collect().await?.to_bytes().to_vec();
+        let echo = serde_json::json!({
+            "content_type": content_type,
+            "parts": parse_multipart(&content_type, &body),
+        });
+        return Ok(Response::new(Full::new(Bytes::from(echo.to_string()))));
+    }
+
+    if req.uri().path() == "/gzip" {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"decompressed response").unwrap();
+        return Ok(Response::builder()
+            .header("content-encoding", "gzip")
+            .body(Full::new(Bytes::from(encoder.finish().unwrap())))
+            .unwrap());
+    }
+
+    if req.uri().path() == "/echo" {
+        let content_type = req.headers().get("content-type").cloned();
+        let body = req.into_body().collect().await?.to_bytes();
+        let mut response = Response::new(Full::new(body));
+        if let Some(content_type) = content_type {
+            response.headers_mut().insert("content-type", content_type);
+        }
+        return Ok(response);
+    }
+
+    if req.uri().path() == "/headers" {
+        let headers = req
+            .headers()
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or_default().to_string()))
+            .collect::<HashMap<String, String>>();
+        return Ok(Response::new(Full::new(Bytes::from(
+            serde_json::to_string(&headers).unwrap(),
+        ))));
+    }
+
     let whole_body = req.into_body().collect().await?.to_bytes().to_vec();
 
     if !whole_body.is_empty() {
@@ -55,6 +158,44 @@ async fn handle_request(req: Request<Incoming>) -> Result<Response<Full<Bytes>>,
     })
 }
 
+/// Minimal `multipart/form-data` parser returning every part with its name, file name, content
+/// type and (lossy UTF-8) content.
+fn parse_multipart(content_type: &str, body: &[u8]) -> Vec<serde_json::Value> {
+    let Some(boundary) = content_type.split("boundary=").nth(1) else {
+        return Vec::new();
+    };
+    let body = String::from_utf8_lossy(body);
+    body.split(&format!("--{}", boundary))
+        .filter_map(|part| {
+            let (head, content) = part.trim_start_matches("\r\n").split_once("\r\n\r\n")?;
+            let attribute = |name: &str| {
+                head.split(';')
+                    .find_map(|t| t.trim().strip_prefix(&format!("{}=", name)))
+                    .map(|t| {
+                        t.split("\r\n")
+                            .next()
+                            .unwrap_or(t)
+                            .trim_matches('"')
+                            .to_string()
+                    })
+            };
+            let content_type = head
+                .lines()
+                .find_map(|t| {
+                    t.strip_prefix("Content-Type: ")
+                        .or(t.strip_prefix("content-type: "))
+                })
+                .map(str::to_string);
+            Some(serde_json::json!({
+                "name": attribute("name"),
+                "filename": attribute("filename"),
+                "content_type": content_type,
+                "content": content.strip_suffix("\r\n").unwrap_or(content),
+            }))
+        })
+        .collect()
+}
+
 pub async fn test_server(listener: TcpListener, mut rx: Receiver<()>) {
     loop {
         tokio::select! {
@@ -66,7 +207,8 @@ pub async fn test_server(listener: TcpListener, mut rx: Receiver<()>) {
                 let io = TokioIo::new(stream);
 
                 tokio::task::spawn(async move {
-                    if let Err(err) = http1::Builder::new()
+                    // Serves HTTP/1.1 and HTTP/2 with prior knowledge.
+                    if let Err(err) = auto::Builder::new(TokioExecutor::new())
                         .serve_connection(io, service_fn(handle_request))
                         .await
                     {
@@ -78,6 +220,67 @@ pub async fn test_server(listener: TcpListener, mut rx: Receiver<()>) {
     }
 }
 
+/// Starts the test server behind TLS, requiring a client certificate issued by the CA in
+/// `fixtures/ca.pem`. The server certificate is valid for `localhost` and `127.0.0.1`.
+///
+/// The fixtures were generated with `openssl genpkey` and `openssl x509 -req` and are valid for
+/// 100 years. `client-pkcs1.key` holds the client key in the traditional PKCS#1 format.
+#[cfg(feature = "tls")]
+pub async fn start_mtls_test_server() -> (SocketAddr, oneshot::Sender<()>) {
+    use hyper::server::conn::http1;
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+    use std::sync::Arc;
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let mut roots = rustls::RootCertStore::empty();
+    roots
+        .add(CertificateDer::from_pem_slice(include_bytes!("fixtures/ca.pem")).unwrap())
+        .unwrap();
+    let verifier = rustls::server::WebPkiClientVerifier::builder_with_provider(
+        Arc::new(roots),
+        provider.clone(),
+    )
+    .build()
+    .unwrap();
+    let config = rustls::ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_client_cert_verifier(verifier)
+        .with_single_cert(
+            vec![CertificateDer::from_pem_slice(include_bytes!("fixtures/server.pem")).unwrap()],
+            PrivateKeyDer::from_pem_slice(include_bytes!("fixtures/server.key")).unwrap(),
+        )
+        .unwrap();
+    let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+
+    let listener = TokioTcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .await
+        .unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (tx, mut rx) = oneshot::channel();
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = &mut rx => break,
+                Ok((stream, _)) = listener.accept() => {
+                    let acceptor = acceptor.clone();
+                    tokio::task::spawn(async move {
+                        // Handshakes without a valid client certificate fail here.
+                        let Ok(stream) = acceptor.accept(stream).await else {
+                            return;
+                        };
+                        let _ = http1::Builder::new()
+                            .serve_connection(TokioIo::new(stream), service_fn(handle_request))
+                            .await;
+                    });
+                }
+            }
+        }
+    });
+    (addr, tx)
+}
+
 pub async fn start_test_server() -> (SocketAddr, oneshot::Sender<()>) {
     let (tx, rx) = oneshot::channel();
 
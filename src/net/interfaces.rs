@@ -0,0 +1,219 @@
+//! Enumeration of the network interfaces of this host.
+//!
+//! The addresses are collected with `if_addrs`, which reports one entry per address. `list`
+//! groups them by interface name, so every `Interface` holds all of its IPv4 and IPv6 addresses.
+//! Hardware addresses are looked up with `pnet` and are only available with the `pnet` feature.
+use alloc::{string::String, vec::Vec};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// A network interface and the addresses assigned to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interface {
+    /// The name of the interface, e.g. `eth0` or `lo`.
+    pub name: String,
+    /// The IPv4 addresses assigned to the interface.
+    pub ipv4: Vec<Ipv4Addr>,
+    /// The IPv6 addresses assigned to the interface.
+    pub ipv6: Vec<Ipv6Addr>,
+    /// Whether this is a loopback interface.
+    pub is_loopback: bool,
+    /// Whether the interface is administratively up.
+    ///
+    /// On platforms other than unix only configured adapters are reported, so this is always
+    /// `true` there.
+    pub is_up: bool,
+}
+
+impl Interface {
+    /// Returns all addresses of the interface, IPv4 addresses first.
+    pub fn ips(&self) -> impl Iterator<Item = IpAddr> + '_ {
+        self.ipv4
+            .iter()
+            .copied()
+            .map(IpAddr::V4)
+            .chain(self.ipv6.iter().copied().map(IpAddr::V6))
+    }
+}
+
+/// Lists the network interfaces of this host that have at least one address assigned.
+///
+/// Interfaces are returned in the order the operating system reports them. If the interfaces
+/// cannot be read an empty list is returned.
+///
+/// # Examples
+/// ```
+/// use gearbox::net::interfaces;
+///
+/// for interface in interfaces::list() {
+///     println!("{}: {:?}", interface.name, interface.ips().collect::<Vec<_>>());
+/// }
+/// ```
+pub fn list() -> Vec<Interface> {
+    let addrs = match if_addrs::get_if_addrs() {
+        Ok(addrs) => addrs,
+        Err(_) => return Vec::new(),
+    };
+    let flags = interface_flags();
+
+    let mut interfaces: Vec<Interface> = Vec::new();
+    for addr in addrs {
+        let index = match interfaces.iter().position(|t| t.name == addr.name) {
+            Some(index) => index,
+            None => {
+                interfaces.push(Interface {
+                    is_up: flags.is_up(&addr.name),
+                    name: addr.name.clone(),
+                    ipv4: Vec::new(),
+                    ipv6: Vec::new(),
+                    is_loopback: false,
+                });
+                interfaces.len() - 1
+            }
+        };
+
+        let interface = &mut interfaces[index];
+        interface.is_loopback |= addr.is_loopback();
+        match addr.ip() {
+            IpAddr::V4(ip) => interface.ipv4.push(ip),
+            IpAddr::V6(ip) => interface.ipv6.push(ip),
+        }
+    }
+    interfaces
+}
+
+/// Returns the first interface that is up, is not a loopback interface and has an IPv4 address.
+///
+/// This is a sensible interface to bind to or advertise when a service should be reachable from
+/// other hosts, instead of binding to `0.0.0.0`.
+pub fn primary() -> Option<Interface> {
+    list()
+        .into_iter()
+        .find(|t| t.is_up && !t.is_loopback && !t.ipv4.is_empty())
+}
+
+/// Returns the MAC address of the interface called `interface`.
+///
+/// Returns `None` if there is no such interface or it has no hardware address. Loopback
+/// interfaces report the all-zero address.
+#[cfg(feature = "pnet")]
+pub fn mac_of(interface: &str) -> Option<[u8; 6]> {
+    pnet::datalink::interfaces()
+        .into_iter()
+        .find(|t| t.name == interface)
+        .and_then(|t| t.mac)
+        .map(|t| t.octets())
+}
+
+/// Returns the MAC address of the `primary` interface, skipping all-zero addresses.
+#[cfg(feature = "pnet")]
+pub fn primary_mac() -> Option<[u8; 6]> {
+    primary()
+        .and_then(|t| mac_of(&t.name))
+        .filter(|t| t.iter().any(|b| *b != 0))
+}
+
+/// The `IFF_*` flags of every interface, read with `getifaddrs`.
+#[cfg(unix)]
+struct InterfaceFlags(Vec<(String, libc::c_uint)>);
+
+#[cfg(unix)]
+impl InterfaceFlags {
+    fn is_up(&self, name: &str) -> bool {
+        self.0
+            .iter()
+            .filter(|(t, _)| t == name)
+            .any(|(_, flags)| flags & libc::IFF_UP as libc::c_uint != 0)
+    }
+}
+
+#[cfg(unix)]
+fn interface_flags() -> InterfaceFlags {
+    use std::ffi::CStr;
+
+    let mut flags = Vec::new();
+    let mut ifaddrs: *mut libc::ifaddrs = core::ptr::null_mut();
+    if unsafe { libc::getifaddrs(&mut ifaddrs) } != 0 {
+        return InterfaceFlags(flags);
+    }
+
+    // SAFETY: getifaddrs succeeded, so the list stays valid until it is freed below.
+    let mut current = ifaddrs;
+    while let Some(ifaddr) = unsafe { current.as_ref() } {
+        if !ifaddr.ifa_name.is_null() {
+            let name = unsafe { CStr::from_ptr(ifaddr.ifa_name) };
+            flags.push((name.to_string_lossy().into_owned(), ifaddr.ifa_flags));
+        }
+        current = ifaddr.ifa_next;
+    }
+    unsafe { libc::freeifaddrs(ifaddrs) };
+
+    InterfaceFlags(flags)
+}
+
+#[cfg(not(unix))]
+struct InterfaceFlags;
+
+#[cfg(not(unix))]
+impl InterfaceFlags {
+    fn is_up(&self, _name: &str) -> bool {
+        true
+    }
+}
+
+#[cfg(not(unix))]
+fn interface_flags() -> InterfaceFlags {
+    InterfaceFlags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_contains_loopback() {
+        let interfaces = list();
+        let loopback = interfaces
+            .iter()
+            .find(|t| t.ipv4.contains(&Ipv4Addr::LOCALHOST))
+            .expect("no interface with 127.0.0.1");
+        assert!(loopback.is_loopback);
+        assert!(loopback.is_up);
+        assert!(loopback.ips().any(|t| t == IpAddr::V4(Ipv4Addr::LOCALHOST)));
+    }
+
+    #[test]
+    fn test_list_groups_by_name() {
+        let interfaces = list();
+        for (i, interface) in interfaces.iter().enumerate() {
+            assert!(!interface.ipv4.is_empty() || !interface.ipv6.is_empty());
+            assert!(interfaces[i + 1..].iter().all(|t| t.name != interface.name));
+        }
+    }
+
+    #[cfg(feature = "pnet")]
+    #[test]
+    fn test_primary_mac() {
+        assert_eq!(mac_of("gearbox-missing0"), None);
+
+        // Sandboxed CI hosts may not expose a hardware interface.
+        let Some(primary) = primary() else {
+            return;
+        };
+        let Some(mac) = mac_of(&primary.name) else {
+            return;
+        };
+        assert_ne!(mac, [0u8; 6]);
+        // An interface address is a unicast address, the group bit is only set for multicast.
+        assert_eq!(mac[0] & 0x01, 0);
+        assert_eq!(primary_mac(), Some(mac));
+    }
+
+    #[test]
+    fn test_primary_is_not_loopback() {
+        if let Some(primary) = primary() {
+            assert!(!primary.is_loopback);
+            assert!(primary.is_up);
+            assert!(!primary.ipv4.is_empty());
+        }
+    }
+}
@@ -4,18 +4,22 @@ pub mod endpoint_config;
 pub mod hostname;
 #[cfg(feature = "net-http")]
 pub mod http;
+#[cfg(feature = "net-interfaces")]
+pub mod interfaces;
 #[cfg(feature = "net-ip")]
 pub mod ip;
 #[cfg(feature = "net-ip-range")]
 pub mod ip_range;
 #[cfg(feature = "net-ips")]
 pub mod ips;
+#[cfg(feature = "net-rate-limit")]
+pub mod rate_limit;
 #[cfg(feature = "net-signature")]
 pub mod signature;
 #[cfg(feature = "net-socket-addr")]
 pub mod socket_addr;
 
 #[cfg(feature = "net-hostname")]
-pub use hostname::gethostname;
+pub use hostname::{gethostname, gethostname_fqdn, short_hostname};
 #[cfg(feature = "net-signature")]
 pub use signature::Signature;
@@ -0,0 +1,175 @@
+//! Client-side rate limiting for calls to rate-limited APIs.
+//!
+//! `RateLimiter` is a token bucket: it holds up to `burst` tokens, refills at `rate` tokens per
+//! second and every call takes one token. Calls that find the bucket empty wait until the next
+//! token is available, in the order they arrived. A limiter is usually shared through an `Arc`,
+//! e.g. by all requests of a `request::Client` created with `Client::with_rate_limiter`.
+//!
+//! # Examples
+//! ```
+//! use gearbox::net::rate_limit::RateLimiter;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! // Two calls right away, then one call every 100 ms.
+//! let limiter = RateLimiter::new(10.0, 2);
+//! for _ in 0..4 {
+//!     limiter.acquire().await;
+//! }
+//! # }
+//! ```
+use core::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// A token bucket that limits how often an operation may run.
+#[derive(Debug)]
+pub struct RateLimiter {
+    rate: f64,
+    burst: u32,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    /// Creates a limiter that allows `rate` calls per second on average and up to `burst` calls
+    /// at once. The bucket starts full. A `burst` of `0` is treated as `1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is not a positive, finite number.
+    pub fn new(rate: f64, burst: u32) -> Self {
+        assert!(
+            rate.is_finite() && rate > 0.0,
+            "the rate of a RateLimiter must be positive, got {}",
+            rate
+        );
+        let burst = burst.max(1);
+        Self {
+            rate,
+            burst,
+            bucket: Mutex::new(Bucket {
+                tokens: burst as f64,
+                updated: Instant::now(),
+            }),
+        }
+    }
+
+    /// Returns the number of calls per second this limiter allows on average.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the number of calls this limiter allows at once.
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    /// Returns the number of tokens that can be taken right away.
+    ///
+    /// Returns `0` while another call is waiting for a token.
+    pub fn available(&self) -> u32 {
+        let Ok(mut bucket) = self.bucket.try_lock() else {
+            return 0;
+        };
+        self.refill(&mut bucket, Instant::now());
+        bucket.tokens as u32
+    }
+
+    /// Takes a token, waiting until one is available.
+    ///
+    /// Waiting calls are served in the order they called `acquire`.
+    pub async fn acquire(&self) {
+        let mut bucket = self.bucket.lock().await;
+        let now = Instant::now();
+        self.refill(&mut bucket, now);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return;
+        }
+
+        // The bucket stays locked while waiting, so later calls queue up behind this one.
+        let ready = now + Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate);
+        tokio::time::sleep_until(ready).await;
+        bucket.tokens = 0.0;
+        bucket.updated = ready;
+    }
+
+    /// Takes a token if one is available right away, without waiting.
+    ///
+    /// Returns `false` if the bucket is empty or another call is currently waiting for a token.
+    pub fn try_acquire(&self) -> bool {
+        let Ok(mut bucket) = self.bucket.try_lock() else {
+            return false;
+        };
+        self.refill(&mut bucket, Instant::now());
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn refill(&self, bucket: &mut Bucket, now: Instant) {
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst as f64);
+        bucket.updated = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{sync::Arc, vec::Vec};
+    use std::time::Instant as StdInstant;
+
+    #[tokio::test]
+    async fn test_burst_then_rate() {
+        let start = StdInstant::now();
+        let limiter = RateLimiter::new(20.0, 3);
+        assert_eq!(limiter.available(), 3);
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+        // The burst is used up, at most a fraction of a token was refilled in the meantime.
+        assert_eq!(limiter.available(), 0);
+        assert!(!limiter.try_acquire());
+
+        // 4 more tokens at 20 per second take at least 200 ms.
+        for _ in 0..4 {
+            limiter.acquire().await;
+        }
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_shared_between_tasks() {
+        let start = StdInstant::now();
+        let limiter = Arc::new(RateLimiter::new(50.0, 1));
+        let tasks: Vec<_> = (0..5)
+            .map(|_| {
+                let limiter = limiter.clone();
+                tokio::spawn(async move { limiter.acquire().await })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        // The first call is free, the other 4 wait 20 ms each.
+        assert!(start.elapsed() >= Duration::from_millis(80));
+    }
+
+    #[test]
+    fn test_zero_burst() {
+        let limiter = RateLimiter::new(1.0, 0);
+        assert_eq!(limiter.burst(), 1);
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+    }
+}
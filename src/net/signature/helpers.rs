@@ -1,9 +1,22 @@
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 use hmac::{Hmac, KeyInit, Mac};
+#[cfg(feature = "net-signature-sha1")]
+use sha1::Sha1;
 use sha2::{Digest, Sha256, Sha512};
+#[cfg(feature = "net-signature-sha3")]
+use sha3::Sha3_256;
 
 type HmacSha512 = Hmac<Sha512>;
 type HmacSha256 = Hmac<Sha256>;
+#[cfg(feature = "net-signature-sha3")]
+type HmacSha3_256 = Hmac<Sha3_256>;
+
+#[cfg(feature = "net-signature-sha1")]
+pub fn sha1(value: &Vec<u8>) -> Vec<u8> {
+    let mut hasher = Sha1::new();
+    hasher.update(value);
+    hasher.finalize().as_slice().to_vec()
+}
 
 pub fn sha256(value: &Vec<u8>) -> Vec<u8> {
     let mut hasher = Sha256::new();
@@ -17,6 +30,13 @@ pub fn sha512(value: &Vec<u8>) -> Vec<u8> {
     hasher.finalize().as_slice().to_vec()
 }
 
+#[cfg(feature = "net-signature-sha3")]
+pub fn sha3_256(value: &Vec<u8>) -> Vec<u8> {
+    let mut hasher = Sha3_256::new();
+    hasher.update(value);
+    hasher.finalize().as_slice().to_vec()
+}
+
 pub fn hmac_sha512(enc_key: &[u8], value: &[u8]) -> Vec<u8> {
     let mut mac = HmacSha512::new_from_slice(enc_key).expect("HMAC can take key of any size");
     mac.update(value);
@@ -30,6 +50,14 @@ pub fn hmac_sha256(enc_key: &Vec<u8>, value: &Vec<u8>) -> Vec<u8> {
     mac.finalize().into_bytes().to_vec()
 }
 
+#[cfg(feature = "net-signature-sha3")]
+pub fn hmac_sha3_256(enc_key: &Vec<u8>, value: &Vec<u8>) -> Vec<u8> {
+    let mut mac =
+        HmacSha3_256::new_from_slice(enc_key.as_slice()).expect("HMAC can take key of any size");
+    mac.update(value.as_slice());
+    mac.finalize().into_bytes().to_vec()
+}
+
 pub fn base64decode(value: &Vec<u8>) -> Vec<u8> {
     base64::decode(value).unwrap_or(Vec::new())
 }
@@ -44,6 +72,102 @@ pub fn base58encode(value: &Vec<u8>) -> Vec<u8> {
     bs58::encode(value).into_string().as_bytes().to_vec()
 }
 
+pub fn hexdecode(value: &Vec<u8>) -> Vec<u8> {
+    hex::decode(value).unwrap_or_default()
+}
+pub fn hexencode(value: &Vec<u8>) -> Vec<u8> {
+    hex::encode(value).as_bytes().to_vec()
+}
+
+pub fn urlencode(value: &[u8]) -> Vec<u8> {
+    url::form_urlencoded::byte_serialize(value)
+        .collect::<String>()
+        .into_bytes()
+}
+pub fn urldecode(value: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::with_capacity(value.len());
+    let mut i = 0;
+    while i < value.len() {
+        let escaped = value
+            .get(i + 1..i + 3)
+            .filter(|_| value[i] == b'%')
+            .and_then(|hex| Some(hex_value(hex[0])? << 4 | hex_value(hex[1])?));
+        match (value[i], escaped) {
+            (_, Some(b)) => {
+                decoded.push(b);
+                i += 3;
+            }
+            (b'+', None) => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (b, None) => {
+                decoded.push(b);
+                i += 1;
+            }
+        }
+    }
+    decoded
+}
+
+fn hex_value(b: u8) -> Option<u8> {
+    (b as char).to_digit(16).map(|d| d as u8)
+}
+
+/// Signs `value` with the Ed25519 key derived from a 32 byte seed. Returns the 64 byte signature,
+/// or an empty value if the seed has the wrong length.
+#[cfg(feature = "ed25519")]
+pub fn ed25519_sign(seed: &[u8], value: &[u8]) -> Vec<u8> {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    match <[u8; 32]>::try_from(seed) {
+        Ok(seed) => SigningKey::from_bytes(&seed)
+            .sign(value)
+            .to_bytes()
+            .to_vec(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Returns the 32 byte Ed25519 public key for a 32 byte seed, or an empty value if the seed has
+/// the wrong length.
+#[cfg(feature = "ed25519")]
+pub fn ed25519_public_key(seed: &[u8]) -> Vec<u8> {
+    match <[u8; 32]>::try_from(seed) {
+        Ok(seed) => ed25519_dalek::SigningKey::from_bytes(&seed)
+            .verifying_key()
+            .to_bytes()
+            .to_vec(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Checks a 64 byte Ed25519 `signature` of `value` against a 32 byte public key.
+#[cfg(feature = "ed25519")]
+pub fn ed25519_verify(public_key: &[u8], value: &[u8], signature: &[u8]) -> bool {
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+    let key = <[u8; 32]>::try_from(public_key)
+        .ok()
+        .and_then(|key| VerifyingKey::from_bytes(&key).ok());
+    let signature = <[u8; 64]>::try_from(signature)
+        .ok()
+        .map(|signature| Signature::from_bytes(&signature));
+    match (key, signature) {
+        (Some(key), Some(signature)) => key.verify(value, &signature).is_ok(),
+        _ => false,
+    }
+}
+
+/// Compares two byte slices without short-circuiting on the first mismatch. Only the length is
+/// allowed to leak.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -85,6 +209,38 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "net-signature-sha1")]
+    #[test]
+    fn test_sha1() {
+        assert_eq!(
+            hex::encode(sha1(&b"abc".to_vec())),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+    }
+
+    #[cfg(feature = "net-signature-sha3")]
+    #[test]
+    fn test_sha3_256() {
+        assert_eq!(
+            hex::encode(sha3_256(&b"abc".to_vec())),
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+        );
+    }
+
+    #[cfg(feature = "net-signature-sha3")]
+    #[test]
+    fn test_hmac_sha3_256() {
+        let key = b"secret key";
+        let data = b"hello world";
+        let mut mac = HmacSha3_256::new_from_slice(key).unwrap();
+        mac.update(data);
+        let expected = mac.finalize().into_bytes();
+        assert_eq!(
+            hmac_sha3_256(&key.to_vec(), &data.to_vec()),
+            expected.to_vec()
+        );
+    }
+
     #[test]
     fn test_base64_encode_decode() {
         let data = b"hello world";
@@ -100,4 +256,51 @@ mod tests {
         let decoded = base58decode(&encoded);
         assert_eq!(decoded, data.to_vec());
     }
+
+    #[test]
+    fn test_hex_encode_decode() {
+        let data = b"hello world";
+        let encoded = hexencode(&data.to_vec());
+        assert_eq!(encoded, b"68656c6c6f20776f726c64".to_vec());
+        assert_eq!(hexdecode(&encoded), data.to_vec());
+    }
+
+    #[test]
+    fn test_url_encode_decode() {
+        let data = b"a=1&b=hello world";
+        let encoded = urlencode(data);
+        assert_eq!(encoded, b"a%3D1%26b%3Dhello+world".to_vec());
+        assert_eq!(urldecode(&encoded), data.to_vec());
+        assert_eq!(urldecode(b"100%25%2x%"), b"100%%2x%".to_vec());
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"signature", b"signature"));
+        assert!(!constant_time_eq(b"signature", b"signaturf"));
+        assert!(!constant_time_eq(b"signature", b"signatur"));
+        assert!(constant_time_eq(b"", b""));
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_ed25519() {
+        // RFC 8032, section 7.1, test 2
+        let seed = hex::decode("4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb")
+            .unwrap();
+        let public_key = ed25519_public_key(&seed);
+        assert_eq!(
+            hex::encode(&public_key),
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c"
+        );
+        let signature = ed25519_sign(&seed, &[0x72]);
+        assert_eq!(
+            hex::encode(&signature),
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+             085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00"
+        );
+        assert!(ed25519_verify(&public_key, &[0x72], &signature));
+        assert!(!ed25519_verify(&public_key, &[0x73], &signature));
+        assert!(ed25519_sign(&seed[1..], &[0x72]).is_empty());
+    }
 }
@@ -65,6 +65,7 @@ use alloc::{
 };
 use core::fmt;
 use core::ops::Deref;
+use core::sync::atomic::{AtomicU64, Ordering};
 use hashbrown::HashMap;
 use serde_derive::{Deserialize, Serialize};
 
@@ -125,6 +126,37 @@ impl Signature {
         self
     }
 
+    /// Uses a counter starting at `start` as nonce, incremented on every signing.
+    pub fn nonce_counter(&mut self, start: u64) -> &mut Self {
+        let counter = Arc::new(AtomicU64::new(start));
+        self.nonce = Some(Arc::new(move || -> Vec<u8> {
+            counter
+                .fetch_add(1, Ordering::SeqCst)
+                .to_string()
+                .into_bytes()
+        }));
+        self
+    }
+
+    /// Uses the current unix time in milliseconds, followed by a three digit sub-counter, as
+    /// nonce. Values are strictly increasing, even for calls within the same millisecond or when
+    /// the clock goes backwards.
+    pub fn nonce_monotonic(&mut self) -> &mut Self {
+        let last = Arc::new(AtomicU64::new(0));
+        self.nonce = Some(Arc::new(move || -> Vec<u8> {
+            let now = (DateTime::now().as_millis_since_epoch().max(0) as u64).saturating_mul(1000);
+            let mut previous = last.load(Ordering::SeqCst);
+            loop {
+                let next = now.max(previous + 1);
+                match last.compare_exchange(previous, next, Ordering::SeqCst, Ordering::SeqCst) {
+                    Ok(_) => return next.to_string().into_bytes(),
+                    Err(current) => previous = current,
+                }
+            }
+        }));
+        self
+    }
+
     pub fn nonce_lock(&mut self) -> Option<Vec<u8>> {
         let nonce_fn = self.nonce.clone();
         let nonce = nonce_fn.map(|t| t());
@@ -147,10 +179,18 @@ impl Signature {
         self
     }
 
+    #[deprecated(note = "use `Signature::verify` instead")]
     pub fn compare<T: Into<Vec<u8>>>(&mut self, signature: T, nonce: Vec<u8>) -> bool {
+        self.verify(signature, nonce)
+    }
+
+    /// Signs with the given nonce and compares the result against `expected` in constant time,
+    /// so the comparison does not leak how much of an inbound signature matched.
+    pub fn verify<T: Into<Vec<u8>>>(&mut self, expected: T, nonce: Vec<u8>) -> bool {
         let mut _self = self.clone();
         _self.nonce = Some(Arc::new(move || -> Vec<u8> { nonce.clone() }));
-        signature.into() == self.sign()
+        _self.nonce_lock = None;
+        helpers::constant_time_eq(&expected.into(), &_self.sign())
     }
 
     pub fn sign(&mut self) -> Vec<u8> {
@@ -190,11 +230,38 @@ fn sign_calc(config: &SignCal, variables: &HashMap<String, Variable>) -> Vec<u8>
         SignCal::HmacSha256(k, c) => {
             helpers::hmac_sha256(&sign_calc(k, variables), &sign_calc(c.deref(), variables))
         }
+        #[cfg(feature = "net-signature-sha3")]
+        SignCal::HmacSha3_256(k, c) => {
+            helpers::hmac_sha3_256(&sign_calc(k, variables), &sign_calc(c.deref(), variables))
+        }
+        #[cfg(feature = "net-signature-sha1")]
+        SignCal::Sha1(c) => helpers::sha1(&sign_calc(c, variables)),
         SignCal::Sha256(c) => helpers::sha256(&sign_calc(c, variables)),
+        #[cfg(feature = "net-signature-sha3")]
+        SignCal::Sha3_256(c) => helpers::sha3_256(&sign_calc(c, variables)),
+        #[cfg(feature = "ed25519")]
+        SignCal::Ed25519Sign(k, c) => {
+            helpers::ed25519_sign(&sign_calc(k, variables), &sign_calc(c, variables))
+        }
+        #[cfg(feature = "ed25519")]
+        SignCal::Ed25519PublicKey(k) => helpers::ed25519_public_key(&sign_calc(k, variables)),
+        #[cfg(feature = "ed25519")]
+        SignCal::Ed25519Verify(k, c, s) => {
+            let valid = helpers::ed25519_verify(
+                &sign_calc(k, variables),
+                &sign_calc(c, variables),
+                &sign_calc(s, variables),
+            );
+            vec![valid as u8]
+        }
         SignCal::Base64Encode(c) => helpers::base64encode(&sign_calc(c, variables)),
         SignCal::Base64Decode(c) => helpers::base64decode(&sign_calc(c, variables)),
         SignCal::Base58Encode(c) => helpers::base58encode(&sign_calc(c, variables)),
         SignCal::Base58Decode(c) => helpers::base58decode(&sign_calc(c, variables)),
+        SignCal::HexEncode(c) => helpers::hexencode(&sign_calc(c, variables)),
+        SignCal::HexDecode(c) => helpers::hexdecode(&sign_calc(c, variables)),
+        SignCal::UrlEncode(c) => helpers::urlencode(&sign_calc(c, variables)),
+        SignCal::UrlDecode(c) => helpers::urldecode(&sign_calc(c, variables)),
         SignCal::Sha512(c) => helpers::sha512(&sign_calc(c, variables)),
         SignCal::Append(a) => a
             .iter()
@@ -227,12 +294,33 @@ fn sign_calc(config: &SignCal, variables: &HashMap<String, Variable>) -> Vec<u8>
 pub enum SignCal {
     HmacSha256(Box<SignCal>, Box<SignCal>),
     HmacSha512(Box<SignCal>, Box<SignCal>),
+    #[cfg(feature = "net-signature-sha3")]
+    HmacSha3_256(Box<SignCal>, Box<SignCal>),
+    #[cfg(feature = "net-signature-sha1")]
+    Sha1(Box<SignCal>),
     Sha256(Box<SignCal>),
+    #[cfg(feature = "net-signature-sha3")]
+    Sha3_256(Box<SignCal>),
     Sha512(Box<SignCal>),
+    /// Signs the message (second node) with the Ed25519 key derived from the 32 byte seed
+    /// (first node), resolving to the 64 byte signature.
+    #[cfg(feature = "ed25519")]
+    Ed25519Sign(Box<SignCal>, Box<SignCal>),
+    /// Resolves to the 32 byte Ed25519 public key of a 32 byte seed.
+    #[cfg(feature = "ed25519")]
+    Ed25519PublicKey(Box<SignCal>),
+    /// Verifies the signature (third node) of the message (second node) against the public key
+    /// (first node), resolving to `[1]` if it is valid and `[0]` otherwise.
+    #[cfg(feature = "ed25519")]
+    Ed25519Verify(Box<SignCal>, Box<SignCal>, Box<SignCal>),
     Base64Encode(Box<SignCal>),
     Base64Decode(Box<SignCal>),
     Base58Encode(Box<SignCal>),
     Base58Decode(Box<SignCal>),
+    HexEncode(Box<SignCal>),
+    HexDecode(Box<SignCal>),
+    UrlEncode(Box<SignCal>),
+    UrlDecode(Box<SignCal>),
     Append(Vec<SignCal>),
     JoinAsString(Vec<SignCal>),
     VarData(String),
@@ -364,6 +452,7 @@ impl From<&[u8]> for Variable {
 mod tests {
     use super::{SignCal, Signature};
     use alloc::sync::Arc;
+    use base64::Engine;
     use hex;
 
     #[test]
@@ -416,6 +505,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hex() {
+        use SignCal::*;
+        assert_eq!(
+            b"f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8".to_vec(),
+            Signature::default()
+                .var("key", b"key".to_vec())
+                .var("payload", "The quick brown fox jumps over the lazy dog")
+                .config(HexEncode(
+                    HmacSha256(
+                        VarData("key".to_string()).into(),
+                        VarString("payload".to_string()).into()
+                    )
+                    .into()
+                ))
+                .sign()
+        );
+    }
+
+    #[test]
+    fn test_hex_decode() {
+        use SignCal::*;
+        assert_eq!(
+            b"1234567890".to_vec(),
+            Signature::default()
+                .config(HexDecode(Raw(b"31323334353637383930".to_vec()).into()))
+                .sign()
+        );
+    }
+
+    #[test]
+    fn test_url_encode() {
+        use SignCal::*;
+        assert_eq!(
+            b"pair=XBTUSD&query=price%3D37500%26type%3Dbuy+now".to_vec(),
+            Signature::default()
+                .var("payload", "price=37500&type=buy now")
+                .config(JoinAsString(vec![
+                    String("pair=XBTUSD&query=".to_string()),
+                    UrlEncode(VarString("payload".to_string()).into()),
+                ]))
+                .sign()
+        );
+    }
+
+    #[test]
+    fn test_url_decode() {
+        use SignCal::*;
+        assert_eq!(
+            b"price=37500&type=buy now".to_vec(),
+            Signature::default()
+                .config(UrlDecode(
+                    Raw(b"price%3D37500%26type%3Dbuy+now".to_vec()).into()
+                ))
+                .sign()
+        );
+    }
+
     #[test]
     fn test_sha256() {
         use SignCal::*;
@@ -444,6 +591,87 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "net-signature-sha1")]
+    #[test]
+    fn test_sha1() {
+        use SignCal::*;
+        assert_eq!(
+            hex::decode("01b307acba4f54f55aafc33bb06bbbf6ca803e9a").unwrap(),
+            Signature::default()
+                .config(Sha1(Raw(b"1234567890".to_vec()).into()))
+                .sign()
+        );
+    }
+
+    #[cfg(feature = "net-signature-sha3")]
+    #[test]
+    fn test_sha3_256() {
+        use SignCal::*;
+        assert_eq!(
+            hex::decode("01da8843e976913aa5c15a62d45f1c9267391dcbd0a76ad411919043f374a163")
+                .unwrap(),
+            Signature::default()
+                .config(Sha3_256(Raw(b"1234567890".to_vec()).into()))
+                .sign()
+        );
+    }
+
+    #[cfg(feature = "net-signature-sha3")]
+    #[test]
+    fn test_hmac_sha3_256() {
+        use SignCal::*;
+        assert_eq!(
+            hex::decode("8c6e0683409427f8931711b10ca92a506eb1fafa48fadd66d76126f47ac2c333")
+                .unwrap(),
+            Signature::default()
+                .config(HmacSha3_256(
+                    Raw(b"key".to_vec()).into(),
+                    Raw(b"The quick brown fox jumps over the lazy dog".to_vec()).into(),
+                ))
+                .sign()
+        );
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_ed25519() {
+        use SignCal::*;
+        // RFC 8032, section 7.1, test 1
+        let mut signature = Signature::default();
+        signature
+            .var(
+                "seed",
+                hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+                    .unwrap(),
+            )
+            .config(Ed25519Sign(
+                VarData("seed".to_string()).into(),
+                Raw(Vec::new()).into(),
+            ));
+        let signed = signature.sign();
+        assert_eq!(
+            hex::decode(
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+            )
+            .unwrap(),
+            signed
+        );
+
+        let verify = |message: &[u8]| {
+            signature
+                .clone()
+                .var("signature", signed.clone())
+                .config(Ed25519Verify(
+                    Ed25519PublicKey(VarData("seed".to_string()).into()).into(),
+                    Raw(message.to_vec()).into(),
+                    VarData("signature".to_string()).into(),
+                ))
+                .sign()
+        };
+        assert_eq!(vec![1], verify(b""));
+        assert_eq!(vec![0], verify(b"tampered"));
+    }
+
     #[test]
     fn test_sign_cal_with_control_signature() {
         use SignCal::*;
@@ -483,6 +711,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_sign_cal_compare_with_control_signature() {
         let nonce = 1616492376594usize;
         let mut signature = Signature::default();
@@ -496,6 +725,21 @@ mod tests {
         assert!(signature.compare(api_sign, nonce.to_string().as_bytes().to_vec()));
     }
 
+    #[test]
+    fn test_sign_cal_verify_with_control_signature() {
+        let nonce = 1616492376594usize;
+        let mut signature = Signature::default();
+        signature.var("payload", "ordertype=limit&pair=XBTUSD&price=37500&type=buy&volume=1.25")
+            .var("secret_key", "kQH5HW/8p1uGOVjbgWA7FunAmGO8lsSUXNsu3eow76sz84Q18fWxnyRzBHCd3pd5nE9qa99HAZtuZuj6F1huXg==")
+            .var("url", "/0/private/AddOrder");
+
+        let api_sign = base64::engine::general_purpose::STANDARD.decode("4/dpxb3iT4tp/ZCVEwSnEsLxx0bqyhLpdfOpc6fn7OR8+UClSV5n9E6aSS8MPtnRfp32bAb0nmbRn6H8ndwLUQ==").unwrap().to_vec();
+
+        assert!(signature.verify(api_sign.clone(), nonce.to_string().as_bytes().to_vec()));
+        assert!(!signature.verify(api_sign.clone(), b"1616492376595".to_vec()));
+        assert!(!signature.verify(&api_sign[1..], nonce.to_string().as_bytes().to_vec()));
+    }
+
     #[test]
     fn test_nonce_lock() {
         let mut signing = Signature::default();
@@ -510,6 +754,34 @@ mod tests {
         assert_eq!(b64_nonce, cal_sign.sign());
     }
 
+    #[test]
+    fn test_nonce_counter() {
+        let mut signing = Signature::default();
+        signing
+            .config(SignCal::VarInteger("nonce".to_string()))
+            .nonce_counter(41);
+        assert_eq!(b"41".to_vec(), signing.sign());
+        assert_eq!(b"42".to_vec(), signing.clone().sign());
+        assert_eq!(b"43".to_vec(), signing.sign());
+    }
+
+    #[test]
+    fn test_nonce_monotonic() {
+        let mut signing = Signature::default();
+        signing
+            .config(SignCal::VarInteger("nonce".to_string()))
+            .nonce_monotonic();
+        let nonces = (0..10)
+            .map(|_| {
+                String::from_utf8(signing.sign())
+                    .unwrap()
+                    .parse::<u64>()
+                    .unwrap()
+            })
+            .collect::<Vec<u64>>();
+        assert!(nonces.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
     #[test]
     fn test_confirm_unlock() {
         let mut signing = Signature::default();
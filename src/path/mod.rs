@@ -1,2 +1,219 @@
 #[cfg(feature = "path-dirs")]
 pub use dirs::*;
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Expands a leading `~` to the home directory and `$VAR` or `${VAR}` to the value of the
+/// environment variable `VAR`.
+///
+/// - `~` is only expanded at the start of the input, when it is the whole input or followed by a
+///   path separator. `~user` is not supported, so the input is returned unchanged.
+/// - Undefined variables expand to an empty string, like they would in a shell.
+/// - A `$` that is not followed by a variable name, or a `${` without a closing `}`, is kept as is.
+///
+/// # Examples
+/// ```
+/// use gearbox::path::expand;
+/// use std::path::PathBuf;
+///
+/// std::env::set_var("GEARBOX_EXPAND_APP", "app");
+/// let home = PathBuf::from(std::env::var_os("HOME").unwrap());
+///
+/// assert_eq!(expand("~/.config/$GEARBOX_EXPAND_APP"), home.join(".config/app"));
+/// assert_eq!(expand("/etc/${GEARBOX_EXPAND_APP}.toml"), PathBuf::from("/etc/app.toml"));
+/// ```
+pub fn expand(input: &str) -> PathBuf {
+    let (home, rest) = match input.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(std::path::is_separator) => {
+            match user_home_dir() {
+                Some(home) => (Some(home), rest),
+                None => (None, input),
+            }
+        }
+        // `~user` would need a lookup in the user database, which is not supported.
+        Some(_) => return PathBuf::from(input),
+        None => (None, input),
+    };
+
+    let mut expanded = home.map(OsString::from).unwrap_or_default();
+    let mut rest = rest;
+    while let Some(start) = rest.find('$') {
+        expanded.push(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        let (name, remaining) = match after.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) if is_var_name(&braced[..end]) => (&braced[..end], &braced[end + 1..]),
+                _ => ("", after),
+            },
+            None => {
+                let end = after
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(after.len());
+                if is_var_name(&after[..end]) {
+                    (&after[..end], &after[end..])
+                } else {
+                    ("", after)
+                }
+            }
+        };
+
+        if name.is_empty() {
+            expanded.push("$");
+        } else if let Some(value) = std::env::var_os(name) {
+            expanded.push(value);
+        }
+        rest = remaining;
+    }
+    expanded.push(rest);
+
+    PathBuf::from(expanded)
+}
+
+/// Returns the configuration directory of `app`, e.g. `~/.config/<app>` on Linux.
+///
+/// The directory is not created, use `ensure` for that. Returns `None` if the platform has no
+/// configuration directory.
+#[cfg(feature = "path-dirs")]
+pub fn config_dir_for(app: &str) -> Option<PathBuf> {
+    app_dir(dirs::config_dir(), app)
+}
+
+/// Returns the cache directory of `app`, e.g. `~/.cache/<app>` on Linux.
+///
+/// The directory is not created, use `ensure` for that. Returns `None` if the platform has no
+/// cache directory.
+#[cfg(feature = "path-dirs")]
+pub fn cache_dir_for(app: &str) -> Option<PathBuf> {
+    app_dir(dirs::cache_dir(), app)
+}
+
+#[cfg(feature = "path-dirs")]
+fn app_dir(base: Option<PathBuf>, app: &str) -> Option<PathBuf> {
+    base.filter(|t| t.is_absolute()).map(|t| t.join(app))
+}
+
+/// Creates the directory `path` and all of its missing parents, and returns the path.
+///
+/// Succeeds if the directory already exists, but fails if `path` exists and is not a directory.
+///
+/// # Examples
+/// ```no_run
+/// use gearbox::path::{ensure, expand};
+///
+/// let config_dir = ensure(expand("~/.config/my-app")).unwrap();
+/// std::fs::write(config_dir.join("config.toml"), "").unwrap();
+/// ```
+pub fn ensure<P: AsRef<Path>>(path: P) -> std::io::Result<PathBuf> {
+    let path = path.as_ref();
+    std::fs::create_dir_all(path).map(|_| path.to_path_buf())
+}
+
+fn is_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn user_home_dir() -> Option<PathBuf> {
+    #[cfg(feature = "path-dirs")]
+    {
+        dirs::home_dir()
+    }
+
+    #[cfg(not(feature = "path-dirs"))]
+    {
+        std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .filter(|t| !t.is_empty())
+            .map(PathBuf::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_home() {
+        let home = user_home_dir().unwrap();
+        assert_eq!(expand("~"), home);
+        assert_eq!(expand("~/foo"), home.join("foo"));
+        assert_eq!(expand("/tmp/~/foo"), PathBuf::from("/tmp/~/foo"));
+        assert_eq!(expand("~user/foo"), PathBuf::from("~user/foo"));
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        let home = PathBuf::from(std::env::var_os("HOME").unwrap());
+        assert_eq!(expand("$HOME/foo"), home.join("foo"));
+        assert_eq!(expand("${HOME}/foo"), home.join("foo"));
+
+        std::env::set_var("GEARBOX_TEST_EXPAND_DIR", "configs");
+        assert_eq!(
+            expand("/srv/$GEARBOX_TEST_EXPAND_DIR/app-${GEARBOX_TEST_EXPAND_DIR}.toml"),
+            PathBuf::from("/srv/configs/app-configs.toml")
+        );
+    }
+
+    #[test]
+    fn test_expand_undefined_and_literal() {
+        std::env::remove_var("GEARBOX_TEST_EXPAND_UNDEFINED");
+        assert_eq!(
+            expand("/srv/$GEARBOX_TEST_EXPAND_UNDEFINED/foo"),
+            PathBuf::from("/srv//foo")
+        );
+        assert_eq!(
+            expand("/srv/${GEARBOX_TEST_EXPAND_UNDEFINED}foo"),
+            PathBuf::from("/srv/foo")
+        );
+        assert_eq!(expand("/srv/$/foo"), PathBuf::from("/srv/$/foo"));
+        assert_eq!(expand("/srv/$1/foo"), PathBuf::from("/srv/$1/foo"));
+        assert_eq!(expand("/srv/${foo"), PathBuf::from("/srv/${foo"));
+    }
+
+    #[test]
+    fn test_ensure() {
+        let root = std::env::temp_dir().join(format!("gearbox-ensure-{}", std::process::id()));
+        let nested = root.join("a/b/c");
+
+        assert_eq!(ensure(&nested).unwrap(), nested);
+        assert!(nested.is_dir());
+        // Existing directories are fine, files are not.
+        assert!(ensure(&nested).is_ok());
+        std::fs::write(root.join("file"), "").unwrap();
+        assert!(ensure(root.join("file")).is_err());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(feature = "path-dirs")]
+    #[test]
+    fn test_app_dirs() {
+        let base = std::env::temp_dir().join(format!("gearbox-app-dirs-{}", std::process::id()));
+        let dir = app_dir(Some(base.join(".config")), "gearbox-test").unwrap();
+        assert_eq!(dir, base.join(".config/gearbox-test"));
+        assert_eq!(ensure(&dir).unwrap(), dir);
+        assert!(dir.is_dir());
+        std::fs::remove_dir_all(&base).unwrap();
+
+        // Without a base directory there is no fallback to the working directory.
+        assert_eq!(app_dir(None, "gearbox-test"), None);
+        assert_eq!(
+            app_dir(Some(PathBuf::from("relative")), "gearbox-test"),
+            None
+        );
+
+        assert_eq!(
+            config_dir_for("gearbox-test"),
+            dirs::config_dir().map(|t| t.join("gearbox-test"))
+        );
+        assert_eq!(
+            cache_dir_for("gearbox-test"),
+            dirs::cache_dir().map(|t| t.join("gearbox-test"))
+        );
+    }
+}
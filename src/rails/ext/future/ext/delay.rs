@@ -0,0 +1,203 @@
+#[cfg(any(feature = "tokio", target_arch = "wasm32"))]
+use alloc::boxed::Box;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use core::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    sync::{mpsc, Arc, Mutex, OnceLock},
+    time::Instant,
+};
+
+/// A timer future resolving once the given duration has elapsed since it was first polled.
+///
+/// With the `tokio` feature and a running tokio runtime the timer is backed by
+/// `tokio::time::sleep`, on wasm32 by `gloo-timers`. Otherwise a single timer thread, shared by all
+/// delays of the process, wakes the task, which works with any executor.
+pub struct Delay {
+    duration: Duration,
+    timer: Option<Timer>,
+}
+
+enum Timer {
+    #[cfg(feature = "tokio")]
+    Tokio(Pin<Box<tokio::time::Sleep>>),
+    #[cfg(target_arch = "wasm32")]
+    Gloo(Pin<Box<gloo_timers::future::TimeoutFuture>>),
+    #[cfg(not(target_arch = "wasm32"))]
+    Thread(Arc<Mutex<ThreadState>>),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct ThreadState {
+    elapsed: bool,
+    waker: Option<Waker>,
+}
+
+/// A delay registered with the shared timer thread.
+#[cfg(not(target_arch = "wasm32"))]
+struct Entry {
+    deadline: Instant,
+    state: Arc<Mutex<ThreadState>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.deadline == other.deadline
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Eq for Entry {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.deadline.cmp(&other.deadline)
+    }
+}
+
+/// The sender of the shared timer thread, which is started by the first delay that needs it.
+#[cfg(not(target_arch = "wasm32"))]
+static TIMER_THREAD: OnceLock<mpsc::Sender<Entry>> = OnceLock::new();
+
+/// Runs the shared timer thread: wakes every registered delay once its deadline has passed.
+#[cfg(not(target_arch = "wasm32"))]
+fn run_timer_thread(receiver: mpsc::Receiver<Entry>) {
+    let mut pending: BinaryHeap<Reverse<Entry>> = BinaryHeap::new();
+    loop {
+        let now = Instant::now();
+        while pending.peek().is_some_and(|t| t.0.deadline <= now) {
+            if let Some(Reverse(entry)) = pending.pop() {
+                let mut state = entry.state.lock().unwrap_or_else(|e| e.into_inner());
+                state.elapsed = true;
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            }
+        }
+        let received = match pending.peek() {
+            Some(next) => match receiver.recv_timeout(next.0.deadline - now) {
+                Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                received => received.ok(),
+            },
+            None => receiver.recv().ok(),
+        };
+        match received {
+            Some(entry) => pending.push(Reverse(entry)),
+            None if pending.is_empty() => return,
+            None => {}
+        }
+    }
+}
+
+impl Delay {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            timer: None,
+        }
+    }
+
+    fn start(duration: Duration, waker: &Waker) -> Timer {
+        #[cfg(feature = "tokio")]
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Timer::Tokio(Box::pin(tokio::time::sleep(duration)));
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let _ = waker;
+            Timer::Gloo(Box::pin(gloo_timers::future::sleep(duration)))
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let state = Arc::new(Mutex::new(ThreadState {
+                elapsed: false,
+                waker: Some(waker.clone()),
+            }));
+            let sender = TIMER_THREAD.get_or_init(|| {
+                let (sender, receiver) = mpsc::channel();
+                std::thread::Builder::new()
+                    .name("gearbox-delay".to_string())
+                    .spawn(move || run_timer_thread(receiver))
+                    .expect("failed to spawn the delay timer thread");
+                sender
+            });
+            let entry = Entry {
+                deadline: Instant::now() + duration,
+                state: state.clone(),
+            };
+            if sender.send(entry).is_err() {
+                // The timer thread is gone, so nobody would wake the task: resolve right away.
+                state.lock().unwrap_or_else(|e| e.into_inner()).elapsed = true;
+            }
+            Timer::Thread(state)
+        }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.timer.is_none() && this.duration.is_zero() {
+            return Poll::Ready(());
+        }
+        let duration = this.duration;
+        match this
+            .timer
+            .get_or_insert_with(|| Delay::start(duration, cx.waker()))
+        {
+            #[cfg(feature = "tokio")]
+            Timer::Tokio(sleep) => sleep.as_mut().poll(cx),
+            #[cfg(target_arch = "wasm32")]
+            Timer::Gloo(sleep) => sleep.as_mut().poll(cx),
+            #[cfg(not(target_arch = "wasm32"))]
+            Timer::Thread(state) => {
+                let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+                if state.elapsed {
+                    Poll::Ready(())
+                } else {
+                    state.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_delays_share_timer_thread() {
+        let started = Instant::now();
+        let delays = (0..64)
+            .map(|t| Delay::new(Duration::from_millis(64 - t)))
+            .collect::<Vec<_>>();
+        futures::executor::block_on(futures::future::join_all(delays));
+        assert!(started.elapsed() >= Duration::from_millis(64));
+        assert!(TIMER_THREAD.get().is_some());
+    }
+
+    #[test]
+    fn test_zero_delay_is_ready() {
+        futures::executor::block_on(Delay::new(Duration::ZERO));
+    }
+}
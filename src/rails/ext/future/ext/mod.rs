@@ -1,5 +1,19 @@
+pub mod delay;
 pub mod option;
 pub mod result;
+#[cfg(not(feature = "futures"))]
+pub mod try_join_all;
+pub mod zip;
+
+use core::future::Future;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+pub use delay::Delay;
+#[cfg(not(feature = "futures"))]
+pub use try_join_all::TryJoinAll;
+pub use zip::Zip;
 
 pub enum MultiState<Fut, FutFunc, FuncOutput> {
     Waiting { future: Fut, func: FutFunc },
@@ -22,3 +36,40 @@ pub enum MultiStateX<Fut, Input, FutFunc, FuncOutput> {
     },
     Done,
 }
+
+/// A future that is driven to completion while its output is kept until it is taken.
+pub enum MaybeDone<Fut: Future> {
+    Future(Fut),
+    Done(Fut::Output),
+    Gone,
+}
+
+impl<Fut: Future> MaybeDone<Fut> {
+    /// Polls the inner future, returning `true` once its output is available.
+    pub fn poll_done(self: Pin<&mut Self>, cx: &mut Context<'_>) -> bool {
+        let this = unsafe { self.get_unchecked_mut() };
+        match this {
+            MaybeDone::Future(future) => match unsafe { Pin::new_unchecked(future) }.poll(cx) {
+                Poll::Ready(output) => {
+                    *this = MaybeDone::Done(output);
+                    true
+                }
+                Poll::Pending => false,
+            },
+            MaybeDone::Done(_) => true,
+            MaybeDone::Gone => panic!("Future polled after completion"),
+        }
+    }
+
+    /// Takes the output out of a completed future.
+    pub fn take_output(self: Pin<&mut Self>) -> Option<Fut::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        match this {
+            MaybeDone::Done(_) => match mem::replace(this, MaybeDone::Gone) {
+                MaybeDone::Done(output) => Some(output),
+                _ => unreachable!(),
+            },
+            _ => None,
+        }
+    }
+}
@@ -2,8 +2,10 @@ pub mod and_then;
 pub mod filter;
 pub mod map;
 pub mod merge;
+pub mod ok_or;
 pub mod or;
 pub mod or_else;
+pub mod timeout;
 pub mod unwrap_or;
 
 pub mod unwrap_or_else;
@@ -13,8 +15,10 @@ pub use {
     filter::Filter,
     map::Map,
     merge::{Merge, Merge2, Merge3, Merge4},
+    ok_or::{OkOr, OkOrElse},
     or::Or,
     or_else::OrElse,
+    timeout::Timeout,
     unwrap_or::UnwrapOr,
     unwrap_or_else::UnwrapOrElse,
 };
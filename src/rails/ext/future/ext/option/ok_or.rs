@@ -0,0 +1,108 @@
+use crate::rails::ext::future::ext::{MultiState, State};
+use core::future::Future;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+pub struct OkOr<Fut, E> {
+    state: State<Fut, E>,
+}
+
+impl<Fut, T, E> OkOr<Fut, E>
+where
+    Fut: Future<Output = Option<T>>,
+{
+    pub fn new(future: Fut, err: E) -> Self {
+        Self {
+            state: State::Waiting { future, func: err },
+        }
+    }
+}
+
+impl<Fut, T, E> Future for OkOr<Fut, E>
+where
+    Fut: Future<Output = Option<T>>,
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        match this.state {
+            State::Waiting { ref mut future, .. } => {
+                match unsafe { Pin::new_unchecked(future) }.poll(cx) {
+                    Poll::Ready(opt_value) => {
+                        // Move the error out of the state
+                        let err = match mem::replace(&mut this.state, State::Done) {
+                            State::Waiting { func, .. } => func,
+                            _ => unreachable!(),
+                        };
+                        Poll::Ready(opt_value.ok_or(err))
+                    }
+                    Poll::Pending => Poll::Pending,
+                }
+            }
+            State::Done => panic!("Future polled after completion"),
+        }
+    }
+}
+
+pub struct OkOrElse<Fut, FutFunc, FuncOutput> {
+    state: MultiState<Fut, FutFunc, FuncOutput>,
+}
+
+impl<Fut, FutFunc, FuncOutput, T, E> OkOrElse<Fut, FutFunc, FuncOutput>
+where
+    Fut: Future<Output = Option<T>>,
+    FutFunc: FnOnce() -> FuncOutput,
+    FuncOutput: Future<Output = E>,
+{
+    pub fn new(future: Fut, func: FutFunc) -> Self {
+        Self {
+            state: MultiState::Waiting { future, func },
+        }
+    }
+}
+
+impl<Fut, FutFunc, FuncOutput, T, E> Future for OkOrElse<Fut, FutFunc, FuncOutput>
+where
+    Fut: Future<Output = Option<T>>,
+    FutFunc: FnOnce() -> FuncOutput,
+    FuncOutput: Future<Output = E>,
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        match this.state {
+            MultiState::Waiting { ref mut future, .. } => {
+                match unsafe { Pin::new_unchecked(future) }.poll(cx) {
+                    Poll::Ready(opt_value) => match opt_value {
+                        Some(t) => {
+                            this.state = MultiState::Done;
+                            Poll::Ready(Ok(t))
+                        }
+                        None => {
+                            // Move the function out of the state
+                            let func = match mem::replace(&mut this.state, MultiState::Done) {
+                                MultiState::Waiting { func, .. } => func,
+                                _ => unreachable!(),
+                            };
+                            // Create the future producing the error
+                            let new_future = func();
+                            this.state = MultiState::Processing { future: new_future };
+                            cx.waker().wake_by_ref();
+                            Poll::Pending
+                        }
+                    },
+                    Poll::Pending => Poll::Pending,
+                }
+            }
+            MultiState::Processing { ref mut future } => {
+                unsafe { Pin::new_unchecked(future) }.poll(cx).map(Err)
+            }
+            MultiState::Done => panic!("Future polled after completion"),
+        }
+    }
+}
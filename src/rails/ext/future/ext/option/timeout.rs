@@ -0,0 +1,39 @@
+use crate::rails::ext::future::ext::Delay;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+pub struct Timeout<Fut> {
+    future: Fut,
+    delay: Delay,
+}
+
+impl<Fut, T> Timeout<Fut>
+where
+    Fut: Future<Output = Option<T>>,
+{
+    pub fn new(future: Fut, duration: Duration) -> Self {
+        Self {
+            future,
+            delay: Delay::new(duration),
+        }
+    }
+}
+
+impl<Fut, T> Future for Timeout<Fut>
+where
+    Fut: Future<Output = Option<T>>,
+{
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        if let Poll::Ready(opt_value) = unsafe { Pin::new_unchecked(&mut this.future) }.poll(cx) {
+            return Poll::Ready(opt_value);
+        }
+        unsafe { Pin::new_unchecked(&mut this.delay) }
+            .poll(cx)
+            .map(|_| None)
+    }
+}
@@ -0,0 +1,97 @@
+use crate::rails::ext::future::ext::State;
+use core::future::Future;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+pub struct Inspect<Fut, F> {
+    state: State<Fut, F>,
+}
+
+impl<Fut, F, T, E> Inspect<Fut, F>
+where
+    Fut: Future<Output = Result<T, E>>,
+    F: FnOnce(&T),
+{
+    pub fn new(future: Fut, func: F) -> Self {
+        Self {
+            state: State::Waiting { future, func },
+        }
+    }
+}
+
+impl<Fut, F, T, E> Future for Inspect<Fut, F>
+where
+    Fut: Future<Output = Result<T, E>>,
+    F: FnOnce(&T),
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        match this.state {
+            State::Waiting { ref mut future, .. } => {
+                match unsafe { Pin::new_unchecked(future) }.poll(cx) {
+                    Poll::Ready(res) => {
+                        let func = match mem::replace(&mut this.state, State::Done) {
+                            State::Waiting { func, .. } => func,
+                            _ => unreachable!(),
+                        };
+                        if let Ok(t) = &res {
+                            func(t);
+                        }
+                        Poll::Ready(res)
+                    }
+                    Poll::Pending => Poll::Pending,
+                }
+            }
+            State::Done => panic!("Future polled after completion"),
+        }
+    }
+}
+
+pub struct InspectErr<Fut, F> {
+    state: State<Fut, F>,
+}
+
+impl<Fut, F, T, E> InspectErr<Fut, F>
+where
+    Fut: Future<Output = Result<T, E>>,
+    F: FnOnce(&E),
+{
+    pub fn new(future: Fut, func: F) -> Self {
+        Self {
+            state: State::Waiting { future, func },
+        }
+    }
+}
+
+impl<Fut, F, T, E> Future for InspectErr<Fut, F>
+where
+    Fut: Future<Output = Result<T, E>>,
+    F: FnOnce(&E),
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        match this.state {
+            State::Waiting { ref mut future, .. } => {
+                match unsafe { Pin::new_unchecked(future) }.poll(cx) {
+                    Poll::Ready(res) => {
+                        let func = match mem::replace(&mut this.state, State::Done) {
+                            State::Waiting { func, .. } => func,
+                            _ => unreachable!(),
+                        };
+                        if let Err(e) = &res {
+                            func(e);
+                        }
+                        Poll::Ready(res)
+                    }
+                    Poll::Pending => Poll::Pending,
+                }
+            }
+            State::Done => panic!("Future polled after completion"),
+        }
+    }
+}
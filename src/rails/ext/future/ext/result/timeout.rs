@@ -0,0 +1,54 @@
+use crate::rails::ext::future::ext::Delay;
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+/// The error produced when a `FutureResult::timeout` elapses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Elapsed;
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "deadline has elapsed")
+    }
+}
+
+impl std::error::Error for Elapsed {}
+
+pub struct Timeout<Fut> {
+    future: Fut,
+    delay: Delay,
+}
+
+impl<Fut, T, E> Timeout<Fut>
+where
+    Fut: Future<Output = Result<T, E>>,
+    E: From<Elapsed>,
+{
+    pub fn new(future: Fut, duration: Duration) -> Self {
+        Self {
+            future,
+            delay: Delay::new(duration),
+        }
+    }
+}
+
+impl<Fut, T, E> Future for Timeout<Fut>
+where
+    Fut: Future<Output = Result<T, E>>,
+    E: From<Elapsed>,
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        if let Poll::Ready(res) = unsafe { Pin::new_unchecked(&mut this.future) }.poll(cx) {
+            return Poll::Ready(res);
+        }
+        unsafe { Pin::new_unchecked(&mut this.delay) }
+            .poll(cx)
+            .map(|_| Err(E::from(Elapsed)))
+    }
+}
@@ -0,0 +1,55 @@
+use crate::rails::ext::future::ext::MaybeDone;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+pub struct ZipOk<Fut1: Future, Fut2: Future> {
+    first: MaybeDone<Fut1>,
+    second: MaybeDone<Fut2>,
+}
+
+impl<Fut1, Fut2, T, U, E> ZipOk<Fut1, Fut2>
+where
+    Fut1: Future<Output = Result<T, E>>,
+    Fut2: Future<Output = Result<U, E>>,
+{
+    pub fn new(first: Fut1, second: Fut2) -> Self {
+        Self {
+            first: MaybeDone::Future(first),
+            second: MaybeDone::Future(second),
+        }
+    }
+}
+
+impl<Fut1, Fut2, T, U, E> Future for ZipOk<Fut1, Fut2>
+where
+    Fut1: Future<Output = Result<T, E>>,
+    Fut2: Future<Output = Result<U, E>>,
+{
+    type Output = Result<(T, U), E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut first = unsafe { Pin::new_unchecked(&mut this.first) };
+        let mut second = unsafe { Pin::new_unchecked(&mut this.second) };
+
+        let first_done = first.as_mut().poll_done(cx);
+        if first_done && matches!(*first, MaybeDone::Done(Err(_))) {
+            // Short-circuit, the pending future is dropped together with this one
+            return Poll::Ready(Err(first.take_output().unwrap().err().unwrap()));
+        }
+        let second_done = second.as_mut().poll_done(cx);
+        if second_done && matches!(*second, MaybeDone::Done(Err(_))) {
+            return Poll::Ready(Err(second.take_output().unwrap().err().unwrap()));
+        }
+
+        if first_done && second_done {
+            match (first.take_output(), second.take_output()) {
+                (Some(Ok(t)), Some(Ok(u))) => Poll::Ready(Ok((t, u))),
+                _ => unreachable!(),
+            }
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::rails::ext::future::ext::MaybeDone;
+use alloc::{boxed::Box, vec::Vec};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+pub struct TryJoinAll<Fut: Future> {
+    futures: Pin<Box<[MaybeDone<Fut>]>>,
+}
+
+impl<Fut, T, E> TryJoinAll<Fut>
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    pub fn new<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Fut>,
+    {
+        Self {
+            futures: Box::into_pin(
+                iter.into_iter()
+                    .map(MaybeDone::Future)
+                    .collect::<Box<[_]>>(),
+            ),
+        }
+    }
+}
+
+impl<Fut, T, E> Future for TryJoinAll<Fut>
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Output = Result<Vec<T>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let futures = unsafe { this.futures.as_mut().get_unchecked_mut() };
+
+        let mut all_done = true;
+        for future in futures.iter_mut() {
+            let mut future = unsafe { Pin::new_unchecked(future) };
+            if !future.as_mut().poll_done(cx) {
+                all_done = false;
+            } else if matches!(*future, MaybeDone::Done(Err(_))) {
+                // Short-circuit on the first error, the remaining futures are dropped
+                return Poll::Ready(Err(future.take_output().unwrap().err().unwrap()));
+            }
+        }
+
+        if all_done {
+            Poll::Ready(
+                futures
+                    .iter_mut()
+                    .map(|future| unsafe { Pin::new_unchecked(future) }.take_output().unwrap())
+                    .collect(),
+            )
+        } else {
+            Poll::Pending
+        }
+    }
+}
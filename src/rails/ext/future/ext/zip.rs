@@ -0,0 +1,45 @@
+use crate::rails::ext::future::ext::MaybeDone;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+pub struct Zip<Fut1: Future, Fut2: Future> {
+    first: MaybeDone<Fut1>,
+    second: MaybeDone<Fut2>,
+}
+
+impl<Fut1, Fut2> Zip<Fut1, Fut2>
+where
+    Fut1: Future,
+    Fut2: Future,
+{
+    pub fn new(first: Fut1, second: Fut2) -> Self {
+        Self {
+            first: MaybeDone::Future(first),
+            second: MaybeDone::Future(second),
+        }
+    }
+}
+
+impl<Fut1, Fut2> Future for Zip<Fut1, Fut2>
+where
+    Fut1: Future,
+    Fut2: Future,
+{
+    type Output = (Fut1::Output, Fut2::Output);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut first = unsafe { Pin::new_unchecked(&mut this.first) };
+        let mut second = unsafe { Pin::new_unchecked(&mut this.second) };
+
+        // Both futures are polled on every wake up so they make progress concurrently
+        let first_done = first.as_mut().poll_done(cx);
+        let second_done = second.as_mut().poll_done(cx);
+        if first_done && second_done {
+            Poll::Ready((first.take_output().unwrap(), second.take_output().unwrap()))
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use super::assert_future;
-use super::ext::{option, result};
+use super::ext::{option, result, Zip};
 use crate::rails::ext::future::private_utils::FutureContainer;
 use core::future::Future;
+use core::time::Duration;
 
 impl<T> IntoFutureOptional<T> for Option<T> {
     fn into_future(self) -> FutureContainer<Option<T>> {
@@ -217,6 +218,63 @@ pub trait FutureOptional<T>: Future<Output = Option<T>> {
         assert_future(option::UnwrapOrElse::new(self, f))
     }
 
+    /// Transforms this future's `Some(v)` output into `Ok(v)` and `None` into `Err(err)`.
+    ///
+    /// This function is similar to the `Option::ok_or` and bridges into the `FutureResult`
+    /// adapters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use gearbox::rails::ext::future::FutureOptional;
+    ///
+    /// let future_opt = async { Some(4) };
+    /// let res = future_opt.ok_or("missing");
+    /// assert_eq!(res.await, Ok(4));
+    ///
+    /// let future_opt = async { None::<u32> };
+    /// let res = future_opt.ok_or("missing");
+    /// assert_eq!(res.await, Err("missing"));
+    /// # });
+    /// ```
+    fn ok_or<E>(self, err: E) -> option::OkOr<Self, E>
+    where
+        Self: Sized,
+    {
+        assert_future(option::OkOr::new(self, err))
+    }
+
+    /// Transforms this future's `Some(v)` output into `Ok(v)`, otherwise calls the provided
+    /// function to produce the `Err` value.
+    ///
+    /// This function is similar to the `Option::ok_or_else`, the function is only called if the
+    /// future resolves to `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use gearbox::rails::ext::future::FutureOptional;
+    ///
+    /// let future_opt = async { Some(4) };
+    /// let res = future_opt.ok_or_else(|| async { "missing" });
+    /// assert_eq!(res.await, Ok(4));
+    ///
+    /// let future_opt = async { None::<u32> };
+    /// let res = future_opt.ok_or_else(|| async { "missing" });
+    /// assert_eq!(res.await, Err("missing"));
+    /// # });
+    /// ```
+    fn ok_or_else<E, F, F2>(self, f: F) -> option::OkOrElse<Self, F, F2>
+    where
+        F: FnOnce() -> F2,
+        F2: Future<Output = E>,
+        Self: Sized,
+    {
+        assert_future(option::OkOrElse::new(self, f))
+    }
+
     /// Merges this future with an optional value, producing a new future.
     ///
     /// This function takes an additional option and a function to combine the resolved value of the
@@ -364,6 +422,31 @@ pub trait FutureOptional<T>: Future<Output = Option<T>> {
     {
         assert_future(option::Merge4::new(self, op, res1, res2, res3, res4))
     }
+
+    /// Bounds this future by a timeout, resolving to `None` if it has not completed once the
+    /// duration elapsed.
+    ///
+    /// Inside a tokio runtime the timer is driven by tokio (with the `tokio` feature), otherwise a
+    /// helper thread is used so any executor works.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use core::time::Duration;
+    /// use gearbox::rails::ext::future::FutureOptional;
+    ///
+    /// let future_opt = async { Some(1) };
+    /// let res = future_opt.timeout(Duration::from_secs(1));
+    /// assert_eq!(res.await, Some(1));
+    /// # });
+    /// ```
+    fn timeout(self, duration: Duration) -> option::Timeout<Self>
+    where
+        Self: Sized,
+    {
+        assert_future(option::Timeout::new(self, duration))
+    }
 }
 
 #[cfg(test)]
@@ -719,6 +802,128 @@ pub mod tests_future_optional {
 
         assert_eq!(res.await, None);
     }
+
+    #[tokio::test]
+    async fn test_ok_or() {
+        use super::FutureOptional;
+        use crate::rails::ext::future::FutureResult;
+
+        let future_opt = async { Some(1) };
+        let res = future_opt.ok_or(0).map(|t| async move { t + 1 });
+        assert_eq!(res.await, Ok(2));
+
+        let future_opt = async { None::<u32> };
+        let res = future_opt.ok_or(0).map(|t| async move { t + 1 });
+        assert_eq!(res.await, Err(0));
+    }
+
+    #[tokio::test]
+    async fn test_ok_or_else() {
+        use super::FutureOptional;
+
+        let mut called = false;
+        let future_opt = async { Some(1) };
+        let res = future_opt.ok_or_else(|| {
+            called = true;
+            async { 0 }
+        });
+        assert_eq!(res.await, Ok(1));
+        assert!(!called);
+
+        let future_opt = async { None::<u32> };
+        let res = future_opt.ok_or_else(|| async { 0 });
+        assert_eq!(res.await, Err(0));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_optional() {
+        use super::FutureOptional;
+        use core::time::Duration;
+
+        let future_opt = async { Some(1) };
+        let res = future_opt.timeout(Duration::from_secs(5));
+        assert_eq!(res.await, Some(1));
+
+        let future_opt = futures::future::pending::<Option<u32>>();
+        let res = future_opt.timeout(Duration::from_millis(20));
+        assert_eq!(res.await, None);
+    }
+}
+
+impl<U> FutureZip for U where U: Future {}
+
+/// An extension trait for all `Future`s that provides adapters independent of the output type.
+pub trait FutureZip: Future {
+    /// Awaits this future together with another future, producing a tuple of both outputs.
+    ///
+    /// Both futures are driven concurrently, this is a shorthand for
+    /// `merge(other, |a, b| async { (a, b) })` without the combining closure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use gearbox::rails::ext::future::FutureZip;
+    ///
+    /// let x = async { 1 };
+    /// let y = async { Some(2) };
+    ///
+    /// let res = x.zip(y);
+    /// assert_eq!(res.await, (1, Some(2)));
+    /// # });
+    /// ```
+    fn zip<Fut>(self, other: Fut) -> Zip<Self, Fut>
+    where
+        Fut: Future,
+        Self: Sized,
+    {
+        assert_future(Zip::new(self, other))
+    }
+}
+
+#[cfg(test)]
+mod tests_future_zip {
+    use super::FutureZip;
+    use core::future::Future;
+    use core::pin::Pin;
+    use core::task::{Context, Poll};
+
+    /// Resolves to its value after being polled `polls` times.
+    struct Delayed<T> {
+        polls: usize,
+        value: Option<T>,
+    }
+
+    impl<T: Unpin> Future for Delayed<T> {
+        type Output = T;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+            if self.polls == 0 {
+                Poll::Ready(self.value.take().unwrap())
+            } else {
+                self.polls -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_zip() {
+        let x = async { 1 };
+        let y = async { Some(2) };
+        assert_eq!(x.zip(y).await, (1, Some(2)));
+
+        let x = Delayed {
+            polls: 3,
+            value: Some("x"),
+        };
+        let y = Delayed {
+            polls: 1,
+            value: Some(2),
+        };
+        assert_eq!(x.zip(y).await, ("x", 2));
+    }
 }
 
 impl<T, E> IntoFutureResult<T, E> for Result<T, E> {
@@ -907,6 +1112,59 @@ pub trait FutureResult<T, E>: Future<Output = Result<T, E>> {
         assert_future(result::UnwrapOrElse::new(self, f))
     }
 
+    /// Calls the provided closure with a reference to the `Ok` value, passing the result through
+    /// unchanged.
+    ///
+    /// This function is similar to the `Result::inspect` and is useful to log intermediate values
+    /// without breaking a chain of adapters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use gearbox::rails::ext::future::FutureResult;
+    ///
+    /// let mut seen = None;
+    /// let future_res = async { Ok::<_, ()>(4) };
+    /// let res = future_res.inspect(|t| seen = Some(*t)).await;
+    /// assert_eq!(res, Ok(4));
+    /// assert_eq!(seen, Some(4));
+    /// # });
+    /// ```
+    fn inspect<F>(self, f: F) -> result::Inspect<Self, F>
+    where
+        F: FnOnce(&T),
+        Self: Sized,
+    {
+        assert_future(result::Inspect::new(self, f))
+    }
+
+    /// Calls the provided closure with a reference to the `Err` value, passing the result through
+    /// unchanged.
+    ///
+    /// This function is similar to the `Result::inspect_err`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use gearbox::rails::ext::future::FutureResult;
+    ///
+    /// let mut seen = None;
+    /// let future_res = async { Err::<u32, _>("failed") };
+    /// let res = future_res.inspect_err(|e| seen = Some(*e)).await;
+    /// assert_eq!(res, Err("failed"));
+    /// assert_eq!(seen, Some("failed"));
+    /// # });
+    /// ```
+    fn inspect_err<F>(self, f: F) -> result::InspectErr<Self, F>
+    where
+        F: FnOnce(&E),
+        Self: Sized,
+    {
+        assert_future(result::InspectErr::new(self, f))
+    }
+
     /// Merges this future with a result value, producing a new future.
     ///
     /// This function takes an additional result and a function to combine the resolved value of the
@@ -1054,6 +1312,59 @@ pub trait FutureResult<T, E>: Future<Output = Result<T, E>> {
     {
         assert_future(result::Merge4::new(self, op, res1, res2, res3, res4))
     }
+
+    /// Awaits this future together with another result future, producing a tuple of both `Ok`
+    /// values.
+    ///
+    /// Both futures are driven concurrently. The first `Err` is returned as soon as it resolves,
+    /// without waiting for the other future.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use gearbox::rails::ext::future::FutureResult;
+    ///
+    /// let x = async { Ok::<_, ()>(1) };
+    /// let y = async { Ok::<_, ()>("two") };
+    ///
+    /// let res = x.zip_ok(y);
+    /// assert_eq!(res.await, Ok((1, "two")));
+    /// # });
+    /// ```
+    fn zip_ok<U, Fut>(self, other: Fut) -> result::ZipOk<Self, Fut>
+    where
+        Fut: Future<Output = Result<U, E>>,
+        Self: Sized,
+    {
+        assert_future(result::ZipOk::new(self, other))
+    }
+
+    /// Bounds this future by a timeout, resolving to `Err(Elapsed.into())` if it has not
+    /// completed once the duration elapsed.
+    ///
+    /// Inside a tokio runtime the timer is driven by tokio (with the `tokio` feature), otherwise a
+    /// helper thread is used so any executor works.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use core::time::Duration;
+    /// use gearbox::rails::ext::future::{Elapsed, FutureResult};
+    ///
+    /// let future_res = futures::future::pending::<Result<u32, Elapsed>>();
+    /// let res = future_res.timeout(Duration::from_millis(10));
+    /// assert_eq!(res.await, Err(Elapsed));
+    /// # });
+    /// ```
+    fn timeout(self, duration: Duration) -> result::Timeout<Self>
+    where
+        E: From<result::Elapsed>,
+        Self: Sized,
+    {
+        assert_future(result::Timeout::new(self, duration))
+    }
 }
 #[cfg(test)]
 mod tests_future_result {
@@ -1124,6 +1435,42 @@ mod tests_future_result {
         assert_eq!(final_res, 10);
     }
 
+    #[tokio::test]
+    async fn test_inspect_result() {
+        let mut seen = Vec::new();
+        let future_res = async { Ok::<_, ()>(1) };
+        let res = future_res
+            .inspect(|t| seen.push(*t))
+            .map(|t| async move { t + 1 })
+            .await;
+        assert_eq!(res, Ok(2));
+        assert_eq!(seen, vec![1]);
+
+        let mut called = false;
+        let future_res = async { Err::<u32, _>(()) };
+        let res = future_res.inspect(|_| called = true).await;
+        assert_eq!(res, Err(()));
+        assert!(!called);
+    }
+
+    #[tokio::test]
+    async fn test_inspect_err_result() {
+        let mut seen = Vec::new();
+        let future_res = async { Err::<u32, _>(1) };
+        let res = future_res
+            .inspect_err(|e| seen.push(*e))
+            .map_err(|e| async move { e + 1 })
+            .await;
+        assert_eq!(res, Err(2));
+        assert_eq!(seen, vec![1]);
+
+        let mut called = false;
+        let future_res = async { Ok::<_, u32>(1) };
+        let res = future_res.inspect_err(|_| called = true).await;
+        assert_eq!(res, Ok(1));
+        assert!(!called);
+    }
+
     #[tokio::test]
     async fn test_merge_result() {
         async fn func_xy(x: u32, y: u32) -> Result<u32, ()> {
@@ -1185,4 +1532,51 @@ mod tests_future_result {
         });
         assert_eq!(res.await, Ok(15));
     }
+
+    #[tokio::test]
+    async fn test_zip_ok_result() {
+        let x = async { Ok::<_, ()>(1) };
+        let y = async { Ok::<_, ()>("two") };
+        assert_eq!(x.zip_ok(y).await, Ok((1, "two")));
+
+        let x = async { Err::<u32, _>(1) };
+        let y = async { Ok::<u32, _>(2) };
+        assert_eq!(x.zip_ok(y).await, Err(1));
+
+        let x = async { Ok::<u32, _>(1) };
+        let y = async { Err::<u32, _>(2) };
+        assert_eq!(x.zip_ok(y).await, Err(2));
+
+        // The error short-circuits without awaiting the pending future
+        let x = futures::future::pending::<Result<u32, u32>>();
+        let y = async { Err::<u32, _>(3) };
+        assert_eq!(x.zip_ok(y).await, Err(3));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_result() {
+        use crate::rails::ext::future::Elapsed;
+        use core::time::Duration;
+
+        #[derive(Debug, PartialEq)]
+        enum Error {
+            Timeout,
+        }
+        impl From<Elapsed> for Error {
+            fn from(_: Elapsed) -> Self {
+                Error::Timeout
+            }
+        }
+
+        let future_res = async { Ok::<_, Error>(1) };
+        let res = future_res.timeout(Duration::from_secs(5));
+        assert_eq!(res.await, Ok(1));
+
+        let future_res = async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok::<_, Error>(1)
+        };
+        let res = future_res.timeout(Duration::from_millis(20));
+        assert_eq!(res.await, Err(Error::Timeout));
+    }
 }
@@ -0,0 +1,60 @@
+//! Joining a dynamic number of futures.
+//!
+//! # Examples
+//!
+//! ```
+//! # futures::executor::block_on(async {
+//! use gearbox::rails::ext::future::try_join_all;
+//!
+//! let requests = (1..=3).map(|id| async move { Ok::<_, ()>(id * 10) });
+//! assert_eq!(try_join_all(requests).await, Ok(vec![10, 20, 30]));
+//! # });
+//! ```
+use alloc::vec::Vec;
+use core::future::Future;
+
+/// Drives all futures concurrently and resolves to their `Ok` values in input order, or to the
+/// first `Err` as soon as it occurs.
+///
+/// With the `futures` feature this delegates to `futures::future::try_join_all`.
+pub fn try_join_all<I, Fut, T, E>(iter: I) -> impl Future<Output = Result<Vec<T>, E>>
+where
+    I: IntoIterator<Item = Fut>,
+    Fut: Future<Output = Result<T, E>>,
+{
+    #[cfg(feature = "futures")]
+    return futures::future::try_join_all(iter);
+    #[cfg(not(feature = "futures"))]
+    return super::ext::TryJoinAll::new(iter);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+
+    #[tokio::test]
+    async fn test_try_join_all_in_order() {
+        let futures = (1..=4u64).map(|id| async move {
+            tokio::time::sleep(Duration::from_millis(20 - id * 5)).await;
+            Ok::<_, ()>(id)
+        });
+        assert_eq!(try_join_all(futures).await, Ok(vec![1, 2, 3, 4]));
+
+        let empty = Vec::<core::future::Ready<Result<u32, ()>>>::new();
+        assert_eq!(try_join_all(empty).await, Ok(vec![]));
+    }
+
+    #[tokio::test]
+    async fn test_try_join_all_first_error() {
+        let futures = (1..=4u64).map(|id| async move {
+            tokio::time::sleep(Duration::from_millis(id * 5)).await;
+            if id % 2 == 0 {
+                Err(id)
+            } else {
+                Ok(id)
+            }
+        });
+        assert_eq!(try_join_all(futures).await, Err(2));
+    }
+}
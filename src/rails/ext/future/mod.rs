@@ -164,6 +164,52 @@
 //! # });
 //! ```
 //!
+//! #### `ok_or`
+//!
+//! Transforms this future's `Some(v)` output into `Ok(v)` and `None` into `Err(err)`.
+//!
+//! This function is similar to the `Option::ok_or` and bridges into the `FutureResult` adapters.
+//!
+//! ##### Example
+//!
+//! ```rust
+//! # #[cfg(feature = "rails-ext")]
+//! # futures::executor::block_on(async {
+//! use gearbox::rails::ext::future::FutureOptional;
+//!
+//! let future_opt = async { Some(4) };
+//! let res = future_opt.ok_or("missing");
+//! assert_eq!(res.await, Ok(4));
+//!
+//! let future_opt = async { None::<u32> };
+//! let res = future_opt.ok_or("missing");
+//! assert_eq!(res.await, Err("missing"));
+//! # });
+//! ```
+//!
+//! #### `ok_or_else`
+//!
+//! Transforms this future's `Some(v)` output into `Ok(v)`, otherwise calls the provided function to produce the `Err` value.
+//!
+//! This function is similar to the `Option::ok_or_else` where it will only call the provided function if the future resolves to `None`.
+//!
+//! ##### Example
+//!
+//! ```rust
+//! # #[cfg(feature = "rails-ext")]
+//! # futures::executor::block_on(async {
+//! use gearbox::rails::ext::future::FutureOptional;
+//!
+//! let future_opt = async { Some(4) };
+//! let res = future_opt.ok_or_else(|| async { "missing" });
+//! assert_eq!(res.await, Ok(4));
+//!
+//! let future_opt = async { None::<u32> };
+//! let res = future_opt.ok_or_else(|| async { "missing" });
+//! assert_eq!(res.await, Err("missing"));
+//! # });
+//! ```
+//!
 //! #### `merge`
 //!
 //! Merges this future with an optional value, producing a new future.
@@ -270,6 +316,24 @@
 //! # });
 //! ```
 //!
+//! #### `timeout`
+//!
+//! Bounds this future by a timeout, resolving to `None` if it has not completed in time.
+//!
+//! ##### Example
+//!
+//! ```rust
+//! # #[cfg(feature = "rails-ext")]
+//! # futures::executor::block_on(async {
+//! use core::time::Duration;
+//! use gearbox::rails::ext::future::FutureOptional;
+//!
+//! let future_opt = futures::future::pending::<Option<u32>>();
+//! let res = future_opt.timeout(Duration::from_millis(10));
+//! assert_eq!(res.await, None);
+//! # });
+//! ```
+//!
 //! ### FutureResult
 //!
 //! An extension trait for `Future`s that yield `Result<T, E>` that provides a variety of convenient adapters.
@@ -409,6 +473,48 @@
 //! # });
 //! ```
 //!
+//! #### `inspect`
+//!
+//! Calls the provided closure with a reference to the `Ok` value, passing the result through unchanged.
+//!
+//! This function is similar to the `Result::inspect` and is useful for logging intermediate values of a chain.
+//!
+//! ##### Example
+//!
+//! ```rust
+//! # #[cfg(feature = "rails-ext")]
+//! # futures::executor::block_on(async {
+//! use gearbox::rails::ext::future::FutureResult;
+//!
+//! let future_res = async { Ok::<_, ()>(1) };
+//! let res = future_res
+//!     .inspect(|t| println!("got {}", t))
+//!     .map(|t| async move { t + 1 });
+//! assert_eq!(res.await, Ok(2));
+//! # });
+//! ```
+//!
+//! #### `inspect_err`
+//!
+//! Calls the provided closure with a reference to the `Err` value, passing the result through unchanged.
+//!
+//! This function is similar to the `Result::inspect_err`.
+//!
+//! ##### Example
+//!
+//! ```rust
+//! # #[cfg(feature = "rails-ext")]
+//! # futures::executor::block_on(async {
+//! use gearbox::rails::ext::future::FutureResult;
+//!
+//! let future_res = async { Err::<u32, _>(1) };
+//! let res = future_res
+//!     .inspect_err(|e| println!("failed with {}", e))
+//!     .map_err(|e| async move { e + 1 });
+//! assert_eq!(res.await, Err(2));
+//! # });
+//! ```
+//!
 //! #### `merge`
 //!
 //! Merges this future with a result value, producing a new future.
@@ -514,16 +620,84 @@
 //! assert_eq!(res.await, Ok(15));
 //! # });
 //! ```
+//!
+//! #### `zip_ok`
+//!
+//! Awaits this future together with another result future, producing a tuple of both `Ok` values.
+//!
+//! Both futures are driven concurrently and the first `Err` is returned as soon as it resolves.
+//!
+//! ##### Example
+//!
+//! ```rust
+//! # #[cfg(feature = "rails-ext")]
+//! # futures::executor::block_on(async {
+//! use gearbox::rails::ext::future::FutureResult;
+//!
+//! let x = async { Ok::<_, ()>(1) };
+//! let y = async { Ok::<_, ()>(2) };
+//!
+//! let res = x.zip_ok(y);
+//! assert_eq!(res.await, Ok((1, 2)));
+//! # });
+//! ```
+//!
+//! #### `timeout`
+//!
+//! Bounds this future by a timeout, resolving to `Err` built from `Elapsed` if it has not completed
+//! in time.
+//!
+//! ##### Example
+//!
+//! ```rust
+//! # #[cfg(feature = "rails-ext")]
+//! # futures::executor::block_on(async {
+//! use core::time::Duration;
+//! use gearbox::rails::ext::future::{Elapsed, FutureResult};
+//!
+//! let future_res = async { Ok::<_, Elapsed>(4) };
+//! let res = future_res.timeout(Duration::from_secs(1));
+//! assert_eq!(res.await, Ok(4));
+//! # });
+//! ```
+//!
+//! ### FutureZip
+//!
+//! An extension trait for all `Future`s that provides adapters independent of the output type.
+//!
+//! #### `zip`
+//!
+//! Awaits this future together with another future, producing a tuple of both outputs.
+//!
+//! ##### Example
+//!
+//! ```rust
+//! # #[cfg(feature = "rails-ext")]
+//! # futures::executor::block_on(async {
+//! use gearbox::rails::ext::future::FutureZip;
+//!
+//! let x = async { 1 };
+//! let y = async { 2 };
+//!
+//! let res = x.zip(y);
+//! assert_eq!(res.await, (1, 2));
+//! # });
+//! ```
 
 pub mod ext;
 pub mod future_ext;
+pub mod join;
+pub mod retry;
 
 pub(crate) mod private_utils;
 
 use alloc::boxed::Box;
 use core::{future::Future, pin::Pin};
 
+pub use ext::result::Elapsed;
 pub use future_ext::*;
+pub use join::try_join_all;
+pub use retry::{retry, retry_if, Backoff, RetryPolicy};
 
 pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
@@ -0,0 +1,311 @@
+//! Retrying fallible futures.
+//!
+//! `retry` calls a factory for a fresh future on every attempt and waits between attempts as
+//! described by a `RetryPolicy`. If every attempt fails the last `Err` is returned. `retry_if`
+//! decides with a predicate which outcomes are attempted again, e.g. an HTTP response with a
+//! transient status code.
+//!
+//! # Examples
+//!
+//! ```
+//! # futures::executor::block_on(async {
+//! use core::sync::atomic::{AtomicU32, Ordering};
+//! use core::time::Duration;
+//! use gearbox::rails::ext::future::{retry, Backoff, RetryPolicy};
+//!
+//! let calls = AtomicU32::new(0);
+//! let policy = RetryPolicy::new(3)
+//!     .backoff(Backoff::exponential(Duration::from_millis(1), Duration::from_millis(5)));
+//!
+//! let res = retry(
+//!     || async {
+//!         match calls.fetch_add(1, Ordering::SeqCst) {
+//!             0 => Err("unavailable"),
+//!             n => Ok(n),
+//!         }
+//!     },
+//!     policy,
+//! );
+//! assert_eq!(res.await, Ok(1));
+//! # });
+//! ```
+use super::ext::Delay;
+use core::future::Future;
+use core::time::Duration;
+#[cfg(feature = "dep_serde")]
+use serde_derive::{Deserialize, Serialize};
+
+/// The delay strategy applied between two attempts.
+#[cfg_attr(feature = "dep_serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Backoff {
+    /// Waits the same amount of time before every retry.
+    Fixed(Duration),
+    /// Doubles the delay for every retry, starting at `initial` and never exceeding `max`. With
+    /// `jitter` enabled the delay is randomized between half and the full computed delay.
+    Exponential {
+        initial: Duration,
+        max: Duration,
+        jitter: bool,
+    },
+}
+
+impl Backoff {
+    /// Creates an exponential backoff with jitter enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - The delay before the first retry.
+    /// * `max` - The upper bound for any delay.
+    pub fn exponential(initial: Duration, max: Duration) -> Self {
+        Backoff::Exponential {
+            initial,
+            max,
+            jitter: true,
+        }
+    }
+
+    /// Returns the delay to wait after the given (1 based) attempt failed.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        match self {
+            Backoff::Fixed(delay) => *delay,
+            Backoff::Exponential {
+                initial,
+                max,
+                jitter,
+            } => {
+                let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+                let delay = initial.saturating_mul(factor).min(*max);
+                if *jitter {
+                    delay / 2 + delay.mul_f64(random_unit() / 2.0)
+                } else {
+                    delay
+                }
+            }
+        }
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Backoff::exponential(Duration::from_millis(100), Duration::from_secs(10))
+    }
+}
+
+/// Describes how often a future is attempted and how long to wait between attempts.
+///
+/// The default policy makes up to 3 attempts with an exponential backoff (100ms up to 10s, with
+/// jitter).
+#[cfg_attr(feature = "dep_serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    backoff: Backoff,
+}
+
+impl RetryPolicy {
+    /// Creates the default policy with the given maximum number of attempts, including the first
+    /// one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::rails::ext::future::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::new(5);
+    /// assert_eq!(policy.max_attempts(), 5);
+    /// ```
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a policy that never retries.
+    pub fn never() -> Self {
+        Self {
+            max_attempts: 1,
+            backoff: Backoff::Fixed(Duration::ZERO),
+        }
+    }
+
+    /// Sets the backoff applied between attempts.
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Returns the maximum number of attempts, including the first one.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the delay to wait after the given (1 based) attempt failed.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.backoff.delay(attempt)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: Backoff::default(),
+        }
+    }
+}
+
+/// Runs the future produced by `factory` until it resolves to `Ok` or the attempts of `policy`
+/// are exhausted, in which case the last `Err` is returned.
+pub fn retry<F, Fut, T, E>(factory: F, policy: RetryPolicy) -> impl Future<Output = Result<T, E>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_if(factory, policy, |_, outcome| outcome.is_err())
+}
+
+/// Runs the future produced by `factory` again as long as `should_retry`, called with the
+/// (1 based) attempt and its output, returns `true` and the attempts of `policy` are not
+/// exhausted. Returns the output of the last attempt.
+pub async fn retry_if<F, Fut, P>(
+    mut factory: F,
+    policy: RetryPolicy,
+    mut should_retry: P,
+) -> Fut::Output
+where
+    F: FnMut() -> Fut,
+    Fut: Future,
+    P: FnMut(u32, &Fut::Output) -> bool,
+{
+    let mut attempt = 1;
+    loop {
+        let outcome = factory().await;
+        if attempt >= policy.max_attempts || !should_retry(attempt, &outcome) {
+            return outcome;
+        }
+        Delay::new(policy.delay(attempt)).await;
+        attempt += 1;
+    }
+}
+
+/// Returns a random number in `[0, 1)` from the operating system's random source. If the source
+/// is unavailable the jitter collapses to the middle of its range.
+fn random_unit() -> f64 {
+    let mut bytes = [0u8; 8];
+    match getrandom::getrandom(&mut bytes) {
+        Ok(()) => (u64::from_le_bytes(bytes) >> 11) as f64 / (1u64 << 53) as f64,
+        Err(_) => 0.5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn test_fixed_backoff() {
+        let backoff = Backoff::Fixed(Duration::from_millis(250));
+        assert_eq!(backoff.delay(1), Duration::from_millis(250));
+        assert_eq!(backoff.delay(5), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_exponential_backoff() {
+        let backoff = Backoff::Exponential {
+            initial: Duration::from_millis(100),
+            max: Duration::from_millis(500),
+            jitter: false,
+        };
+        assert_eq!(backoff.delay(1), Duration::from_millis(100));
+        assert_eq!(backoff.delay(2), Duration::from_millis(200));
+        assert_eq!(backoff.delay(3), Duration::from_millis(400));
+        assert_eq!(backoff.delay(4), Duration::from_millis(500));
+        assert_eq!(backoff.delay(64), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_exponential_backoff_jitter() {
+        let backoff = Backoff::exponential(Duration::from_millis(100), Duration::from_secs(1));
+        for attempt in 1..6 {
+            let delay = backoff.delay(attempt);
+            let upper =
+                Duration::from_millis(100 * 2u64.pow(attempt - 1)).min(Duration::from_secs(1));
+            assert!(delay >= upper / 2 && delay <= upper);
+        }
+    }
+
+    #[test]
+    fn test_policy() {
+        let policy = RetryPolicy::new(0).backoff(Backoff::Fixed(Duration::from_millis(20)));
+        assert_eq!(policy.max_attempts(), 1);
+        assert_eq!(policy.delay(3), Duration::from_millis(20));
+        assert_eq!(RetryPolicy::never().max_attempts(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_succeeds_on_third_attempt() {
+        let calls = AtomicU32::new(0);
+        let res = retry(
+            || async {
+                match calls.fetch_add(1, Ordering::SeqCst) + 1 {
+                    attempt if attempt < 3 => Err(attempt),
+                    attempt => Ok(attempt),
+                }
+            },
+            RetryPolicy::new(5).backoff(Backoff::Fixed(Duration::from_millis(1))),
+        );
+        assert_eq!(res.await, Ok(3));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_returns_last_error() {
+        let calls = AtomicU32::new(0);
+        let res = retry(
+            || async { Err::<(), _>(calls.fetch_add(1, Ordering::SeqCst) + 1) },
+            RetryPolicy::new(3).backoff(Backoff::Fixed(Duration::from_millis(1))),
+        );
+        assert_eq!(res.await, Err(3));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_if() {
+        // Retries successful outcomes below 2, and never errors.
+        let calls = AtomicU32::new(0);
+        let res = retry_if(
+            || async { Ok::<_, ()>(calls.fetch_add(1, Ordering::SeqCst)) },
+            RetryPolicy::new(5).backoff(Backoff::Fixed(Duration::from_millis(1))),
+            |attempt, outcome| {
+                assert_eq!(outcome.map(|t| t + 1), Ok(attempt));
+                outcome.is_ok_and(|t| t < 2)
+            },
+        );
+        assert_eq!(res.await, Ok(2));
+
+        let res = retry_if(
+            || async { Err::<(), _>("fatal") },
+            RetryPolicy::new(5),
+            |_, outcome| outcome.is_ok(),
+        );
+        assert_eq!(res.await, Err("fatal"));
+    }
+
+    #[test]
+    fn test_concurrent_retries_without_runtime() {
+        // Outside a tokio runtime all backoffs are served by the one shared timer thread.
+        let calls = AtomicU32::new(0);
+        let retries = (0..32).map(|_| {
+            retry(
+                || async { Err::<(), _>(calls.fetch_add(1, Ordering::SeqCst)) },
+                RetryPolicy::new(3).backoff(Backoff::Fixed(Duration::from_millis(5))),
+            )
+        });
+        let results = futures::executor::block_on(futures::future::join_all(retries));
+        assert!(results.iter().all(Result::is_err));
+        assert_eq!(calls.load(Ordering::SeqCst), 96);
+    }
+}
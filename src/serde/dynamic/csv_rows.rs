@@ -0,0 +1,504 @@
+//! Row mapping between serde types and CSV documents.
+//!
+//! The `csv` crate serializes and deserializes a single record at a time. This module maps a whole
+//! document onto a type: a sequence type becomes one row per element, any other type becomes a
+//! single row. Headers are always written on encode and mapped to field names on decode.
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde::ser::{self, Impossible, Serialize};
+
+#[derive(Debug)]
+enum RowError {
+    /// Raised by the row serializer when the top level value is not a sequence, signalling that it
+    /// should be written as a single row instead.
+    NotSequence,
+    Csv(csv::Error),
+}
+
+impl Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RowError::NotSequence => write!(f, "Top level value is not a sequence"),
+            RowError::Csv(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for RowError {}
+
+impl ser::Error for RowError {
+    fn custom<T: Display>(msg: T) -> Self {
+        RowError::Csv(<csv::Error as ser::Error>::custom(msg))
+    }
+}
+
+impl de::Error for RowError {
+    fn custom<T: Display>(msg: T) -> Self {
+        RowError::Csv(csv::Error::from(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            msg.to_string(),
+        )))
+    }
+}
+
+impl From<csv::Error> for RowError {
+    fn from(e: csv::Error) -> Self {
+        RowError::Csv(e)
+    }
+}
+
+impl From<RowError> for csv::Error {
+    fn from(e: RowError) -> Self {
+        match e {
+            RowError::NotSequence => <csv::Error as ser::Error>::custom(e),
+            RowError::Csv(e) => e,
+        }
+    }
+}
+
+pub(super) fn to_writer<W, T>(writer: W, value: &T) -> Result<(), csv::Error>
+where
+    W: std::io::Write,
+    T: Serialize + ?Sized,
+{
+    let mut writer = csv::Writer::from_writer(writer);
+    match value.serialize(RowsSerializer {
+        writer: &mut writer,
+    }) {
+        Ok(()) => {}
+        Err(RowError::NotSequence) => writer.serialize(value)?,
+        Err(RowError::Csv(e)) => return Err(e),
+    }
+    writer.flush().map_err(csv::Error::from)
+}
+
+pub(super) fn to_vec<T>(value: &T) -> Result<Vec<u8>, csv::Error>
+where
+    T: Serialize + ?Sized,
+{
+    let mut buf = Vec::new();
+    to_writer(&mut buf, value)?;
+    Ok(buf)
+}
+
+pub(super) fn from_reader<R, T>(reader: R) -> Result<T, csv::Error>
+where
+    R: std::io::Read,
+    T: DeserializeOwned,
+{
+    let mut reader = csv::Reader::from_reader(reader);
+    let headers = reader.headers()?.clone();
+    let records = reader
+        .records()
+        .collect::<Result<Vec<csv::StringRecord>, csv::Error>>()?;
+    T::deserialize(RowsDeserializer {
+        headers: &headers,
+        records: &records,
+    })
+    .map_err(csv::Error::from)
+}
+
+pub(super) fn from_slice<T>(input: &[u8]) -> Result<T, csv::Error>
+where
+    T: DeserializeOwned,
+{
+    from_reader(input)
+}
+
+struct RowsSerializer<'a, W: std::io::Write> {
+    writer: &'a mut csv::Writer<W>,
+}
+
+macro_rules! not_sequence {
+    ($($method:ident($($arg:ty),*)),* $(,)?) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<Self::Ok, Self::Error> {
+                Err(RowError::NotSequence)
+            }
+        )*
+    };
+}
+
+impl<'a, W: std::io::Write> ser::Serializer for RowsSerializer<'a, W> {
+    type Ok = ();
+    type Error = RowError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Impossible<(), RowError>;
+    type SerializeMap = Impossible<(), RowError>;
+    type SerializeStruct = Impossible<(), RowError>;
+    type SerializeStructVariant = Impossible<(), RowError>;
+
+    not_sequence!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_none(),
+        serialize_unit(),
+        serialize_unit_struct(&'static str),
+        serialize_unit_variant(&'static str, u32, &'static str),
+    );
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _: &T) -> Result<Self::Ok, Self::Error> {
+        Err(RowError::NotSequence)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(RowError::NotSequence)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(RowError::NotSequence)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(RowError::NotSequence)
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(RowError::NotSequence)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(RowError::NotSequence)
+    }
+}
+
+impl<'a, W: std::io::Write> ser::SerializeSeq for RowsSerializer<'a, W> {
+    type Ok = ();
+    type Error = RowError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), RowError> {
+        self.writer.serialize(value).map_err(RowError::from)
+    }
+
+    fn end(self) -> Result<(), RowError> {
+        Ok(())
+    }
+}
+
+impl<'a, W: std::io::Write> ser::SerializeTuple for RowsSerializer<'a, W> {
+    type Ok = ();
+    type Error = RowError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), RowError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), RowError> {
+        Ok(())
+    }
+}
+
+impl<'a, W: std::io::Write> ser::SerializeTupleStruct for RowsSerializer<'a, W> {
+    type Ok = ();
+    type Error = RowError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), RowError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), RowError> {
+        Ok(())
+    }
+}
+
+/// Deserializes the full document, sequences receive every row while any other type receives the
+/// first row.
+struct RowsDeserializer<'de> {
+    headers: &'de csv::StringRecord,
+    records: &'de [csv::StringRecord],
+}
+
+impl<'de> RowsDeserializer<'de> {
+    fn first(&self) -> Result<RowDeserializer<'de>, RowError> {
+        self.records
+            .first()
+            .map(|record| RowDeserializer {
+                headers: self.headers,
+                record,
+            })
+            .ok_or_else(|| {
+                <RowError as de::Error>::custom("CSV document does not contain any rows")
+            })
+    }
+}
+
+impl<'de> de::Deserializer<'de> for RowsDeserializer<'de> {
+    type Error = RowError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(RowsAccess {
+            headers: self.headers,
+            records: self.records.iter(),
+        })
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.records.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.first()?.deserialize_map(visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.first()?.deserialize_map(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct enum identifier ignored_any
+    }
+}
+
+struct RowsAccess<'de> {
+    headers: &'de csv::StringRecord,
+    records: core::slice::Iter<'de, csv::StringRecord>,
+}
+
+impl<'de> de::SeqAccess<'de> for RowsAccess<'de> {
+    type Error = RowError;
+
+    fn next_element_seed<S: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, Self::Error> {
+        self.records
+            .next()
+            .map(|record| {
+                seed.deserialize(RowDeserializer {
+                    headers: self.headers,
+                    record,
+                })
+            })
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.records.len())
+    }
+}
+
+/// Deserializes a single row as a map of header to field.
+struct RowDeserializer<'de> {
+    headers: &'de csv::StringRecord,
+    record: &'de csv::StringRecord,
+}
+
+impl<'de> de::Deserializer<'de> for RowDeserializer<'de> {
+    type Error = RowError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(RowAccess {
+            fields: self.headers.iter().zip(self.record.iter()),
+            value: None,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct RowAccess<'de, I: Iterator<Item = (&'de str, &'de str)>> {
+    fields: I,
+    value: Option<&'de str>,
+}
+
+impl<'de, I: Iterator<Item = (&'de str, &'de str)>> de::MapAccess<'de> for RowAccess<'de, I> {
+    type Error = RowError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.fields.next() {
+            Some((header, value)) => {
+                self.value = Some(value);
+                seed.deserialize(header.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let value = self.value.take().ok_or_else(|| {
+            <RowError as de::Error>::custom("CSV value requested before its header")
+        })?;
+        seed.deserialize(FieldDeserializer(value))
+    }
+}
+
+/// Deserializes a single CSV field, parsing it into whatever primitive the target type asks for.
+struct FieldDeserializer<'de>(&'de str);
+
+macro_rules! parse_field {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                visitor.$visit(self.0.trim().parse().map_err(|e| {
+                    <RowError as de::Error>::custom(format_args!("Unable to parse CSV field {:?}: {}", self.0, e))
+                })?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for FieldDeserializer<'de> {
+    type Error = RowError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if let Ok(b) = self.0.parse::<bool>() {
+            visitor.visit_bool(b)
+        } else if let Ok(i) = self.0.parse::<i64>() {
+            visitor.visit_i64(i)
+        } else if let Ok(u) = self.0.parse::<u64>() {
+            visitor.visit_u64(u)
+        } else if let Ok(f) = self.0.parse::<f64>() {
+            visitor.visit_f64(f)
+        } else {
+            visitor.visit_borrowed_str(self.0)
+        }
+    }
+
+    parse_field!(
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    );
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_borrowed_str(self.0)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_string(String::from(self.0))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(self.0.into_deserializer())
+    }
+
+    serde::forward_to_deserialize_any! {
+        i128 u128 char bytes byte_buf unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
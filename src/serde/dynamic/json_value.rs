@@ -0,0 +1,119 @@
+//! Schema-less decoding of any supported format into a `serde_json::Value`.
+//!
+//! `serde_json::Value` on its own turns raw bytes into an array of numbers and rejects non-string
+//! map keys. The `JsonValue` wrapper defined here applies the conventions documented on
+//! `SimpleValueDecoder::decode_value` instead.
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Number, Value};
+
+pub(super) struct JsonValue(pub(super) Value);
+
+impl<'de> Deserialize<'de> for JsonValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(JsonValueVisitor)
+    }
+}
+
+struct JsonValueVisitor;
+
+impl<'de> Visitor<'de> for JsonValueVisitor {
+    type Value = JsonValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "any value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(JsonValue(Value::Bool(v)))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(JsonValue(Value::Number(v.into())))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(JsonValue(Value::Number(v.into())))
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Self::Value, E> {
+        Ok(JsonValue(
+            i64::try_from(v)
+                .map(|v| Value::Number(v.into()))
+                .unwrap_or_else(|_| Value::String(v.to_string())),
+        ))
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Self::Value, E> {
+        Ok(JsonValue(
+            u64::try_from(v)
+                .map(|v| Value::Number(v.into()))
+                .unwrap_or_else(|_| Value::String(v.to_string())),
+        ))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(JsonValue(
+            Number::from_f64(v).map_or(Value::Null, Value::Number),
+        ))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(JsonValue(Value::String(v.to_string())))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(JsonValue(Value::String(v)))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        use base64::Engine;
+        Ok(JsonValue(Value::String(
+            base64::engine::general_purpose::STANDARD.encode(v),
+        )))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(JsonValue(Value::Null))
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        JsonValue::deserialize(deserializer)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(JsonValue(Value::Null))
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        JsonValue::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        // The length hint comes from the payload, so it is not trusted for preallocation.
+        let mut values = Vec::new();
+        while let Some(JsonValue(value)) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(JsonValue(Value::Array(values)))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut values = Map::new();
+        while let Some((JsonValue(key), JsonValue(value))) = map.next_entry()? {
+            let key = match key {
+                Value::String(key) => key,
+                key => key.to_string(),
+            };
+            values.insert(key, value);
+        }
+        Ok(JsonValue(Value::Object(values)))
+    }
+}
@@ -25,9 +25,22 @@
 //! `Encoded` had implemented the `Deref` and `DerefMut` traits to make it easier to gain access to
 //! encapsulated data.
 //!
+//! ## Streaming
+//! With the `std` feature enabled, `.encode_to_writer` (from `SimpleStreamEncoder`) and
+//! `decode_from_reader` work directly on `std::io::Write` and `std::io::Read`, avoiding an
+//! intermediate `Vec<u8>` for large payloads. Formats whose backends only produce or consume a
+//! complete string/slice (Bson encoding, Flexbuffers, Json5, Postcard, Ron, Toml and QueryString
+//! decoding) still buffer internally.
+//!
+//! ## Transcoding
+//! With the `serde-json` feature enabled, `transcode(input, "yaml", "json")` converts a payload
+//! between two formats without a concrete type, using `serde_json::Value` as the intermediary.
+//! The feature builds `serde_json` with `preserve_order`, so map keys keep their input order.
+//!
 //! ## Supported formats
 //! - Bson
 //! - Cbor
+//! - Csv
 //! - FlexBuffers
 //! - Json
 //! - Json5
@@ -110,9 +123,16 @@
 //! ```
 use core::str::from_utf8;
 
+#[cfg(feature = "serde-csv")]
+mod csv_rows;
+#[cfg(feature = "serde-json")]
+mod json_value;
+
 pub mod prelude {
     #[cfg(feature = "serde-bson")]
     pub extern crate bson;
+    #[cfg(feature = "serde-csv")]
+    pub extern crate csv;
     #[cfg(feature = "serde-flexbuffers")]
     pub extern crate flexbuffers;
     #[cfg(feature = "serde-json5")]
@@ -162,6 +182,8 @@ pub enum ContentType {
     Bson,
     #[cfg(feature = "serde-cbor")]
     Cbor,
+    #[cfg(feature = "serde-csv")]
+    Csv,
     #[cfg(feature = "serde-flexbuffers")]
     FlexBuffers,
     #[cfg(feature = "serde-json")]
@@ -188,6 +210,85 @@ pub enum ContentType {
     Xml,
 }
 
+/// Every `ContentType` compiled into this build, in declaration order.
+const SUPPORTED_CONTENT_TYPES: &[ContentType] = &[
+    #[cfg(feature = "serde-bson")]
+    ContentType::Bson,
+    #[cfg(feature = "serde-cbor")]
+    ContentType::Cbor,
+    #[cfg(feature = "serde-csv")]
+    ContentType::Csv,
+    #[cfg(feature = "serde-flexbuffers")]
+    ContentType::FlexBuffers,
+    #[cfg(feature = "serde-json")]
+    ContentType::Json,
+    #[cfg(feature = "serde-json5")]
+    ContentType::Json5,
+    #[cfg(feature = "serde-lexpr")]
+    ContentType::Lexpr,
+    #[cfg(feature = "serde-messagepack")]
+    ContentType::MessagePack,
+    #[cfg(feature = "serde-pickle")]
+    ContentType::Pickle,
+    #[cfg(feature = "serde-postcard")]
+    ContentType::Postcard,
+    #[cfg(feature = "serde-ron")]
+    ContentType::Ron,
+    #[cfg(feature = "serde-toml")]
+    ContentType::Toml,
+    #[cfg(feature = "serde-query-string")]
+    ContentType::QueryString,
+    #[cfg(feature = "serde-yaml")]
+    ContentType::Yaml,
+    #[cfg(feature = "serde-accept-limited-xml-serialize")]
+    ContentType::Xml,
+];
+
+impl ContentType {
+    /// Returns the `ContentType` variants that are available in this build, as enabled through
+    /// the `serde-*` feature flags.
+    pub fn supported() -> &'static [ContentType] {
+        SUPPORTED_CONTENT_TYPES
+    }
+
+    /// Returns the canonical token for the content type, e.g. `"json"` or `"bson"`. The token is
+    /// accepted by `ContentType::try_from`.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            #[cfg(feature = "serde-bson")]
+            Self::Bson => "bson",
+            #[cfg(feature = "serde-cbor")]
+            Self::Cbor => "cbor",
+            #[cfg(feature = "serde-csv")]
+            Self::Csv => "csv",
+            #[cfg(feature = "serde-flexbuffers")]
+            Self::FlexBuffers => "flexbuffers",
+            #[cfg(feature = "serde-json")]
+            Self::Json => "json",
+            #[cfg(feature = "serde-json5")]
+            Self::Json5 => "json5",
+            #[cfg(feature = "serde-lexpr")]
+            Self::Lexpr => "lexpr",
+            #[cfg(feature = "serde-messagepack")]
+            Self::MessagePack => "messagepack",
+            #[cfg(feature = "serde-pickle")]
+            Self::Pickle => "pickle",
+            #[cfg(feature = "serde-postcard")]
+            Self::Postcard => "postcard",
+            #[cfg(feature = "serde-ron")]
+            Self::Ron => "ron",
+            #[cfg(feature = "serde-toml")]
+            Self::Toml => "toml",
+            #[cfg(feature = "serde-query-string")]
+            Self::QueryString => "querystring",
+            #[cfg(feature = "serde-yaml")]
+            Self::Yaml => "yaml",
+            #[cfg(feature = "serde-accept-limited-xml-serialize")]
+            Self::Xml => "xml",
+        }
+    }
+}
+
 impl TryFrom<&str> for ContentType {
     type Error = Error;
 
@@ -206,6 +307,14 @@ impl TryFrom<&str> for ContentType {
             "application/cbor" => Ok(ContentType::Cbor),
             #[cfg(feature = "serde-cbor")]
             "application/x-cbor" => Ok(ContentType::Cbor),
+            #[cfg(feature = "serde-csv")]
+            "csv" => Ok(ContentType::Csv),
+            #[cfg(feature = "serde-csv")]
+            "text/csv" => Ok(ContentType::Csv),
+            #[cfg(feature = "serde-csv")]
+            "application/csv" => Ok(ContentType::Csv),
+            #[cfg(feature = "serde-csv")]
+            "application/x-csv" => Ok(ContentType::Csv),
             #[cfg(feature = "serde-flexbuffers")]
             "flexbuffers" => Ok(ContentType::FlexBuffers),
             #[cfg(feature = "serde-flexbuffers")]
@@ -308,6 +417,8 @@ impl TryFrom<&ContentType> for ContentType {
             Self::Bson => Ok(Self::Bson),
             #[cfg(feature = "serde-cbor")]
             Self::Cbor => Ok(Self::Cbor),
+            #[cfg(feature = "serde-csv")]
+            Self::Csv => Ok(Self::Csv),
             #[cfg(feature = "serde-flexbuffers")]
             Self::FlexBuffers => Ok(Self::FlexBuffers),
             #[cfg(feature = "serde-json")]
@@ -354,6 +465,9 @@ pub enum Error {
     #[cfg(feature = "serde-cbor")]
     #[display(fmt = "CBOR encoder/decoder error: {}", _0)]
     CborFailure(serde_cbor::Error),
+    #[cfg(feature = "serde-csv")]
+    #[display(fmt = "CSV encoder/decoder error: {}", _0)]
+    CsvError(csv::Error),
     #[cfg(feature = "serde-flexbuffers")]
     #[display(fmt = "Flexbuffers encoder/decoder error: {}", _0)]
     FlexBuffersSerializationFailure(flexbuffers::SerializationError),
@@ -402,10 +516,73 @@ pub enum Error {
     #[cfg(feature = "serde-accept-limited-xml-serialize")]
     #[display(fmt = "XML encoder/decoder error: {}", _0)]
     XmlError(prelude::xml::Error),
+    #[cfg(feature = "std")]
+    #[display(fmt = "IO error while streaming encoder/decoder data: {}", _0)]
+    IoError(std::io::Error),
+    #[cfg(feature = "base64")]
+    #[display(fmt = "Base64 decoding error: {}", _0)]
+    Base64DecodeError(base64::DecodeError),
     #[display(fmt = "Type is not supported for encoding/decoding: {:?}", _0)]
     TypeDoesNotSupportSerialization(ContentType),
     #[display(fmt = "This would only happen if no serializers/deserializers have been set")]
     NoSerializersDeserializersSet,
+    #[display(fmt = "Unable to detect the content type of an empty input")]
+    EmptyInput,
+    #[display(fmt = "Unable to detect the content type, no decoder accepted the input")]
+    UnableToDetectContentType,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::ByteToUTF8ConversionFailure(e) => Some(e),
+            #[cfg(feature = "serde-bson")]
+            Error::BsonSerializationFailure(e) => Some(e),
+            #[cfg(feature = "serde-bson")]
+            Error::BsonDeserializationFailure(e) => Some(e),
+            #[cfg(feature = "serde-cbor")]
+            Error::CborFailure(e) => Some(e),
+            #[cfg(feature = "serde-csv")]
+            Error::CsvError(e) => Some(e),
+            #[cfg(feature = "serde-flexbuffers")]
+            Error::FlexBuffersSerializationFailure(e) => Some(e),
+            #[cfg(feature = "serde-flexbuffers")]
+            Error::FlexBuffersDeserializationFailure(e) => Some(e),
+            #[cfg(feature = "serde-json")]
+            Error::JsonError(e) => Some(e),
+            #[cfg(feature = "serde-json5")]
+            Error::Json5Error(e) => Some(e),
+            #[cfg(feature = "serde-lexpr")]
+            Error::LexprError(e) => Some(e),
+            #[cfg(feature = "serde-messagepack")]
+            Error::MessagePackEncodeError(e) => Some(e),
+            #[cfg(feature = "serde-messagepack")]
+            Error::MessagePackDecodeError(e) => Some(e),
+            #[cfg(feature = "serde-pickle")]
+            Error::PickleError(e) => Some(e),
+            #[cfg(feature = "serde-postcard")]
+            Error::PostcardError(e) => Some(e),
+            #[cfg(feature = "serde-ron")]
+            Error::RonError(e) => Some(e),
+            #[cfg(feature = "serde-ron")]
+            Error::RonDecodeError(e) => Some(e),
+            #[cfg(feature = "serde-toml")]
+            Error::TomlSerializationFailure(e) => Some(e),
+            #[cfg(feature = "serde-toml")]
+            Error::TomlDeserializationFailure(e) => Some(e),
+            #[cfg(feature = "serde-query-string")]
+            Error::QueryStringEncodingFailure(e) => Some(e),
+            #[cfg(feature = "serde-yaml")]
+            Error::YamlError(e) => Some(e),
+            #[cfg(feature = "serde-accept-limited-xml-serialize")]
+            Error::XmlError(e) => Some(e),
+            Error::IoError(e) => Some(e),
+            #[cfg(feature = "base64")]
+            Error::Base64DecodeError(e) => Some(e),
+            _ => None,
+        }
+    }
 }
 
 // Test for this from is disabled as its not possible to create the external
@@ -444,6 +621,13 @@ impl From<serde_cbor::Error> for Error {
     }
 }
 
+#[cfg(feature = "serde-csv")]
+impl From<csv::Error> for Error {
+    fn from(e: csv::Error) -> Self {
+        Error::CsvError(e)
+    }
+}
+
 #[cfg(feature = "serde-flexbuffers")]
 impl From<flexbuffers::SerializationError> for Error {
     fn from(e: flexbuffers::SerializationError) -> Self {
@@ -556,11 +740,84 @@ impl From<ron::de::SpannedError> for Error {
     }
 }
 
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IoError(e)
+    }
+}
+
+#[cfg(feature = "base64")]
+impl From<base64::DecodeError> for Error {
+    fn from(e: base64::DecodeError) -> Self {
+        Error::Base64DecodeError(e)
+    }
+}
+
 pub trait TryToString {
     type Error;
     fn try_to_string(&self) -> core::result::Result<String, Self::Error>;
 }
 
+/// Pickle protocol version used when encoding with `ContentType::Pickle`.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum PickleProtocol {
+    V2,
+    #[default]
+    V3,
+}
+
+/// Per-format configuration for `SimpleEncoder::encode_with`.
+///
+/// `SerdeOptions::default()` produces exactly the same output as `encode`.
+///
+/// ```rust
+/// use gearbox::serde::dynamic::{PickleProtocol, SerdeOptions};
+///
+/// let options = SerdeOptions::new()
+///     .json_pretty(true)
+///     .ron_struct_names(true)
+///     .pickle_protocol(PickleProtocol::V2);
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct SerdeOptions {
+    json_pretty: bool,
+    ron_struct_names: bool,
+    pickle_protocol: PickleProtocol,
+}
+
+impl SerdeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emit indented JSON instead of compact JSON.
+    pub fn json_pretty(mut self, pretty: bool) -> Self {
+        self.json_pretty = pretty;
+        self
+    }
+
+    /// Prefix RON structs with their type name, e.g. `MyStruct(field: 1)`.
+    pub fn ron_struct_names(mut self, struct_names: bool) -> Self {
+        self.ron_struct_names = struct_names;
+        self
+    }
+
+    /// Select the pickle protocol version.
+    pub fn pickle_protocol(mut self, protocol: PickleProtocol) -> Self {
+        self.pickle_protocol = protocol;
+        self
+    }
+
+    #[cfg(feature = "serde-pickle")]
+    fn pickle_options(&self) -> serde_pickle::SerOptions {
+        match self.pickle_protocol {
+            PickleProtocol::V2 => serde_pickle::SerOptions::new().proto_v2(),
+            PickleProtocol::V3 => serde_pickle::SerOptions::new(),
+        }
+    }
+}
+
 pub trait SimpleEncoder
 where
     Self: serde::Serialize,
@@ -569,6 +826,21 @@ where
         &self,
         content_type: F,
     ) -> Result<Encoded>;
+
+    /// Same as `encode`, but emits human-readable output for the text formats that support it
+    /// (Json, Ron, Toml). Every other format is encoded exactly as `encode` would.
+    fn encode_pretty<F: TryInto<ContentType, Error = impl Into<Error>>>(
+        &self,
+        content_type: F,
+    ) -> Result<Encoded>;
+
+    /// Same as `encode`, using `options` for the formats that can be configured (Json, Ron,
+    /// Pickle).
+    fn encode_with<F: TryInto<ContentType, Error = impl Into<Error>>>(
+        &self,
+        content_type: F,
+        options: &SerdeOptions,
+    ) -> Result<Encoded>;
 }
 
 impl<T> SimpleEncoder for T
@@ -583,6 +855,8 @@ where
         let bson = |o: &T| -> Result<Encoded> { bson::to_vec(o).try_into() };
         #[cfg(feature = "serde-cbor")]
         let cbor = |o: &T| -> Result<Encoded> { serde_cbor::to_vec(o).try_into() };
+        #[cfg(feature = "serde-csv")]
+        let csv = |o: &T| -> Result<Encoded> { csv_rows::to_vec(o).try_into() };
         #[cfg(feature = "serde-flexbuffers")]
         let flexbuffers = |o: &T| -> Result<Encoded> { flexbuffers::to_vec(o).try_into() };
         #[cfg(feature = "serde-json")]
@@ -613,6 +887,8 @@ where
             ContentType::Bson => bson(self),
             #[cfg(feature = "serde-cbor")]
             ContentType::Cbor => cbor(self),
+            #[cfg(feature = "serde-csv")]
+            ContentType::Csv => csv(self),
             #[cfg(feature = "serde-flexbuffers")]
             ContentType::FlexBuffers => flexbuffers(self),
             #[cfg(feature = "serde-json")]
@@ -639,6 +915,49 @@ where
             ContentType::Xml => xml(self),
         }
     }
+
+    fn encode_pretty<F: TryInto<ContentType, Error = impl Into<Error>>>(
+        &self,
+        content_type: F,
+    ) -> Result<Encoded> {
+        match content_type.try_into().map_err(|e| e.into())? {
+            #[cfg(feature = "serde-json")]
+            ContentType::Json => serde_json::to_vec_pretty(self).try_into(),
+            #[cfg(feature = "serde-ron")]
+            ContentType::Ron => {
+                ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()).try_into()
+            }
+            #[cfg(feature = "serde-toml")]
+            ContentType::Toml => toml::to_string_pretty(self).try_into(),
+            #[allow(unreachable_patterns)]
+            content_type => self.encode(content_type),
+        }
+    }
+
+    fn encode_with<F: TryInto<ContentType, Error = impl Into<Error>>>(
+        &self,
+        content_type: F,
+        options: &SerdeOptions,
+    ) -> Result<Encoded> {
+        match content_type.try_into().map_err(|e| e.into())? {
+            #[cfg(feature = "serde-json")]
+            ContentType::Json if options.json_pretty => serde_json::to_vec_pretty(self).try_into(),
+            #[cfg(feature = "serde-ron")]
+            ContentType::Ron if options.ron_struct_names => ron::ser::to_string_pretty(
+                self,
+                ron::ser::PrettyConfig::new()
+                    .struct_names(true)
+                    .new_line(String::new())
+                    .indentor(String::new())
+                    .separator(String::new()),
+            )
+            .try_into(),
+            #[cfg(feature = "serde-pickle")]
+            ContentType::Pickle => serde_pickle::to_vec(self, options.pickle_options()).try_into(),
+            #[allow(unreachable_patterns)]
+            content_type => self.encode(content_type),
+        }
+    }
 }
 
 pub trait SimpleDecoder<T> {
@@ -660,6 +979,8 @@ where
         let bson = |o: &[u8]| -> Result<Decoded<T>> { bson::from_slice(o).try_into() };
         #[cfg(feature = "serde-cbor")]
         let cbor = |o: &[u8]| -> Result<Decoded<T>> { serde_cbor::from_slice(o).try_into() };
+        #[cfg(feature = "serde-csv")]
+        let csv = |o: &[u8]| -> Result<Decoded<T>> { csv_rows::from_slice(o).try_into() };
         #[cfg(feature = "serde-flexbuffers")]
         let flexbuffers =
             |o: &[u8]| -> Result<Decoded<T>> { flexbuffers::from_slice(o).try_into() };
@@ -708,6 +1029,8 @@ where
             ContentType::Bson => bson(self),
             #[cfg(feature = "serde-cbor")]
             ContentType::Cbor => cbor(self),
+            #[cfg(feature = "serde-csv")]
+            ContentType::Csv => csv(self),
             #[cfg(feature = "serde-flexbuffers")]
             ContentType::FlexBuffers => flexbuffers(self),
             #[cfg(feature = "serde-json")]
@@ -772,6 +1095,371 @@ where
     }
 }
 
+/// Decoder producing a schema-less `serde_json::Value`, for inspecting payloads without a target
+/// type.
+///
+/// Values that JSON cannot represent directly are mapped as follows:
+/// - raw bytes (e.g. BSON binary, MessagePack bin) become a standard, padded base64 string
+/// - non-string map keys become their JSON text, e.g. the integer key `1` becomes `"1"`
+/// - integers outside the 64-bit range become decimal strings
+/// - non-finite floats (`NaN`, `inf`) become `null`
+#[cfg(feature = "serde-json")]
+pub trait SimpleValueDecoder {
+    fn decode_value<F: TryInto<ContentType, Error = impl Into<Error>>>(
+        &self,
+        content_type: F,
+    ) -> Result<serde_json::Value>;
+}
+
+#[cfg(feature = "serde-json")]
+impl SimpleValueDecoder for &[u8] {
+    fn decode_value<F: TryInto<ContentType, Error = impl Into<Error>>>(
+        &self,
+        content_type: F,
+    ) -> Result<serde_json::Value> {
+        SimpleDecoder::<Decoded<json_value::JsonValue>>::decode(self, content_type)
+            .map(|decoded| decoded.into().0)
+    }
+}
+
+#[cfg(feature = "serde-json")]
+impl SimpleValueDecoder for Vec<u8> {
+    fn decode_value<F: TryInto<ContentType, Error = impl Into<Error>>>(
+        &self,
+        content_type: F,
+    ) -> Result<serde_json::Value> {
+        self.as_slice().decode_value(content_type)
+    }
+}
+
+#[cfg(feature = "serde-json")]
+impl SimpleValueDecoder for &str {
+    fn decode_value<F: TryInto<ContentType, Error = impl Into<Error>>>(
+        &self,
+        content_type: F,
+    ) -> Result<serde_json::Value> {
+        self.as_bytes().decode_value(content_type)
+    }
+}
+
+#[cfg(feature = "serde-json")]
+impl SimpleValueDecoder for String {
+    fn decode_value<F: TryInto<ContentType, Error = impl Into<Error>>>(
+        &self,
+        content_type: F,
+    ) -> Result<serde_json::Value> {
+        self.as_bytes().decode_value(content_type)
+    }
+}
+
+/// Decoder that detects the `ContentType` of the input instead of taking it as an argument.
+pub trait SimpleAutoDecoder<T> {
+    /// Decodes the input, returning the decoded value together with the `ContentType` that was
+    /// able to decode it.
+    ///
+    /// Binary formats are recognized by their leading bytes (BSON by its length header, CBOR and
+    /// MessagePack by their map/array markers), text formats by their structure (JSON/JSON5 by
+    /// `{`/`[`, YAML by `---`, TOML by `key =`). If none of the structural checks lead to a
+    /// successful decode every text decoder is attempted in turn. JSON is always tried before
+    /// JSON5, so input that is valid as both is reported as `ContentType::Json`.
+    fn decode_auto(&self) -> Result<(T, ContentType)>;
+}
+
+/// Orders the compiled-in content types by how likely they are to match `input`, cheap structural
+/// checks first followed by every remaining text format.
+fn detect_content_types(input: &[u8]) -> Vec<ContentType> {
+    let mut candidates: Vec<ContentType> = Vec::new();
+    #[allow(dead_code)]
+    fn push(candidates: &mut Vec<ContentType>, content_type: ContentType) {
+        if !candidates.contains(&content_type) {
+            candidates.push(content_type);
+        }
+    }
+
+    #[cfg(feature = "serde-bson")]
+    if input.len() >= 5
+        && input[input.len() - 1] == 0
+        && i32::from_le_bytes([input[0], input[1], input[2], input[3]]) as usize == input.len()
+    {
+        push(&mut candidates, ContentType::Bson);
+    }
+
+    match input[0] {
+        #[cfg(feature = "serde-messagepack")]
+        0xdc..=0xdf => push(&mut candidates, ContentType::MessagePack),
+        0x80..=0x9f => {
+            #[cfg(feature = "serde-messagepack")]
+            push(&mut candidates, ContentType::MessagePack);
+            #[cfg(feature = "serde-cbor")]
+            push(&mut candidates, ContentType::Cbor);
+        }
+        #[cfg(feature = "serde-cbor")]
+        0xa0..=0xbf | 0xd9 => push(&mut candidates, ContentType::Cbor),
+        _ => {}
+    }
+
+    if let Ok(text) = from_utf8(input).map(|t| t.trim_start()) {
+        if text.starts_with('{') || text.starts_with('[') {
+            #[cfg(feature = "serde-json")]
+            push(&mut candidates, ContentType::Json);
+            #[cfg(feature = "serde-json5")]
+            push(&mut candidates, ContentType::Json5);
+        }
+        #[cfg(feature = "serde-yaml")]
+        if text.starts_with("---") {
+            push(&mut candidates, ContentType::Yaml);
+        }
+        #[cfg(feature = "serde-toml")]
+        if text
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty() && !l.starts_with('#'))
+            .and_then(|l| l.split_once('='))
+            .map(|(key, _)| {
+                let key = key.trim();
+                !key.is_empty()
+                    && key.chars().all(|c| {
+                        c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '"' | '\'')
+                    })
+            })
+            .unwrap_or(false)
+        {
+            push(&mut candidates, ContentType::Toml);
+        }
+
+        #[cfg(feature = "serde-json")]
+        push(&mut candidates, ContentType::Json);
+        #[cfg(feature = "serde-json5")]
+        push(&mut candidates, ContentType::Json5);
+        #[cfg(feature = "serde-toml")]
+        push(&mut candidates, ContentType::Toml);
+        #[cfg(feature = "serde-ron")]
+        push(&mut candidates, ContentType::Ron);
+        #[cfg(feature = "serde-lexpr")]
+        push(&mut candidates, ContentType::Lexpr);
+        #[cfg(feature = "serde-accept-limited-xml-serialize")]
+        push(&mut candidates, ContentType::Xml);
+        #[cfg(feature = "serde-yaml")]
+        push(&mut candidates, ContentType::Yaml);
+    }
+    candidates
+}
+
+impl<T> SimpleAutoDecoder<Decoded<T>> for &[u8]
+where
+    T: DeserializeOwned,
+{
+    fn decode_auto(&self) -> Result<(Decoded<T>, ContentType)> {
+        if self.iter().all(|b| b.is_ascii_whitespace()) {
+            return Err(Error::EmptyInput);
+        }
+        detect_content_types(self)
+            .into_iter()
+            .find_map(|content_type| {
+                SimpleDecoder::<Decoded<T>>::decode(self, &content_type)
+                    .ok()
+                    .map(|decoded| (decoded, content_type))
+            })
+            .ok_or(Error::UnableToDetectContentType)
+    }
+}
+
+impl<T> SimpleAutoDecoder<Decoded<T>> for Vec<u8>
+where
+    T: DeserializeOwned,
+{
+    fn decode_auto(&self) -> Result<(Decoded<T>, ContentType)> {
+        self.as_slice().decode_auto()
+    }
+}
+
+impl<T> SimpleAutoDecoder<Decoded<T>> for &str
+where
+    T: DeserializeOwned,
+{
+    fn decode_auto(&self) -> Result<(Decoded<T>, ContentType)> {
+        self.as_bytes().decode_auto()
+    }
+}
+
+impl<T> SimpleAutoDecoder<Decoded<T>> for String
+where
+    T: DeserializeOwned,
+{
+    fn decode_auto(&self) -> Result<(Decoded<T>, ContentType)> {
+        self.as_bytes().decode_auto()
+    }
+}
+
+/// Streaming counterpart to [`SimpleEncoder`], writing the encoded payload straight into a
+/// `std::io::Write` instead of materializing an [`Encoded`] buffer.
+///
+/// Formats without a writer based serializer (Bson, Flexbuffers, Json5, Postcard, Ron, Toml) are
+/// encoded into a buffer first, and the buffer is then written to the writer.
+#[cfg(feature = "std")]
+pub trait SimpleStreamEncoder
+where
+    Self: serde::Serialize,
+{
+    fn encode_to_writer<F: TryInto<ContentType, Error = impl Into<Error>>, W: std::io::Write>(
+        &self,
+        content_type: F,
+        writer: W,
+    ) -> Result<()>;
+}
+
+#[cfg(feature = "std")]
+impl<T> SimpleStreamEncoder for T
+where
+    T: Serialize,
+{
+    fn encode_to_writer<F: TryInto<ContentType, Error = impl Into<Error>>, W: std::io::Write>(
+        &self,
+        content_type: F,
+        mut writer: W,
+    ) -> Result<()> {
+        #[cfg(any(
+            feature = "serde-bson",
+            feature = "serde-flexbuffers",
+            feature = "serde-json5",
+            feature = "serde-postcard",
+            feature = "serde-ron",
+            feature = "serde-toml"
+        ))]
+        let buffered = |encoded: Result<Encoded>, w: &mut W| -> Result<()> {
+            encoded.and_then(|e| w.write_all(&e).map_err(Error::from))
+        };
+        match content_type.try_into().map_err(|e| e.into())? {
+            #[cfg(feature = "serde-bson")]
+            ContentType::Bson => buffered(self.encode(ContentType::Bson), &mut writer),
+            #[cfg(feature = "serde-cbor")]
+            ContentType::Cbor => serde_cbor::to_writer(&mut writer, self).map_err(Error::from),
+            #[cfg(feature = "serde-csv")]
+            ContentType::Csv => csv_rows::to_writer(&mut writer, self).map_err(Error::from),
+            #[cfg(feature = "serde-flexbuffers")]
+            ContentType::FlexBuffers => {
+                buffered(self.encode(ContentType::FlexBuffers), &mut writer)
+            }
+            #[cfg(feature = "serde-json")]
+            ContentType::Json => serde_json::to_writer(&mut writer, self).map_err(Error::from),
+            #[cfg(feature = "serde-json5")]
+            ContentType::Json5 => buffered(self.encode(ContentType::Json5), &mut writer),
+            #[cfg(feature = "serde-lexpr")]
+            ContentType::Lexpr => serde_lexpr::to_writer(&mut writer, self).map_err(Error::from),
+            #[cfg(feature = "serde-messagepack")]
+            ContentType::MessagePack => {
+                rmp_serde::encode::write(&mut writer, self).map_err(Error::from)
+            }
+            #[cfg(feature = "serde-pickle")]
+            ContentType::Pickle => {
+                serde_pickle::to_writer(&mut writer, self, Default::default()).map_err(Error::from)
+            }
+            #[cfg(feature = "serde-postcard")]
+            ContentType::Postcard => buffered(self.encode(ContentType::Postcard), &mut writer),
+            #[cfg(feature = "serde-ron")]
+            ContentType::Ron => buffered(self.encode(ContentType::Ron), &mut writer),
+            #[cfg(feature = "serde-toml")]
+            ContentType::Toml => buffered(self.encode(ContentType::Toml), &mut writer),
+            #[cfg(feature = "serde-query-string")]
+            ContentType::QueryString => serde_qs::to_writer(self, &mut writer).map_err(Error::from),
+            #[cfg(feature = "serde-yaml")]
+            ContentType::Yaml => serde_yaml::to_writer(&mut writer, self).map_err(Error::from),
+            #[cfg(feature = "serde-accept-limited-xml-serialize")]
+            ContentType::Xml => prelude::xml::to_writer(&mut writer, self).map_err(Error::from),
+        }
+    }
+}
+
+/// Decodes `T` directly from a `std::io::Read`, the streaming counterpart to [`SimpleDecoder`].
+///
+/// Formats without a reader based deserializer (Flexbuffers, Json5, Postcard, Ron, Toml,
+/// QueryString) read the full input into a buffer before decoding.
+#[cfg(feature = "std")]
+pub fn decode_from_reader<R, T, F: TryInto<ContentType, Error = impl Into<Error>>>(
+    reader: R,
+    content_type: F,
+) -> Result<Decoded<T>>
+where
+    R: std::io::Read,
+    T: DeserializeOwned,
+{
+    #[cfg(any(
+        feature = "serde-flexbuffers",
+        feature = "serde-json5",
+        feature = "serde-postcard",
+        feature = "serde-ron",
+        feature = "serde-toml",
+        feature = "serde-query-string"
+    ))]
+    let buffered = |mut reader: R, content_type: ContentType| -> Result<Decoded<T>> {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        buf.as_slice().decode(content_type)
+    };
+    match content_type.try_into().map_err(|e| e.into())? {
+        #[cfg(feature = "serde-bson")]
+        ContentType::Bson => bson::from_reader(reader).try_into(),
+        #[cfg(feature = "serde-cbor")]
+        ContentType::Cbor => serde_cbor::from_reader(reader).try_into(),
+        #[cfg(feature = "serde-csv")]
+        ContentType::Csv => csv_rows::from_reader(reader).try_into(),
+        #[cfg(feature = "serde-flexbuffers")]
+        ContentType::FlexBuffers => buffered(reader, ContentType::FlexBuffers),
+        #[cfg(feature = "serde-json")]
+        ContentType::Json => serde_json::from_reader(reader).try_into(),
+        #[cfg(feature = "serde-json5")]
+        ContentType::Json5 => buffered(reader, ContentType::Json5),
+        #[cfg(feature = "serde-lexpr")]
+        ContentType::Lexpr => serde_lexpr::from_reader(reader).try_into(),
+        #[cfg(feature = "serde-messagepack")]
+        ContentType::MessagePack => rmp_serde::from_read(reader).try_into(),
+        #[cfg(feature = "serde-pickle")]
+        ContentType::Pickle => serde_pickle::from_reader(reader, Default::default()).try_into(),
+        #[cfg(feature = "serde-postcard")]
+        ContentType::Postcard => buffered(reader, ContentType::Postcard),
+        #[cfg(feature = "serde-ron")]
+        ContentType::Ron => buffered(reader, ContentType::Ron),
+        #[cfg(feature = "serde-toml")]
+        ContentType::Toml => buffered(reader, ContentType::Toml),
+        #[cfg(feature = "serde-query-string")]
+        ContentType::QueryString => buffered(reader, ContentType::QueryString),
+        #[cfg(feature = "serde-yaml")]
+        ContentType::Yaml => serde_yaml::from_reader(reader).try_into(),
+        #[cfg(feature = "serde-accept-limited-xml-serialize")]
+        ContentType::Xml => prelude::xml::de::from_reader(reader).try_into(),
+    }
+}
+
+/// Converts `input` from one `ContentType` into another without a concrete target type.
+///
+/// The payload is decoded into a `serde_json::Value` and re-encoded into the target format. Map
+/// keys keep the order of the input, as `serde-json` enables the `preserve_order` feature of
+/// `serde_json`.
+/// Formats that are not self-describing (Postcard) cannot be represented by the intermediary and
+/// return `Error::TypeDoesNotSupportSerialization`.
+#[cfg(feature = "serde-json")]
+pub fn transcode<
+    F: TryInto<ContentType, Error = impl Into<Error>>,
+    T: TryInto<ContentType, Error = impl Into<Error>>,
+>(
+    input: &[u8],
+    from: F,
+    to: T,
+) -> Result<Encoded> {
+    let from = from.try_into().map_err(|e| e.into())?;
+    let to = to.try_into().map_err(|e| e.into())?;
+    #[cfg(feature = "serde-postcard")]
+    if from == ContentType::Postcard {
+        return Err(Error::TypeDoesNotSupportSerialization(from));
+    }
+    #[cfg(feature = "serde-postcard")]
+    if to == ContentType::Postcard {
+        return Err(Error::TypeDoesNotSupportSerialization(to));
+    }
+    let value: Decoded<serde_json::Value> = input.decode(from)?;
+    value.into().encode(to)
+}
+
 pub struct Encoded {
     inner: Vec<u8>,
 }
@@ -879,6 +1567,30 @@ impl TryToString for Encoded {
     }
 }
 
+#[cfg(feature = "base64")]
+impl Encoded {
+    /// Encodes the payload as standard, padded base64.
+    pub fn to_base64(&self) -> String {
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD.encode(&self.inner)
+    }
+
+    /// Encodes the payload as URL-safe base64 without padding.
+    pub fn to_base64_url(&self) -> String {
+        use base64::Engine;
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&self.inner)
+    }
+
+    /// Decodes a base64 string produced by either `to_base64` or `to_base64_url`.
+    pub fn from_base64(s: &str) -> Result<Encoded> {
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD
+            .decode(s)
+            .or_else(|_| base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(s))
+            .try_into()
+    }
+}
+
 pub struct Decoded<T>
 where
     T: DeserializeOwned,
@@ -929,7 +1641,34 @@ mod test {
     mod test_constants;
     mod test_trait_impl;
 
-    use super::{ContentType, Decoded, Encoded, Error, SimpleDecoder, SimpleEncoder, TryToString};
+    #[cfg(all(
+        feature = "serde-json",
+        any(
+            feature = "serde-yaml",
+            feature = "serde-cbor",
+            feature = "serde-postcard"
+        )
+    ))]
+    use super::transcode;
+    #[cfg(any(
+        feature = "serde-yaml",
+        all(feature = "base64", feature = "serde-cbor")
+    ))]
+    use super::Encoded;
+    #[cfg(all(
+        feature = "serde-json",
+        any(feature = "serde-yaml", feature = "serde-cbor")
+    ))]
+    use super::SimpleValueDecoder;
+    #[cfg(all(
+        feature = "std",
+        any(feature = "serde-json", feature = "serde-cbor", feature = "serde-toml")
+    ))]
+    use super::{decode_from_reader, SimpleStreamEncoder};
+    use super::{
+        ContentType, Decoded, Error, SerdeOptions, SimpleAutoDecoder, SimpleDecoder, SimpleEncoder,
+        TryToString,
+    };
     use core::ops::Deref;
     use serde::{Deserialize, Serialize};
     use test_constants::*;
@@ -1015,6 +1754,16 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_supported_as_str_round_trip() {
+        for content_type in ContentType::supported() {
+            assert_eq!(
+                content_type,
+                &ContentType::try_from(content_type.as_str()).unwrap()
+            );
+        }
+    }
+
     #[test]
     #[cfg(feature = "serde-bson")]
     fn test_from_str() {
@@ -1124,6 +1873,89 @@ mod test {
         assert_eq!(my_struct.into(), MyStruct::default());
     }
 
+    #[test]
+    #[cfg(feature = "serde-json")]
+    fn test_json_pretty() {
+        let pretty = MyStruct::default()
+            .encode_pretty("json")
+            .unwrap()
+            .try_to_string()
+            .unwrap();
+        assert!(pretty.starts_with("{\n  \"unquoted\": \"and you can quote me on that\",\n"));
+        let decoded: Decoded<MyStruct> = pretty.decode("json").unwrap();
+        assert_eq!(decoded.into(), MyStruct::default());
+    }
+
+    #[test]
+    #[cfg(feature = "serde-ron")]
+    fn test_ron_pretty() {
+        let pretty = MyStruct::default().encode_pretty("ron").unwrap();
+        assert!(pretty.contains(&b'\n'));
+        let decoded: Decoded<MyStruct> = pretty.decode("ron").unwrap();
+        assert_eq!(decoded.into(), MyStruct::default());
+    }
+
+    #[test]
+    #[cfg(feature = "serde-cbor")]
+    fn test_binary_pretty_matches_encode() {
+        assert_eq!(
+            CBOR_SERIALIZE,
+            MyStruct::default().encode_pretty("cbor").unwrap().deref()
+        );
+    }
+
+    #[test]
+    fn test_encode_with_default_options() {
+        let options = SerdeOptions::default();
+        for content_type in ContentType::supported() {
+            assert_eq!(
+                MyStruct::default()
+                    .encode(content_type)
+                    .map(|e| e.to_vec())
+                    .ok(),
+                MyStruct::default()
+                    .encode_with(content_type, &options)
+                    .map(|e| e.to_vec())
+                    .ok()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde-json")]
+    fn test_encode_with_json_pretty() {
+        let options = SerdeOptions::new().json_pretty(true);
+        assert_eq!(
+            MyStruct::default().encode_pretty("json").unwrap().deref(),
+            MyStruct::default()
+                .encode_with("json", &options)
+                .unwrap()
+                .deref()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde-ron")]
+    fn test_encode_with_ron_struct_names() {
+        let options = SerdeOptions::new().ron_struct_names(true);
+        let encoded = MyStruct::default().encode_with("ron", &options).unwrap();
+        assert!(encoded.try_to_string().unwrap().starts_with("MyStruct("));
+        let decoded: Decoded<MyStruct> = encoded.decode("ron").unwrap();
+        assert_eq!(decoded.into(), MyStruct::default());
+    }
+
+    #[test]
+    #[cfg(feature = "serde-pickle")]
+    fn test_encode_with_pickle_protocol() {
+        use super::PickleProtocol;
+
+        let v2 = SerdeOptions::new().pickle_protocol(PickleProtocol::V2);
+        let encoded = MyStruct::default().encode_with("pickle", &v2).unwrap();
+        assert_ne!(PICKLE_SERIALIZE, encoded.deref());
+        let decoded: Decoded<MyStruct> = encoded.decode("pickle").unwrap();
+        assert_eq!(decoded.into(), MyStruct::default());
+    }
+
     #[test]
     #[cfg(feature = "serde-yaml")]
     fn test_yaml() {
@@ -1159,6 +1991,49 @@ mod test {
         deserialize_test("cbor", CBOR_SERIALIZE);
     }
 
+    #[test]
+    #[cfg(feature = "serde-csv")]
+    fn test_csv() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Record {
+            name: String,
+            age: u32,
+            active: bool,
+            score: Option<f64>,
+        }
+
+        let records = vec![
+            Record {
+                name: "foo".to_string(),
+                age: 1,
+                active: true,
+                score: Some(0.5),
+            },
+            Record {
+                name: "bar, baz".to_string(),
+                age: 2,
+                active: false,
+                score: None,
+            },
+        ];
+        let expected = "name,age,active,score\nfoo,1,true,0.5\n\"bar, baz\",2,false,\n";
+
+        for content_type in ["csv", "text/csv", "application/csv", "application/x-csv"] {
+            let encoded = records.encode(content_type).unwrap();
+            assert_eq!(expected, encoded.try_to_string().unwrap());
+            let decoded: Decoded<Vec<Record>> = encoded.decode(content_type).unwrap();
+            assert_eq!(records, decoded.into());
+        }
+
+        let single = records[0].encode("csv").unwrap();
+        assert_eq!(
+            "name,age,active,score\nfoo,1,true,0.5\n",
+            single.try_to_string().unwrap()
+        );
+        let decoded: Decoded<Record> = single.decode("csv").unwrap();
+        assert_eq!(records[0], decoded.into());
+    }
+
     #[test]
     #[cfg(feature = "serde-bson")]
     fn test_bson() {
@@ -1222,6 +2097,228 @@ mod test {
         deserialize_test("querystring", URL_SERIALIZE.as_bytes());
     }
 
+    #[test]
+    #[cfg(all(feature = "std", feature = "serde-json"))]
+    fn test_json_stream() {
+        let mut writer = Vec::new();
+        MyStruct::default()
+            .encode_to_writer("json", &mut writer)
+            .unwrap();
+        assert_eq!(EXAMPLE_JSON_SERIALIZE.as_bytes(), writer.as_slice());
+
+        let decoded: Decoded<MyStruct> = decode_from_reader(writer.as_slice(), "json").unwrap();
+        assert_eq!(decoded.into(), MyStruct::default());
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", feature = "serde-cbor"))]
+    fn test_cbor_stream() {
+        let mut writer = Vec::new();
+        MyStruct::default()
+            .encode_to_writer("cbor", &mut writer)
+            .unwrap();
+        assert_eq!(CBOR_SERIALIZE, writer.as_slice());
+
+        let decoded: Decoded<MyStruct> = decode_from_reader(writer.as_slice(), "cbor").unwrap();
+        assert_eq!(decoded.into(), MyStruct::default());
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", feature = "serde-toml"))]
+    fn test_toml_stream_buffered() {
+        let mut writer = Vec::new();
+        MyStruct::default()
+            .encode_to_writer("toml", &mut writer)
+            .unwrap();
+        assert_eq!(TOML_SERIALIZE.as_bytes(), writer.as_slice());
+
+        let decoded: Decoded<MyStruct> = decode_from_reader(writer.as_slice(), "toml").unwrap();
+        assert_eq!(decoded.into(), MyStruct::default());
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde-json", feature = "serde-yaml"))]
+    fn test_transcode_json_yaml_json() {
+        let yaml = transcode(EXAMPLE_JSON_SERIALIZE.as_bytes(), "json", "yaml").unwrap();
+        let json = transcode(&yaml, ContentType::Yaml, ContentType::Json).unwrap();
+        let original: Decoded<serde_json::Value> =
+            EXAMPLE_JSON_SERIALIZE.as_bytes().decode("json").unwrap();
+        let round_trip: Decoded<serde_json::Value> = json.decode("json").unwrap();
+        assert_eq!(original.into(), round_trip.into());
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde-json", feature = "serde-yaml"))]
+    fn test_transcode_preserves_map_order() {
+        let yaml = "zebra: 1\napple:\n  mango: true\n  banana: false\nkiwi: x\n";
+        let json = transcode(yaml.as_bytes(), "yaml", "json").unwrap();
+        assert_eq!(
+            json.try_to_string().unwrap(),
+            r#"{"zebra":1,"apple":{"mango":true,"banana":false},"kiwi":"x"}"#
+        );
+        let yaml_round_trip = transcode(&json, "json", "yaml").unwrap();
+        assert_eq!(yaml_round_trip.try_to_string().unwrap(), yaml);
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde-json", feature = "serde-cbor"))]
+    fn test_transcode_json_cbor_json() {
+        let cbor = transcode(EXAMPLE_JSON_SERIALIZE.as_bytes(), "json", "cbor").unwrap();
+        let decoded: Decoded<MyStruct> = cbor.decode("cbor").unwrap();
+        assert_eq!(decoded.into(), MyStruct::default());
+        let json = transcode(&cbor, "cbor", "json").unwrap();
+        let decoded: Decoded<MyStruct> = json.decode("json").unwrap();
+        assert_eq!(decoded.into(), MyStruct::default());
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde-json", feature = "serde-postcard"))]
+    fn test_transcode_postcard_unsupported() {
+        assert!(matches!(
+            transcode(EXAMPLE_JSON_SERIALIZE.as_bytes(), "json", "postcard"),
+            Err(Error::TypeDoesNotSupportSerialization(
+                ContentType::Postcard
+            ))
+        ));
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", feature = "serde-json"))]
+    fn test_error_source() {
+        use std::error::Error as StdError;
+
+        let err = SimpleDecoder::<Decoded<MyStruct>>::decode(&"{\"unquoted\": 1}", "json")
+            .err()
+            .unwrap();
+        let source = err.source().expect("JSON error should expose its source");
+        let json_error = source
+            .downcast_ref::<serde_json::Error>()
+            .expect("Source should be the serde_json error");
+        assert_eq!(1, json_error.line());
+        assert!(Error::NoSerializersDeserializersSet.source().is_none());
+    }
+
+    fn decode_auto_test(input: &[u8], expected: ContentType) {
+        let (decoded, content_type): (Decoded<MyStruct>, ContentType) =
+            input.decode_auto().unwrap();
+        assert_eq!(expected, content_type);
+        assert_eq!(decoded.into(), MyStruct::default());
+    }
+
+    #[test]
+    fn test_decode_auto_empty() {
+        let empty: &[u8] = &[];
+        assert_eq!(
+            Error::EmptyInput,
+            SimpleAutoDecoder::<Decoded<MyStruct>>::decode_auto(&empty)
+                .err()
+                .unwrap()
+        );
+        assert_eq!(
+            Error::EmptyInput,
+            SimpleAutoDecoder::<Decoded<MyStruct>>::decode_auto(&" \n")
+                .err()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde-json")]
+    fn test_decode_auto_prefers_json() {
+        decode_auto_test(EXAMPLE_JSON_DESERIALIZE.as_bytes(), ContentType::Json);
+    }
+
+    #[test]
+    #[cfg(feature = "serde-json5")]
+    fn test_decode_auto_json5() {
+        decode_auto_test(EXAMPLE_JSON5_DESERIALIZE.as_bytes(), ContentType::Json5);
+    }
+
+    #[test]
+    #[cfg(feature = "serde-yaml")]
+    fn test_decode_auto_yaml() {
+        decode_auto_test(
+            format!("---\n{}", EXAMPLE_YAML_DESERIALIZE).as_bytes(),
+            ContentType::Yaml,
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde-toml")]
+    fn test_decode_auto_toml() {
+        decode_auto_test(TOML_SERIALIZE.as_bytes(), ContentType::Toml);
+    }
+
+    #[test]
+    #[cfg(feature = "serde-bson")]
+    fn test_decode_auto_bson() {
+        decode_auto_test(BSON_SERIALIZE, ContentType::Bson);
+    }
+
+    #[test]
+    #[cfg(feature = "serde-cbor")]
+    fn test_decode_auto_cbor() {
+        decode_auto_test(CBOR_SERIALIZE, ContentType::Cbor);
+    }
+
+    #[test]
+    #[cfg(feature = "serde-messagepack")]
+    fn test_decode_auto_messagepack() {
+        decode_auto_test(MESSAGEPACK_SERIALIZE, ContentType::MessagePack);
+    }
+
+    #[test]
+    #[cfg(all(feature = "base64", feature = "serde-cbor"))]
+    fn test_base64_round_trip() {
+        let encoded = MyStruct::default().encode("cbor").unwrap();
+
+        let standard = encoded.to_base64();
+        assert!(standard.ends_with('='));
+        let url = encoded.to_base64_url();
+        assert!(!url.contains('=') && !url.contains('+') && !url.contains('/'));
+
+        for b64 in [standard, url] {
+            let from_b64 = Encoded::from_base64(&b64).unwrap();
+            assert_eq!(CBOR_SERIALIZE, from_b64.deref());
+            let decoded: Decoded<MyStruct> = from_b64.decode("cbor").unwrap();
+            assert_eq!(decoded.into(), MyStruct::default());
+        }
+        assert!(matches!(
+            Encoded::from_base64("not base64!"),
+            Err(Error::Base64DecodeError(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde-json", feature = "serde-yaml"))]
+    fn test_decode_value() {
+        let from_yaml = EXAMPLE_YAML_DESERIALIZE.decode_value("yaml").unwrap();
+        let from_json = EXAMPLE_JSON_DESERIALIZE.decode_value("json").unwrap();
+        assert_eq!(from_json, from_yaml);
+        assert_eq!(
+            "and you can quote me on that",
+            from_yaml["unquoted"].as_str().unwrap()
+        );
+        assert_eq!(912559, from_yaml["hexadecimal"].as_i64().unwrap());
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde-json", feature = "serde-cbor"))]
+    fn test_decode_value_bytes_and_keys() {
+        // {"data": h'010203', 1: "one"}
+        let cbor: &[u8] = &[
+            0xa2, 0x64, 0x64, 0x61, 0x74, 0x61, 0x43, 0x01, 0x02, 0x03, 0x01, 0x63, 0x6f, 0x6e,
+            0x65,
+        ];
+        let value = cbor.decode_value("cbor").unwrap();
+        assert_eq!(serde_json::json!("AQID"), value["data"]);
+        assert_eq!(serde_json::json!("one"), value["1"]);
+
+        // An array announcing 2^60 elements without any is rejected instead of preallocated.
+        let cbor: &[u8] = &[0x9b, 0x10, 0, 0, 0, 0, 0, 0, 0];
+        assert!(cbor.decode_value("cbor").is_err());
+    }
+
     #[test]
     #[cfg(feature = "serde-bson")]
     fn test_error_from_bson_error() {
@@ -1232,6 +2329,7 @@ mod test {
     }
 
     #[test]
+    #[cfg(feature = "serde-yaml")]
     fn test_documentation_example() {
         #[derive(Serialize)]
         struct Foo {
@@ -34,6 +34,11 @@ impl PartialEq for Error {
                 Error::CborFailure(ee) => format!("{}", e) == format!("{}", ee),
                 _ => false,
             },
+            #[cfg(feature = "serde-csv")]
+            Error::CsvError(e) => match other {
+                Error::CsvError(ee) => format!("{}", e) == format!("{}", ee),
+                _ => false,
+            },
             #[cfg(feature = "serde-flexbuffers")]
             Error::FlexBuffersSerializationFailure(e) => match other {
                 Error::FlexBuffersSerializationFailure(ee) => format!("{}", e) == format!("{}", ee),
@@ -111,6 +116,16 @@ impl PartialEq for Error {
                 Error::XmlError(ee) => format!("{:?}", e) == format!("{:?}", ee),
                 _ => false,
             },
+            #[cfg(feature = "std")]
+            Error::IoError(e) => match other {
+                Error::IoError(ee) => format!("{}", e) == format!("{}", ee),
+                _ => false,
+            },
+            #[cfg(feature = "base64")]
+            Error::Base64DecodeError(e) => match other {
+                Error::Base64DecodeError(ee) => format!("{}", e) == format!("{}", ee),
+                _ => false,
+            },
             Error::TypeDoesNotSupportSerialization(e) => match other {
                 Error::TypeDoesNotSupportSerialization(ee) => {
                     format!("{:?}", e) == format!("{:?}", ee)
@@ -126,6 +141,8 @@ impl PartialEq for Error {
                 Error::NoSerializersDeserializersSet => true,
                 _ => false,
             },
+            Error::EmptyInput => matches!(other, Error::EmptyInput),
+            Error::UnableToDetectContentType => matches!(other, Error::UnableToDetectContentType),
         }
     }
 }
@@ -0,0 +1,235 @@
+//! HTTP responses for `DynTracerError`.
+//!
+//! Errors are rendered as JSON with `ErrorDigest::to_json`. The status code is taken from the
+//! `code` of the error when it is an HTTP client or server error status, every other error is
+//! answered with `500 Internal Server Error`, so an error never turns into a successful response.
+//!
+//! Server errors only expose their `message` and `code`. The location, subsystem and stack of the
+//! error are internal details, they are only included for client errors or when debug output is
+//! enabled with `TracerErrorLayer::with_debug`.
+use crate::error::tracer::DynTracerError;
+use alloc::boxed::Box;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use core::convert::Infallible;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Renders `error` as a JSON response, see the module documentation for the status code and the
+/// fields included.
+pub fn error_response(error: &DynTracerError) -> Response {
+    render_error(error, false)
+}
+
+/// Renders `error` as a JSON response that includes all fields of the digest, even for server
+/// errors. Only meant for development, as it exposes the internals of the service.
+pub fn debug_error_response(error: &DynTracerError) -> Response {
+    render_error(error, true)
+}
+
+fn render_error(error: &DynTracerError, debug: bool) -> Response {
+    let digest = error.digest();
+    let status = digest
+        .code
+        .and_then(|t| StatusCode::from_u16(t).ok())
+        .filter(|t| t.is_client_error() || t.is_server_error())
+        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let body = match debug || !status.is_server_error() {
+        true => digest.to_json(),
+        false => serde_json::json!({
+            "message": digest.message,
+            "code": digest.code,
+        }),
+    };
+    (status, Json(body)).into_response()
+}
+
+impl IntoResponse for DynTracerError {
+    fn into_response(self) -> Response {
+        error_response(&self)
+    }
+}
+
+/// A tower layer that turns the `DynTracerError`s of a service into JSON error responses.
+///
+/// The wrapped service is infallible, so it can be mounted with `Router::route_service`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracerErrorLayer {
+    debug: bool,
+}
+
+impl TracerErrorLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Includes all fields of the digest in server error responses, see `debug_error_response`.
+    pub fn with_debug(mut self, debug: bool) -> Self {
+        self.debug = debug;
+        self
+    }
+}
+
+impl<S> Layer<S> for TracerErrorLayer {
+    type Service = TracerErrorService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TracerErrorService {
+            inner,
+            debug: self.debug,
+            error: None,
+        }
+    }
+}
+
+/// The service created by `TracerErrorLayer`.
+pub struct TracerErrorService<S> {
+    inner: S,
+    debug: bool,
+    /// An error of `poll_ready`, answered on the next call.
+    error: Option<Response>,
+}
+
+impl<S: Clone> Clone for TracerErrorService<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            debug: self.debug,
+            error: None,
+        }
+    }
+}
+
+impl<S, Req> Service<Req> for TracerErrorService<S>
+where
+    S: Service<Req, Error = DynTracerError>,
+    S::Response: IntoResponse,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map(|result| {
+            if let Err(e) = result {
+                self.error = Some(render_error(&e, self.debug));
+            }
+            Ok(())
+        })
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        if let Some(response) = self.error.take() {
+            return Box::pin(async move { Ok(response) });
+        }
+
+        let future = self.inner.call(req);
+        let debug = self.debug;
+        Box::pin(async move {
+            Ok(match future.await {
+                Ok(response) => response.into_response(),
+                Err(e) => render_error(&e, debug),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::tracer::ErrorTracerExtInfo;
+    use alloc::string::{String, ToString};
+    use axum::body::Body;
+    use axum::http::Request;
+    use axum::Router;
+    use http_body_util::BodyExt;
+    use tower::{service_fn, ServiceExt};
+
+    async fn find_user(req: Request<Body>) -> Result<String, DynTracerError> {
+        match req.uri().path() {
+            "/users/1" => Ok("alice".to_string()),
+            "/users/teapot" => Err(ErrorTracerExtInfo::default()
+                .with_code(7)
+                .with_dyn_error("not an http status")),
+            "/users/crash" => Err(ErrorTracerExtInfo::default()
+                .with_code(503)
+                .with_file("src/users.rs")
+                .with_line(42)
+                .with_subsystem("users")
+                .with_dyn_error("database unavailable")),
+            _ => Err(ErrorTracerExtInfo::default()
+                .with_code(404)
+                .with_subsystem("users")
+                .with_dyn_error("user not found")),
+        }
+    }
+
+    async fn get_path(path: &str) -> (StatusCode, String) {
+        get_path_with(TracerErrorLayer::new(), path).await
+    }
+
+    async fn get_path_with(layer: TracerErrorLayer, path: &str) -> (StatusCode, String) {
+        let router = Router::new().route_service("/users/:id", layer.layer(service_fn(find_user)));
+        let response = router
+            .oneshot(Request::get(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_error_code_maps_to_status() {
+        let (status, body) = get_path("/users/2").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["code"], 404);
+        assert_eq!(json["subsystem"], "users");
+        assert!(json["message"].as_str().unwrap().contains("user not found"));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_code_maps_to_internal_error() {
+        let (status, body) = get_path("/users/teapot").await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["code"], 7);
+    }
+
+    #[tokio::test]
+    async fn test_server_error_hides_internals() {
+        let (status, body) = get_path("/users/crash").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["code"], 503);
+        assert!(json["message"]
+            .as_str()
+            .unwrap()
+            .contains("database unavailable"));
+        for field in ["file", "line", "subsystem", "stack"] {
+            assert!(json.get(field).is_none(), "{} leaked: {}", field, body);
+        }
+
+        let (_, body) =
+            get_path_with(TracerErrorLayer::new().with_debug(true), "/users/crash").await;
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["file"], "src/users.rs");
+        assert_eq!(json["line"], 42);
+        assert_eq!(json["subsystem"], "users");
+    }
+
+    #[tokio::test]
+    async fn test_success_passes_through() {
+        assert_eq!(
+            get_path("/users/1").await,
+            (StatusCode::OK, "alice".to_string())
+        );
+    }
+}
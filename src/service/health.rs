@@ -0,0 +1,108 @@
+//! Liveness and readiness routes for axum services.
+//!
+//! `/livez` answers `200 OK` as long as the process is able to serve requests at all, while
+//! `/readyz` asks a readiness check whether the service can handle traffic, e.g. whether its
+//! database connection is established, and answers `503 Service Unavailable` otherwise.
+//!
+//! # Examples
+//! ```
+//! use axum::{routing::get, Router};
+//! use gearbox::service::health::health_routes_with_readiness;
+//! use std::sync::atomic::{AtomicBool, Ordering};
+//! use std::sync::Arc;
+//!
+//! let ready = Arc::new(AtomicBool::new(false));
+//! let app: Router = Router::new()
+//!     .route("/", get(|| async { "hello" }))
+//!     .merge(health_routes_with_readiness(move || {
+//!         let ready = ready.clone();
+//!         async move { ready.load(Ordering::Relaxed) }
+//!     }));
+//! ```
+use axum::{http::StatusCode, routing::get, Router};
+use core::future::Future;
+
+/// The path of the liveness route.
+pub const LIVEZ_PATH: &str = "/livez";
+/// The path of the readiness route.
+pub const READYZ_PATH: &str = "/readyz";
+
+/// Returns a router with `/livez` and a `/readyz` route that always reports ready.
+pub fn health_routes() -> Router {
+    health_routes_with_readiness(|| async { true })
+}
+
+/// Returns a router with `/livez` and a `/readyz` route that reports the result of `check`.
+///
+/// `check` is called for every request to `/readyz`.
+pub fn health_routes_with_readiness<F, Fut>(check: F) -> Router
+where
+    F: Fn() -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = bool> + Send + 'static,
+{
+    Router::new()
+        .route(LIVEZ_PATH, get(|| async { (StatusCode::OK, "ok") }))
+        .route(
+            READYZ_PATH,
+            get(move || async move {
+                if check().await {
+                    (StatusCode::OK, "ready")
+                } else {
+                    (StatusCode::SERVICE_UNAVAILABLE, "not ready")
+                }
+            }),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    async fn get_path(router: Router, path: &str) -> (StatusCode, String) {
+        let response = router
+            .oneshot(Request::get(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_livez() {
+        let (status, body) = get_path(health_routes(), "/livez").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "ok");
+
+        let (status, _) = get_path(health_routes(), "/readyz").await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_readyz_uses_check() {
+        let ready = Arc::new(AtomicBool::new(false));
+        let check = {
+            let ready = ready.clone();
+            move || {
+                let ready = ready.clone();
+                async move { ready.load(Ordering::SeqCst) }
+            }
+        };
+        let router = health_routes_with_readiness(check);
+
+        let (status, body) = get_path(router.clone(), "/readyz").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body, "not ready");
+        // Liveness does not depend on readiness.
+        assert_eq!(get_path(router.clone(), "/livez").await.0, StatusCode::OK);
+
+        ready.store(true, Ordering::SeqCst);
+        assert_eq!(get_path(router, "/readyz").await.0, StatusCode::OK);
+    }
+}
@@ -2,3 +2,11 @@
 // pub mod discovery;
 // #[cfg(feature = "service-framework-axum")]
 // pub mod framework;
+#[cfg(feature = "service-error")]
+pub mod error;
+#[cfg(feature = "service-health")]
+pub mod health;
+#[cfg(feature = "service-negotiate")]
+pub mod negotiate;
+#[cfg(feature = "service-shutdown")]
+pub mod shutdown;
@@ -0,0 +1,313 @@
+//! Content negotiation for axum handlers, built on `serde::dynamic`.
+//!
+//! `Negotiated<T>` decodes the request body according to its `Content-Type` header, and `Accept`
+//! picks the format of the response from the `Accept` header. Together they make a handler
+//! independent of the wire format, every format enabled through the `serde-*` features works.
+//!
+//! | Failure                                 | Status                         |
+//! |-----------------------------------------|--------------------------------|
+//! | Missing or unknown `Content-Type`       | `415 Unsupported Media Type`   |
+//! | Body that cannot be decoded             | `400 Bad Request`              |
+//! | No supported format in `Accept`         | `406 Not Acceptable`           |
+//! | Response that cannot be encoded         | `500 Internal Server Error`    |
+//!
+//! # Examples
+//! ```
+//! use axum::{routing::post, Router};
+//! use gearbox::service::negotiate::{Accept, Negotiated, NegotiatedResponse};
+//! use serde_derive::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct User {
+//!     name: String,
+//! }
+//!
+//! async fn echo(accept: Accept, Negotiated(user): Negotiated<User>) -> NegotiatedResponse<User> {
+//!     accept.respond(user)
+//! }
+//!
+//! let app: Router = Router::new().route("/users", post(echo));
+//! ```
+use crate::prelude::serde::{de::DeserializeOwned, Serialize};
+use crate::serde::dynamic::{ContentType, Decoded, SimpleDecoder, SimpleEncoder};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use axum::async_trait;
+use axum::body::Bytes;
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use axum::http::{header, request::Parts, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+
+/// Extracts a `T` from the request body, decoded according to the `Content-Type` header.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Negotiated<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for Negotiated<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = NegotiationRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let content_type = header_content_type(req.headers())?;
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(|e| NegotiationRejection::InvalidBody(e.body_text()))?;
+
+        let decoded: Decoded<T> = body
+            .as_ref()
+            .decode(content_type)
+            .map_err(|e| NegotiationRejection::InvalidBody(e.to_string()))?;
+        Ok(Negotiated(decoded.into()))
+    }
+}
+
+/// The format of the response, picked from the `Accept` header of the request.
+///
+/// Entries are tried in order of their `q` value. For `*/*`, `application/*` or a missing header
+/// the format of the request body is used if it is supported, otherwise JSON, otherwise the first
+/// format of `ContentType::supported`.
+#[derive(Debug)]
+pub struct Accept(pub ContentType);
+
+impl Accept {
+    /// Returns a response that encodes `value` in the accepted format.
+    pub fn respond<T: Serialize>(self, value: T) -> NegotiatedResponse<T> {
+        NegotiatedResponse {
+            content_type: self.0,
+            value,
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Accept
+where
+    S: Send + Sync,
+{
+    type Rejection = NegotiationRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let accept = match parts.headers.get(header::ACCEPT) {
+            Some(accept) => accept.to_str().map_err(|_| {
+                NegotiationRejection::NotAcceptable("invalid Accept header".to_string())
+            })?,
+            None => return default_content_type(&parts.headers).map(Accept),
+        };
+
+        let mut ranges: Vec<(&str, f32)> = accept
+            .split(',')
+            .map(|range| {
+                let mut params = range.split(';');
+                let media_type = params.next().unwrap_or_default().trim();
+                let quality = params
+                    .filter_map(|t| t.trim().strip_prefix("q="))
+                    .find_map(|t| t.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (media_type, quality)
+            })
+            .filter(|(media_type, quality)| !media_type.is_empty() && *quality > 0.0)
+            .collect();
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        for (media_type, _) in ranges {
+            if matches!(media_type, "*/*" | "application/*") {
+                return default_content_type(&parts.headers).map(Accept);
+            }
+            if let Ok(content_type) = ContentType::try_from(media_type) {
+                return Ok(Accept(content_type));
+            }
+        }
+        Err(NegotiationRejection::NotAcceptable(accept.to_string()))
+    }
+}
+
+/// A `T` that is encoded in the format picked by `Accept`.
+#[derive(Debug)]
+pub struct NegotiatedResponse<T> {
+    content_type: ContentType,
+    value: T,
+}
+
+impl<T: Serialize> IntoResponse for NegotiatedResponse<T> {
+    fn into_response(self) -> Response {
+        match self.value.encode(&self.content_type) {
+            Ok(encoded) => {
+                let mime_type = HeaderValue::from_str(&mime_type(&self.content_type))
+                    .unwrap_or(HeaderValue::from_static("application/octet-stream"));
+                ([(header::CONTENT_TYPE, mime_type)], encoded.to_vec()).into_response()
+            }
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to encode response: {}", e),
+            )
+                .into_response(),
+        }
+    }
+}
+
+/// The rejection of `Negotiated` and `Accept`.
+#[derive(Debug)]
+pub enum NegotiationRejection {
+    /// The `Content-Type` of the request is missing or not supported.
+    UnsupportedMediaType(String),
+    /// The request body could not be read or decoded.
+    InvalidBody(String),
+    /// None of the formats in the `Accept` header are supported.
+    NotAcceptable(String),
+}
+
+impl IntoResponse for NegotiationRejection {
+    fn into_response(self) -> Response {
+        match self {
+            Self::UnsupportedMediaType(t) => (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                format!("Unsupported content type: {}", t),
+            ),
+            Self::InvalidBody(t) => (StatusCode::BAD_REQUEST, format!("Invalid body: {}", t)),
+            Self::NotAcceptable(t) => (
+                StatusCode::NOT_ACCEPTABLE,
+                format!("None of the accepted content types are supported: {}", t),
+            ),
+        }
+        .into_response()
+    }
+}
+
+/// Returns the `Content-Type` of the request, ignoring parameters such as `charset`.
+fn header_content_type(headers: &HeaderMap) -> Result<ContentType, NegotiationRejection> {
+    let value = headers
+        .get(header::CONTENT_TYPE)
+        .ok_or(NegotiationRejection::UnsupportedMediaType(
+            "missing Content-Type header".to_string(),
+        ))?
+        .to_str()
+        .map_err(|_| {
+            NegotiationRejection::UnsupportedMediaType("invalid Content-Type header".to_string())
+        })?;
+    let media_type = value.split(';').next().unwrap_or_default().trim();
+    ContentType::try_from(media_type)
+        .map_err(|_| NegotiationRejection::UnsupportedMediaType(value.to_string()))
+}
+
+fn default_content_type(headers: &HeaderMap) -> Result<ContentType, NegotiationRejection> {
+    header_content_type(headers)
+        .ok()
+        .or_else(|| ContentType::try_from("json").ok())
+        .or_else(|| {
+            ContentType::supported()
+                .first()
+                .and_then(|t| ContentType::try_from(t).ok())
+        })
+        .ok_or(NegotiationRejection::NotAcceptable(
+            "no content types are enabled".to_string(),
+        ))
+}
+
+fn mime_type(content_type: &ContentType) -> String {
+    match content_type.as_str() {
+        "csv" => "text/csv".to_string(),
+        token => format!("application/{}", token),
+    }
+}
+
+#[cfg(all(test, feature = "serde-cbor", feature = "serde-yaml"))]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::routing::post;
+    use axum::Router;
+    use http_body_util::BodyExt;
+    use serde_derive::{Deserialize, Serialize};
+    use tower::ServiceExt;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct User {
+        name: String,
+        age: u8,
+    }
+
+    async fn echo(accept: Accept, Negotiated(user): Negotiated<User>) -> NegotiatedResponse<User> {
+        accept.respond(user)
+    }
+
+    async fn send(content_type: Option<&str>, accept: Option<&str>, body: Vec<u8>) -> Response {
+        let mut request = Request::post("/users");
+        if let Some(content_type) = content_type {
+            request = request.header(header::CONTENT_TYPE, content_type);
+        }
+        if let Some(accept) = accept {
+            request = request.header(header::ACCEPT, accept);
+        }
+        Router::new()
+            .route("/users", post(echo))
+            .oneshot(request.body(Body::from(body)).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn body_bytes(response: Response) -> Vec<u8> {
+        response
+            .into_body()
+            .collect()
+            .await
+            .unwrap()
+            .to_bytes()
+            .to_vec()
+    }
+
+    fn alice() -> User {
+        User {
+            name: "alice".to_string(),
+            age: 30,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cbor_request_yaml_response() {
+        let body = serde_cbor::to_vec(&alice()).unwrap();
+        let response = send(
+            Some("application/cbor"),
+            Some("application/xml;q=0.1, application/yaml"),
+            body,
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/yaml");
+        let user: User = serde_yaml::from_slice(&body_bytes(response).await).unwrap();
+        assert_eq!(user, alice());
+    }
+
+    #[tokio::test]
+    async fn test_wildcard_accept_uses_request_format() {
+        let body = serde_cbor::to_vec(&alice()).unwrap();
+        let response = send(Some("application/cbor"), Some("*/*"), body).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/cbor");
+        let user: User = serde_cbor::from_slice(&body_bytes(response).await).unwrap();
+        assert_eq!(user, alice());
+    }
+
+    #[tokio::test]
+    async fn test_rejections() {
+        let body = serde_cbor::to_vec(&alice()).unwrap();
+        let response = send(Some("application/unknown"), None, body.clone()).await;
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let response = send(None, None, body.clone()).await;
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let response = send(Some("application/yaml"), None, b"name: [".to_vec()).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = send(Some("application/cbor"), Some("image/png"), body).await;
+        assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+    }
+}
@@ -0,0 +1,47 @@
+//! Signal handling for graceful shutdowns.
+
+/// Resolves once the process receives `SIGINT` (Ctrl+C) or, on unix, `SIGTERM`.
+///
+/// This is meant to be passed to `axum::serve(...).with_graceful_shutdown(...)`, so in-flight
+/// requests can finish before the server stops. If a signal handler cannot be installed, that
+/// signal is ignored instead of shutting the server down right away.
+///
+/// # Examples
+/// ```no_run
+/// use axum::Router;
+/// use gearbox::service::{health::health_routes, shutdown::shutdown_signal};
+///
+/// # async fn run() -> std::io::Result<()> {
+/// let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
+/// let app: Router = health_routes();
+/// axum::serve(listener, app)
+///     .with_graceful_shutdown(shutdown_signal())
+///     .await
+/// # }
+/// ```
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if tokio::signal::ctrl_c().await.is_err() {
+            core::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                terminate.recv().await;
+            }
+            Err(_) => core::future::pending::<()>().await,
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = core::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
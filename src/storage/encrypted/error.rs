@@ -0,0 +1,66 @@
+use core::fmt::{Debug, Display, Formatter};
+
+pub enum Error<E> {
+    /// `SelectiveStorage::create` was used, which cannot provide an encryption key.
+    NoKeyConfigured,
+    /// The value could not be serialized or deserialized.
+    SerializationError(serde_json::Error),
+    /// The stored value is not valid base64.
+    InvalidEncoding(base64::DecodeError),
+    /// The stored value could not be decrypted, either because it was encrypted with a different
+    /// key or because it was tampered with.
+    DecryptionFailed,
+    /// Encrypting the value failed.
+    EncryptionFailed,
+    /// The wrapped storage backend failed.
+    StorageError(E),
+}
+
+impl<E: Debug> Debug for Error<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::NoKeyConfigured => f.write_str("NoKeyConfigured"),
+            Error::SerializationError(e) => f.debug_tuple("SerializationError").field(e).finish(),
+            Error::InvalidEncoding(e) => f.debug_tuple("InvalidEncoding").field(e).finish(),
+            Error::DecryptionFailed => f.write_str("DecryptionFailed"),
+            Error::EncryptionFailed => f.write_str("EncryptionFailed"),
+            Error::StorageError(e) => f.debug_tuple("StorageError").field(e).finish(),
+        }
+    }
+}
+
+impl<E: Display> Display for Error<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::NoKeyConfigured => write!(f, "No encryption key configured"),
+            Error::SerializationError(e) => write!(f, "Serialization error: {}", e),
+            Error::InvalidEncoding(e) => write!(f, "Invalid encoding: {}", e),
+            Error::DecryptionFailed => write!(f, "Failed to decrypt value"),
+            Error::EncryptionFailed => write!(f, "Failed to encrypt value"),
+            Error::StorageError(e) => write!(f, "Storage error: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: Debug + Display> std::error::Error for Error<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::SerializationError(e) => Some(e),
+            Error::InvalidEncoding(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl<E> From<serde_json::Error> for Error<E> {
+    fn from(e: serde_json::Error) -> Self {
+        Error::SerializationError(e)
+    }
+}
+
+impl<E> From<base64::DecodeError> for Error<E> {
+    fn from(e: base64::DecodeError) -> Self {
+        Error::InvalidEncoding(e)
+    }
+}
@@ -0,0 +1,255 @@
+//! Encryption at rest for storage backends.
+//!
+//! `EncryptedStorage` wraps any `SelectiveStorage` backend and encrypts values with AES-256-GCM
+//! before handing them to it. Every value is serialized to JSON, encrypted with a fresh random
+//! nonce and stored as a base64 string of the nonce followed by the ciphertext. Keys are stored in
+//! plaintext, so the wrapped backend can still look values up, and are authenticated as associated
+//! data, so a value moved to another key fails to decrypt.
+//!
+//! # Examples
+//!
+//! ```
+//! use gearbox::storage::encrypted::EncryptedStorage;
+//! use gearbox::storage::memory::MemoryStorage;
+//! use gearbox::storage::selective_storage::SelectiveStorage;
+//!
+//! let key = [7u8; 32];
+//! let mut storage = EncryptedStorage::new(MemoryStorage::new(), &key);
+//! storage.set("secret_key", &"s3cr3t".to_string()).unwrap();
+//!
+//! let secret: String = storage.get("secret_key").unwrap();
+//! assert_eq!(secret, "s3cr3t");
+//! ```
+pub mod error;
+
+use crate::prelude::serde;
+use crate::storage::selective_storage::SelectiveStorage;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use alloc::{string::String, vec::Vec};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+pub use error::Error;
+
+/// The size in bytes of the AES-GCM nonce stored in front of every ciphertext.
+const NONCE_SIZE: usize = 12;
+
+/// A `SelectiveStorage` wrapper that encrypts values before delegating to `S`.
+pub struct EncryptedStorage<S: SelectiveStorage> {
+    inner: S,
+    cipher: Aes256Gcm,
+}
+
+impl<S: SelectiveStorage> EncryptedStorage<S> {
+    /// Wraps `inner`, encrypting its values with the given 256 bit key.
+    pub fn new(inner: S, key: &[u8; 32]) -> Self {
+        Self {
+            inner,
+            cipher: Aes256Gcm::new(key.into()),
+        }
+    }
+
+    /// Returns the wrapped backend, which only holds encrypted values.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Consumes the wrapper and returns the wrapped backend.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn encrypt<T: serde::Serialize>(
+        &self,
+        key: &str,
+        value: &T,
+    ) -> Result<String, Error<S::Error>> {
+        let plaintext = serde_json::to_vec(value)?;
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let payload = Payload {
+            msg: plaintext.as_slice(),
+            aad: key.as_bytes(),
+        };
+        self.cipher
+            .encrypt(&nonce, payload)
+            .map_err(|_| Error::EncryptionFailed)
+            .map(|ciphertext| {
+                let mut payload = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
+                payload.extend_from_slice(&nonce);
+                payload.extend_from_slice(&ciphertext);
+                STANDARD.encode(payload)
+            })
+    }
+
+    fn decrypt<T: serde::de::DeserializeOwned>(
+        &self,
+        key: &str,
+        payload: &str,
+    ) -> Result<T, Error<S::Error>> {
+        let payload = STANDARD.decode(payload)?;
+        if payload.len() < NONCE_SIZE {
+            return Err(Error::DecryptionFailed);
+        }
+        let (nonce, ciphertext) = payload.split_at(NONCE_SIZE);
+        self.cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: key.as_bytes(),
+                },
+            )
+            .map_err(|_| Error::DecryptionFailed)
+            .and_then(|plaintext| serde_json::from_slice(&plaintext).map_err(Error::from))
+    }
+}
+
+impl<S: SelectiveStorage> SelectiveStorage for EncryptedStorage<S> {
+    type Error = Error<S::Error>;
+
+    fn get<T: serde::de::DeserializeOwned>(&self, key: &str) -> Result<T, Self::Error> {
+        self.inner
+            .get::<String>(key)
+            .map_err(Error::StorageError)
+            .and_then(|payload| self.decrypt(key, &payload))
+    }
+
+    fn set<T: serde::Serialize>(&mut self, key: &str, value: &T) -> Result<(), Self::Error> {
+        self.encrypt(key, value)
+            .and_then(|payload| self.inner.set(key, &payload).map_err(Error::StorageError))
+    }
+
+    fn set_with_ttl<T: serde::Serialize>(
+        &mut self,
+        key: &str,
+        value: &T,
+        ttl: core::time::Duration,
+    ) -> Result<(), Self::Error> {
+        self.encrypt(key, value).and_then(|payload| {
+            self.inner
+                .set_with_ttl(key, &payload, ttl)
+                .map_err(Error::StorageError)
+        })
+    }
+
+    fn del<T: serde::Serialize>(&mut self, key: &str) -> Result<(), Self::Error> {
+        self.inner.del::<String>(key).map_err(Error::StorageError)
+    }
+
+    /// Always fails with `Error::NoKeyConfigured`, use `EncryptedStorage::new` instead.
+    fn create() -> Result<Self, Self::Error> {
+        Err(Error::NoKeyConfigured)
+    }
+}
+
+#[cfg(all(test, feature = "storage-memory"))]
+mod tests {
+    use super::*;
+    use crate::storage::memory::MemoryStorage;
+    use crate::storage::selective_storage::Error as StorageError;
+    use alloc::string::ToString;
+
+    const KEY: [u8; 32] = [42u8; 32];
+
+    #[test]
+    fn test_encrypted_storage_round_trip() {
+        let mut storage = EncryptedStorage::new(MemoryStorage::new(), &KEY);
+        storage.set("secret_key", &"s3cr3t").unwrap();
+        storage.set("attempts", &3).unwrap();
+
+        assert_eq!(storage.get::<String>("secret_key").unwrap(), "s3cr3t");
+        assert_eq!(storage.get::<u32>("attempts").unwrap(), 3);
+
+        // The wrapped backend only sees ciphertext under the plaintext key.
+        let stored: String = storage.inner().get("secret_key").unwrap();
+        assert!(!stored.contains("s3cr3t"));
+        let raw = STANDARD.decode(&stored).unwrap();
+        assert!(raw.len() > NONCE_SIZE);
+        assert!(!raw.windows(6).any(|t| t == b"s3cr3t"));
+
+        // Every write uses a fresh nonce.
+        storage.set("secret_key", &"s3cr3t").unwrap();
+        assert_ne!(storage.inner().get::<String>("secret_key").unwrap(), stored);
+    }
+
+    #[test]
+    fn test_encrypted_storage_wrong_key() {
+        let mut storage = EncryptedStorage::new(MemoryStorage::new(), &KEY);
+        storage.set("secret_key", &"s3cr3t").unwrap();
+
+        let storage = EncryptedStorage::new(storage.into_inner(), &[1u8; 32]);
+        assert!(matches!(
+            storage.get::<String>("secret_key"),
+            Err(Error::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_encrypted_storage_swapped_values() {
+        let mut storage = EncryptedStorage::new(MemoryStorage::new(), &KEY);
+        storage.set("admin_token", &"admin").unwrap();
+        storage.set("guest_token", &"guest").unwrap();
+
+        let admin: String = storage.inner().get("admin_token").unwrap();
+        let guest: String = storage.inner().get("guest_token").unwrap();
+        let mut inner = storage.into_inner();
+        inner.set("admin_token", &guest).unwrap();
+        inner.set("guest_token", &admin).unwrap();
+
+        let storage = EncryptedStorage::new(inner, &KEY);
+        assert!(matches!(
+            storage.get::<String>("admin_token"),
+            Err(Error::DecryptionFailed)
+        ));
+        assert!(matches!(
+            storage.get::<String>("guest_token"),
+            Err(Error::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_encrypted_storage_error() {
+        let error: Error<StorageError> = Error::StorageError(StorageError::NotFound("key".into()));
+        assert!(alloc::format!("{:?}", error).starts_with("StorageError("));
+        assert_eq!(
+            alloc::format!("{:?}", Error::<StorageError>::DecryptionFailed),
+            "DecryptionFailed"
+        );
+
+        let error: Error<StorageError> = serde_json::from_str::<u32>("x")
+            .map_err(Error::from)
+            .unwrap_err();
+        let error: &dyn std::error::Error = &error;
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn test_encrypted_storage_invalid_payload() {
+        let mut inner = MemoryStorage::new();
+        inner.set("plain", &"not base64!").unwrap();
+        inner.set("short", &STANDARD.encode([0u8; 4])).unwrap();
+
+        let storage = EncryptedStorage::new(inner, &KEY);
+        assert!(matches!(
+            storage.get::<String>("plain"),
+            Err(Error::InvalidEncoding(_))
+        ));
+        assert!(matches!(
+            storage.get::<String>("short"),
+            Err(Error::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_encrypted_storage_delete_and_missing() {
+        let mut storage = EncryptedStorage::new(MemoryStorage::new(), &KEY);
+        storage.set("secret_key", &"s3cr3t".to_string()).unwrap();
+        storage.del::<String>("secret_key").unwrap();
+
+        assert!(matches!(
+            storage.get::<String>("secret_key"),
+            Err(Error::StorageError(StorageError::NotFound(_)))
+        ));
+        assert!(EncryptedStorage::<MemoryStorage>::create().is_err());
+    }
+}
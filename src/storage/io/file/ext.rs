@@ -3,7 +3,13 @@ use crate::common::TryDefault;
 use crate::path;
 use crate::storage::io::file::error::Error;
 use crate::storage::{json::JsonExt, yaml::YamlExt, KeyStoreExt};
+use crate::time::DateTime;
+use alloc::vec::Vec;
 use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::ffi::OsString;
+use std::io::ErrorKind;
+use std::path::PathBuf;
 
 #[cfg(feature = "storage-json-ext")]
 impl JsonExt for File {
@@ -35,57 +41,148 @@ impl YamlExt for File {
     }
 }
 
+impl File {
+    /// Path of the sidecar file that maps the keys stored with a TTL to their expiry, in
+    /// milliseconds since the Unix epoch. Keeping the expiries out of the document means they can
+    /// never collide with a stored key.
+    fn expires_path(&self) -> Result<PathBuf, Error> {
+        let path = &self.file_access_data.path;
+        let mut name = OsString::from(".");
+        name.push(path.file_name().ok_or(Error::NoPath)?);
+        name.push(".expires.json");
+        Ok(path.with_file_name(name))
+    }
+
+    fn read_expires(&self) -> Result<Map<String, Value>, Error> {
+        self.expires_path()
+            .and_then(|path| match std::fs::read(path) {
+                Ok(t) => serde_json::from_slice(&t).map_err(Error::from),
+                Err(e) if e.kind() == ErrorKind::NotFound => Ok(Map::new()),
+                Err(e) => Err(Error::from(e)),
+            })
+    }
+
+    fn write_expires(&self, expires: &Map<String, Value>) -> Result<(), Error> {
+        let path = self.expires_path()?;
+        if expires.is_empty() {
+            return match std::fs::remove_file(path) {
+                Err(e) if e.kind() != ErrorKind::NotFound => Err(Error::from(e)),
+                _ => Ok(()),
+            };
+        }
+        serde_json::to_vec(expires)
+            .map_err(Error::from)
+            .and_then(|t| Self::write_atomic(&path, &t))
+    }
+
+    /// Applies `update` to the document with its expired entries removed, then persists both the
+    /// document and the expiries.
+    fn update_entries<R>(
+        &self,
+        key: &str,
+        update: impl FnOnce(&mut Map<String, Value>, &mut Map<String, Value>) -> Result<R, Error>,
+    ) -> Result<R, Error> {
+        let mut expires = self.read_expires()?;
+        let had_expires = !expires.is_empty();
+        let mut json: Value = self.get_json()?;
+        let map = json
+            .as_object_mut()
+            .ok_or(Error::NoFileConfigured(key.to_string()))?;
+        remove_expired(map, &mut expires, &DateTime::now());
+        let result = update(map, &mut expires)?;
+        serde_json::to_vec(&json)
+            .map_err(Error::from)
+            .and_then(|t| self.replace_contents(&t))?;
+        if had_expires || !expires.is_empty() {
+            self.write_expires(&expires)?;
+        }
+        Ok(result)
+    }
+
+    fn store_entry<T: serde::Serialize>(
+        &mut self,
+        key: &str,
+        value: &T,
+        expires_at: Option<DateTime>,
+    ) -> Result<(), Error> {
+        let value = serde_json::to_value(value)?;
+        self.update_entries(key, |map, expires| {
+            match expires_at {
+                Some(t) => expires.insert(key.to_string(), Value::from(t.as_millis_since_epoch())),
+                None => expires.remove(key),
+            };
+            map.insert(key.to_string(), value);
+            Ok(())
+        })
+    }
+}
+
+fn is_expired(expires: &Map<String, Value>, key: &str, now: &DateTime) -> bool {
+    expires
+        .get(key)
+        .and_then(Value::as_i64)
+        .is_some_and(|t| t <= now.as_millis_since_epoch())
+}
+
+fn remove_expired(map: &mut Map<String, Value>, expires: &mut Map<String, Value>, now: &DateTime) {
+    let expired = expires
+        .keys()
+        .filter(|t| is_expired(expires, t, now))
+        .cloned()
+        .collect::<Vec<_>>();
+    for key in expired {
+        expires.remove(&key);
+        map.remove(&key);
+    }
+}
+
 impl KeyStoreExt for File
 where
     Self: JsonExt,
 {
     type Error = Error;
+
+    /// Returns the value stored for `key`. An expired value is reported as missing and removed
+    /// from the file. The removal is best effort, so a file that can't be written to still reads.
     fn get<T: serde::de::DeserializeOwned>(&self, key: &str) -> Result<T, Self::Error> {
-        self.get_json()
-            .and_then(|t: serde_json::Value| {
-                t.get(key)
+        self.read_expires()
+            .and_then(
+                |expires| match is_expired(&expires, key, &DateTime::now()) {
+                    true => {
+                        let _ = self.update_entries(key, |_, _| Ok(()));
+                        Err(Error::NoFileConfigured(key.to_string()))
+                    }
+                    false => self.get_json(),
+                },
+            )
+            .and_then(|json: Value| {
+                json.get(key)
                     .cloned()
                     .ok_or(Error::NoFileConfigured(key.to_string()))
             })
-            .and_then(|t| serde_json::from_value::<T>(t.clone()).map_err(Error::from))
+            .and_then(|t| serde_json::from_value::<T>(t).map_err(Error::from))
     }
 
     fn set<T: serde::Serialize>(&mut self, key: &str, value: &T) -> Result<(), Self::Error> {
-        self.get_json()
-            .and_then(|mut json: serde_json::Value| {
-                serde_json::to_value(value)
-                    .map_err(Error::from)
-                    .and_then(|t| {
-                        json.as_object_mut()
-                            .ok_or(Error::NoFileConfigured(key.to_string()))
-                            .and_then(|map| {
-                                map.insert(key.to_string(), t);
-                                Ok(())
-                            })
-                    })
-            })
-            .and_then(|t| serde_json::to_string(&t).map_err(Error::from))
-            .and_then(|t| self.write_str_to_file(&t))
+        self.store_entry(key, value, None)
+    }
+
+    fn set_with_ttl<T: serde::Serialize>(
+        &mut self,
+        key: &str,
+        value: &T,
+        ttl: core::time::Duration,
+    ) -> Result<(), Self::Error> {
+        self.store_entry(key, value, Some(DateTime::now() + ttl))
     }
 
     fn del<T: DeserializeOwned>(&mut self, key: &str) -> Result<T, Self::Error> {
-        self.get_json()
-            .and_then(|mut json: serde_json::Value| {
-                json.as_object_mut()
-                    .ok_or(Error::NoFileConfigured(key.to_string()))
-                    .and_then(|map| {
-                        map.remove(key)
-                            .ok_or(Error::NoFileConfigured(key.to_string()))
-                            .and_then(|t| serde_json::from_value::<T>(t).map_err(Error::from))
-                    })
-                    .map(|t: T| (t, json))
-            })
-            .and_then(|(t, json)| {
-                serde_json::to_string(&json)
-                    .map_err(Error::from)
-                    .and_then(|t| self.write_str_to_file(&t))
-                    .map(|_| t)
-            })
+        self.update_entries(key, |map, expires| {
+            expires.remove(key);
+            map.remove(key)
+                .ok_or(Error::NoFileConfigured(key.to_string()))
+                .and_then(|t| serde_json::from_value::<T>(t).map_err(Error::from))
+        })
     }
 }
 
@@ -97,8 +194,9 @@ impl TryDefault for File {
 }
 #[cfg(test)]
 mod tests {
-    use super::{File, KeyStoreExt};
+    use super::{Error, File, KeyStoreExt};
     use crate::storage::json::JsonExt;
+    use core::time::Duration;
     use std::fs;
     use std::path::PathBuf;
 
@@ -144,6 +242,110 @@ mod tests {
         fs::remove_file(path).unwrap();
     }
 
+    #[test]
+    fn test_file_key_value() {
+        let path =
+            PathBuf::from("/tmp/rust-test/storage/io/file/ext/tests/test_file_key_value.json");
+        let mut file = File::new(path.clone(), None);
+        file.write_str_to_file("{}").unwrap();
+
+        file.set("token", &"abc").unwrap();
+        file.set("count", &3).unwrap();
+        assert_eq!(file.get::<String>("token").unwrap(), "abc");
+        assert_eq!(file.get::<u32>("count").unwrap(), 3);
+
+        assert_eq!(file.del::<String>("token").unwrap(), "abc");
+        assert!(matches!(
+            file.get::<String>("token"),
+            Err(Error::NoFileConfigured(_))
+        ));
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_file_ttl() {
+        let dir = PathBuf::from("/tmp/rust-test/storage/io/file/ext/tests/ttl");
+        fs::remove_dir_all(&dir).ok();
+        let path = dir.join("test_file_ttl.json");
+        let expires_path = dir.join(".test_file_ttl.json.expires.json");
+        let mut file = File::new(path.clone(), None);
+        file.write_str_to_file("{}").unwrap();
+
+        file.set_with_ttl("token", &"abc", Duration::from_millis(50))
+            .unwrap();
+        file.set_with_ttl("refresh", &"def", Duration::from_secs(60))
+            .unwrap();
+        assert_eq!(file.get::<String>("token").unwrap(), "abc");
+
+        // The expiries are kept out of the document.
+        let json: serde_json::Value = file.get_json().unwrap();
+        assert_eq!(json, serde_json::json!({"token": "abc", "refresh": "def"}));
+        assert!(expires_path.exists());
+
+        std::thread::sleep(Duration::from_millis(80));
+        assert!(matches!(
+            file.get::<String>("token"),
+            Err(Error::NoFileConfigured(_))
+        ));
+        assert_eq!(file.get::<String>("refresh").unwrap(), "def");
+
+        // Reading the expired value removes it together with its expiry.
+        let json: serde_json::Value = file.get_json().unwrap();
+        assert_eq!(json, serde_json::json!({"refresh": "def"}));
+        let expires: serde_json::Value =
+            serde_json::from_slice(&fs::read(&expires_path).unwrap()).unwrap();
+        assert!(expires.get("token").is_none());
+        assert!(expires.get("refresh").is_some());
+
+        // A plain set drops the expiry of the key, and with it the sidecar file.
+        file.set("refresh", &"ghi").unwrap();
+        assert!(!expires_path.exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_ttl_user_key_does_not_collide() {
+        let dir = PathBuf::from("/tmp/rust-test/storage/io/file/ext/tests/ttl_collision");
+        fs::remove_dir_all(&dir).ok();
+        let mut file = File::new(dir.join("test_file_ttl.json"), None);
+        file.write_str_to_file("{}").unwrap();
+
+        file.set("$expires", &"user value").unwrap();
+        file.set_with_ttl("token", &"abc", Duration::from_secs(60))
+            .unwrap();
+        assert_eq!(file.get::<String>("$expires").unwrap(), "user value");
+        assert_eq!(file.del::<String>("$expires").unwrap(), "user value");
+        assert_eq!(file.get::<String>("token").unwrap(), "abc");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    struct Unserializable;
+
+    impl serde::Serialize for Unserializable {
+        fn serialize<S: serde::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+            Err(serde::ser::Error::custom("not serializable"))
+        }
+    }
+
+    #[test]
+    fn test_file_failed_serialization_keeps_file() {
+        let path = PathBuf::from(
+            "/tmp/rust-test/storage/io/file/ext/tests/test_file_failed_serialization.json",
+        );
+        let mut file = File::new(path.clone(), None);
+        let content = r#"{"key":"value"}"#;
+        file.write_str_to_file(content).unwrap();
+
+        assert!(file.set_json(&Unserializable).is_err());
+        assert!(file.set("other", &Unserializable).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+
+        fs::remove_file(path).unwrap();
+    }
+
     #[test]
     #[ignore]
     fn test_file_error_handling() {
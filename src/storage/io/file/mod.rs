@@ -1,15 +1,20 @@
 use crate::rails::ext::syn::if_then::RailsIfExt;
 use crate::rails::ext::syn::map_into::RailsMapErrInto;
 use crate::rails::tracing::common::RailsLog;
-use alloc::{string::String, vec::Vec};
+use alloc::{format, string::String, vec::Vec};
 use libc::FILE;
 use spin::{Mutex, MutexGuard};
-use std::fs::{create_dir_all, File as StdFile, OpenOptions};
-use std::io::{Error, Read, Write};
+use std::ffi::OsString;
+use std::fs::{create_dir_all, remove_file, rename, File as StdFile, OpenOptions};
+use std::io::{Read, Seek, Write};
 use std::ops::DerefMut;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub mod error;
+
+/// Distinguishes the temporary files of concurrent atomic writes within this process.
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
 pub mod ext;
 
 pub struct FileWrapper {
@@ -105,31 +110,33 @@ impl File {
 
     pub fn contents(&self) -> Result<Vec<u8>, error::Error> {
         self.wrapper()
-            .file(true, true, true)
+            .file(true, false, false)
             .and_then(|mut t| Self::contents_map(t).map_err_into())
             .log(crate::error!(Err))
     }
 
     pub fn contents_string(&self) -> Result<String, error::Error> {
         self.wrapper()
-            .file(true, true, true)
+            .file(true, false, false)
             .and_then(|t| Self::contents_string_map(t).map_err_into())
             .log(crate::error!(Err))
     }
 
+    /// Replaces the contents of the file with `content`.
+    ///
+    /// The content is written to a sibling temporary file which is synced to disk and then renamed
+    /// over the target, so readers observe either the previous or the new document, never a
+    /// partially written one.
     pub fn write_to_file(
         &mut self,
         content: &[u8],
     ) -> Result<(), crate::storage::io::file::error::Error> {
-        self.wrapper()
-            .file(false, true, true)
-            .and_then(|t| t.write_all(content).map_err(error::Error::from))
+        self.replace_contents(content)
     }
 
+    /// Replaces the contents of the file with `content`, see `File::write_to_file`.
     pub fn write_str_to_file(&mut self, content: &str) -> Result<(), error::Error> {
-        self.wrapper()
-            .file(false, true, true)
-            .and_then(|t| t.write_all(content.as_bytes()).map_err(error::Error::from))
+        self.write_to_file(content.as_bytes())
     }
 
     pub fn exists(&self) -> bool {
@@ -139,13 +146,70 @@ impl File {
     fn wrapper(&self) -> MutexGuard<'_, FileWrapper> {
         self.inner.lock()
     }
+    fn replace_contents(&self, content: &[u8]) -> Result<(), error::Error> {
+        let mut wrapper = self.wrapper();
+        // The open handle still points to the replaced file after the rename.
+        wrapper.file = None;
+        Self::write_atomic(&wrapper.path, content)
+    }
+    fn write_atomic(path: &Path, content: &[u8]) -> Result<(), error::Error> {
+        let parent = path
+            .parent()
+            .filter(|t| !t.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        let file_name = path.file_name().ok_or(error::Error::NoPath)?;
+        create_dir_all(parent)?;
+
+        let mut temp_name = OsString::from(".");
+        temp_name.push(file_name);
+        temp_name.push(format!(
+            ".{}.{}.tmp",
+            std::process::id(),
+            TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let temp_path = parent.join(temp_name);
+
+        let mut temp = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp_path)?;
+        let result = std::fs::metadata(path)
+            .map(|t| Some(t.permissions()))
+            .or_else(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => Ok(None),
+                _ => Err(e),
+            })
+            .and_then(|t| match t {
+                Some(permissions) => temp.set_permissions(permissions),
+                None => Ok(()),
+            })
+            .and_then(|_| temp.write_all(content))
+            .and_then(|_| temp.sync_all())
+            .and_then(|_| {
+                drop(temp);
+                rename(&temp_path, path)
+            });
+        if result.is_err() {
+            remove_file(&temp_path).ok();
+        }
+        result?;
+
+        // Persist the rename itself, a failure here does not invalidate the written document.
+        #[cfg(unix)]
+        StdFile::open(parent).and_then(|t| t.sync_all()).ok();
+        Ok(())
+    }
     fn contents_map(t: &mut StdFile) -> Result<Vec<u8>, std::io::Error> {
         let mut contents = Vec::new();
-        t.read_to_end(&mut contents).map(|_| contents)
+        t.rewind()
+            .and_then(|_| t.read_to_end(&mut contents))
+            .map(|_| contents)
     }
     fn contents_string_map(t: &mut StdFile) -> Result<String, std::io::Error> {
         let mut contents = String::new();
-        t.read_to_string(&mut contents).map(|_| contents)
+        t.rewind()
+            .and_then(|_| t.read_to_string(&mut contents))
+            .map(|_| contents)
     }
 }
 
@@ -212,6 +276,99 @@ mod tests {
         fs::remove_file(path).unwrap();
     }
 
+    #[test]
+    fn test_file_atomic_write() {
+        let dir = PathBuf::from("/tmp/rust-test/storage/io/file/tests/atomic_write");
+        fs::remove_dir_all(&dir).ok();
+        let path = dir.join("document.json");
+        let mut file = File::new(path.clone(), None);
+        let large = format!("[{}0]", "1234567890,".repeat(200_000));
+
+        file.write_str_to_file(&large)
+            .expect("Failed to write to file");
+        assert_eq!(file.contents_string().unwrap(), large);
+
+        // A shorter document fully replaces the previous one.
+        file.write_str_to_file(r#"{"key": "value"}"#)
+            .expect("Failed to write to file");
+        assert_eq!(file.contents_string().unwrap(), r#"{"key": "value"}"#);
+
+        let entries = fs::read_dir(&dir)
+            .unwrap()
+            .map(|t| t.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(entries, vec!["document.json".to_string()]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_file_atomic_write_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = PathBuf::from("/tmp/rust-test/storage/io/file/tests/atomic_write_permissions");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("document.json");
+        fs::write(&path, "{}").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+
+        let mut file = File::new(path.clone(), None);
+        file.write_str_to_file(r#"{"key": "value"}"#)
+            .expect("Failed to write to file");
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(fs::read_to_string(&path).unwrap(), r#"{"key": "value"}"#);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_concurrent_atomic_writes() {
+        let dir = PathBuf::from("/tmp/rust-test/storage/io/file/tests/atomic_write_concurrent");
+        fs::remove_dir_all(&dir).ok();
+        let path = dir.join("document.json");
+
+        let handles = (0..8)
+            .map(|t| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    let mut file = File::new(path, None);
+                    file.write_str_to_file(&format!("{{\"writer\": {}}}", t))
+                        .is_ok()
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            assert!(handle.join().unwrap(), "Failed to write to file");
+        }
+
+        let entries = fs::read_dir(&dir).unwrap().count();
+        assert_eq!(entries, 1);
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .starts_with("{\"writer\": "));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_read_twice() {
+        let path = PathBuf::from("/tmp/rust-test/storage/io/file/tests/read_twice.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, r#"{"key": "value"}"#).unwrap();
+        let file = File::new(path.clone(), None);
+
+        // The open handle is reused, so every read has to start at the beginning again.
+        assert_eq!(file.contents_string().unwrap(), r#"{"key": "value"}"#);
+        assert_eq!(file.contents_string().unwrap(), r#"{"key": "value"}"#);
+        assert_eq!(file.contents().unwrap(), br#"{"key": "value"}"#);
+
+        fs::remove_file(path).unwrap();
+    }
+
     #[test]
     fn test_file_manual_create_and_simplefile_read() {
         let path = PathBuf::from("/tmp/test_file_manual-1.json");
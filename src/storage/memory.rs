@@ -0,0 +1,246 @@
+//! An in-memory storage backend.
+//!
+//! `MemoryStorage` keeps every value as a JSON document in a `HashMap`, so values go through the
+//! same serialization as the file backend without touching the disk or the browser. This makes it
+//! a drop-in replacement for tests of code that is generic over `SelectiveStorage`.
+//!
+//! # Examples
+//!
+//! ```
+//! use gearbox::storage::memory::MemoryStorage;
+//! use gearbox::storage::selective_storage::SelectiveStorage;
+//!
+//! let mut storage = MemoryStorage::new();
+//! storage.set("token", &"abc".to_string()).unwrap();
+//! let token: String = storage.get("token").unwrap();
+//! assert_eq!(token, "abc");
+//! ```
+use crate::prelude::serde;
+use crate::storage::selective_storage::{Error, SelectiveStorage};
+use alloc::string::{String, ToString};
+use hashbrown::HashMap;
+use serde_json::Value;
+use spin::Mutex;
+use std::time::Instant;
+
+/// A `SelectiveStorage` backend that keeps its values in memory.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    value: Value,
+    /// Taken from the monotonic clock, so changes of the system time don't affect the expiry.
+    expires_at: Option<Instant>,
+}
+
+impl Entry {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+impl MemoryStorage {
+    /// Creates an empty storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether a value that has not expired is stored for `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.live_value(key).is_some()
+    }
+
+    /// Returns the number of stored values that have not expired.
+    pub fn len(&self) -> usize {
+        let now = Instant::now();
+        self.entries
+            .lock()
+            .values()
+            .filter(|t| !t.is_expired(now))
+            .count()
+    }
+
+    /// Returns whether the storage holds no values that have not expired.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value stored for `key`, removing it if it has expired.
+    fn live_value(&self, key: &str) -> Option<Value> {
+        let mut entries = self.entries.lock();
+        match entries.get(key) {
+            Some(entry) if entry.is_expired(Instant::now()) => {
+                entries.remove(key);
+                None
+            }
+            entry => entry.map(|t| t.value.clone()),
+        }
+    }
+
+    fn insert<T: serde::Serialize>(
+        &mut self,
+        key: &str,
+        value: &T,
+        expires_at: Option<Instant>,
+    ) -> Result<(), Error> {
+        serde_json::to_value(value)
+            .map_err(Error::SerializationError)
+            .map(|value| {
+                self.entries
+                    .get_mut()
+                    .insert(key.to_string(), Entry { value, expires_at });
+            })
+    }
+}
+
+impl Clone for MemoryStorage {
+    fn clone(&self) -> Self {
+        Self {
+            entries: Mutex::new(self.entries.lock().clone()),
+        }
+    }
+}
+
+impl SelectiveStorage for MemoryStorage {
+    type Error = Error;
+
+    /// Returns the value stored for `key`, or `Error::NotFound` if there is none or it expired.
+    fn get<T: serde::de::DeserializeOwned>(&self, key: &str) -> Result<T, Self::Error> {
+        self.live_value(key)
+            .ok_or(Error::NotFound(key.to_string()))
+            .and_then(|t| serde_json::from_value::<T>(t).map_err(Error::SerializationError))
+    }
+
+    fn set<T: serde::Serialize>(&mut self, key: &str, value: &T) -> Result<(), Self::Error> {
+        self.insert(key, value, None)
+    }
+
+    fn set_with_ttl<T: serde::Serialize>(
+        &mut self,
+        key: &str,
+        value: &T,
+        ttl: core::time::Duration,
+    ) -> Result<(), Self::Error> {
+        // A ttl too large to represent never expires.
+        self.insert(key, value, Instant::now().checked_add(ttl))
+    }
+
+    /// Removes the value stored for `key`, or returns `Error::NotFound` if there is none or it
+    /// expired.
+    fn del<T: serde::Serialize>(&mut self, key: &str) -> Result<(), Self::Error> {
+        let now = Instant::now();
+        self.entries
+            .get_mut()
+            .remove(key)
+            .filter(|entry| !entry.is_expired(now))
+            .map(|_| ())
+            .ok_or(Error::NotFound(key.to_string()))
+    }
+
+    fn create() -> Result<Self, Self::Error> {
+        Ok(Self::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::time::Duration;
+    use serde_derive::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Credentials {
+        key_id: String,
+        scopes: Vec<String>,
+    }
+
+    #[test]
+    fn test_memory_storage_get_set() {
+        let mut storage = MemoryStorage::create().unwrap();
+        let credentials = Credentials {
+            key_id: "key-1".to_string(),
+            scopes: vec!["read".to_string(), "write".to_string()],
+        };
+
+        storage.set("credentials", &credentials).unwrap();
+        storage.set("counter", &3).unwrap();
+
+        let retrieved: Credentials = storage.get("credentials").unwrap();
+        assert_eq!(retrieved, credentials);
+        assert_eq!(storage.get::<u32>("counter").unwrap(), 3);
+        assert_eq!(storage.len(), 2);
+    }
+
+    #[test]
+    fn test_memory_storage_missing_key() {
+        let storage = MemoryStorage::new();
+        let result = storage.get::<String>("missing");
+        assert!(matches!(result, Err(Error::NotFound(ref key)) if key == "missing"));
+    }
+
+    #[test]
+    fn test_memory_storage_type_mismatch() {
+        let mut storage = MemoryStorage::new();
+        storage.set("counter", &"three").unwrap();
+        let result = storage.get::<u32>("counter");
+        assert!(matches!(result, Err(Error::SerializationError(_))));
+    }
+
+    #[test]
+    fn test_memory_storage_ttl() {
+        let mut storage = MemoryStorage::new();
+        storage
+            .set_with_ttl("token", &"abc", Duration::from_millis(50))
+            .unwrap();
+        storage
+            .set_with_ttl("refresh", &"def", Duration::from_secs(60))
+            .unwrap();
+        assert_eq!(storage.get::<String>("token").unwrap(), "abc");
+
+        std::thread::sleep(Duration::from_millis(80));
+        assert_eq!(storage.len(), 1);
+        assert!(matches!(
+            storage.get::<String>("token"),
+            Err(Error::NotFound(_))
+        ));
+        assert!(!storage.contains_key("token"));
+        assert_eq!(storage.len(), 1);
+        assert_eq!(storage.get::<String>("refresh").unwrap(), "def");
+
+        // A plain set replaces the entry together with its expiry.
+        storage
+            .set_with_ttl("refresh", &"ghi", Duration::from_millis(1))
+            .unwrap();
+        storage.set("refresh", &"jkl").unwrap();
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(storage.get::<String>("refresh").unwrap(), "jkl");
+
+        storage
+            .set_with_ttl("forever", &"mno", Duration::MAX)
+            .unwrap();
+        assert_eq!(storage.get::<String>("forever").unwrap(), "mno");
+    }
+
+    #[test]
+    fn test_memory_storage_delete() {
+        let mut storage = MemoryStorage::new();
+        storage.set("token", &"abc").unwrap();
+        assert!(storage.contains_key("token"));
+
+        storage.del::<String>("token").unwrap();
+        assert!(storage.is_empty());
+        assert!(matches!(
+            storage.get::<String>("token"),
+            Err(Error::NotFound(_))
+        ));
+        assert!(matches!(
+            storage.del::<String>("token"),
+            Err(Error::NotFound(_))
+        ));
+    }
+}
@@ -3,9 +3,13 @@ use crate::rails::ext::syn::RailsMapErrInto;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
+#[cfg(feature = "storage-encrypted")]
+pub mod encrypted;
 #[cfg(all(not(target_arch = "wasm32"), feature = "storage-io"))]
 pub mod io;
 pub mod json;
+#[cfg(feature = "storage-memory")]
+pub mod memory;
 #[cfg(all(target_arch = "wasm32", feature = "storage-web"))]
 pub mod web;
 pub mod yaml;
@@ -16,6 +20,16 @@ pub trait KeyStoreExt {
     type Error;
     fn get<T: DeserializeOwned>(&self, key: &str) -> Result<T, Self::Error>;
     fn set<T: Serialize>(&mut self, key: &str, value: &T) -> Result<(), Self::Error>;
+    /// Stores `value` so that `get` no longer returns it once `ttl` has elapsed. Stores that do
+    /// not support expiry keep the value indefinitely.
+    fn set_with_ttl<T: Serialize>(
+        &mut self,
+        key: &str,
+        value: &T,
+        _ttl: core::time::Duration,
+    ) -> Result<(), Self::Error> {
+        self.set(key, value)
+    }
     fn del<T: DeserializeOwned>(&mut self, key: &str) -> Result<T, Self::Error>;
     fn create<E: Into<Self::Error>, S: KeyStoreExt + TryDefault<Error = E>>(
     ) -> Result<S, Self::Error> {
@@ -19,6 +19,16 @@ where
     type Error;
     fn get<T: serde::de::DeserializeOwned>(&self, key: &str) -> Result<T, Self::Error>;
     fn set<T: serde::Serialize>(&mut self, key: &str, value: &T) -> Result<(), Self::Error>;
+    /// Stores `value` so that `get` returns the not-found result once `ttl` has elapsed. Backends
+    /// that do not support expiry keep the value indefinitely.
+    fn set_with_ttl<T: serde::Serialize>(
+        &mut self,
+        key: &str,
+        value: &T,
+        _ttl: core::time::Duration,
+    ) -> Result<(), Self::Error> {
+        self.set(key, value)
+    }
     fn del<T: serde::Serialize>(&mut self, key: &str) -> Result<(), Self::Error>;
     fn create() -> Result<Self, Self::Error>;
 }
@@ -39,6 +49,17 @@ where
             .map_err_box_into()
     }
 
+    fn set_with_ttl<T: serde::Serialize>(
+        &mut self,
+        key: &str,
+        value: &T,
+        ttl: core::time::Duration,
+    ) -> Result<(), Self::Error> {
+        S::create()
+            .and_then(|mut t: S| t.set_with_ttl(key, value, ttl))
+            .map_err_box_into()
+    }
+
     fn del<T: serde::Serialize>(&mut self, key: &str) -> Result<(), Self::Error> {
         S::create()
             .and_then(|mut t: S| t.del(key))
@@ -49,11 +70,13 @@ where
     }
 }
 
+#[non_exhaustive]
 pub enum Error {
     NoFileConfigured(String),
     #[cfg(all(feature = "with_serde", feature = "with_json"))]
     SerializationError(JsonError),
     FailedToGetMutableInstance,
+    NotFound(String),
     UnderlyingLayerError(Box<dyn crate::error::tracer::ErrorDebug>),
 }
 
@@ -70,6 +93,7 @@ impl Display for Error {
             #[cfg(all(feature = "with_serde", feature = "with_json"))]
             Error::SerializationError(e) => write!(f, "Serialization error: {}", e),
             Error::FailedToGetMutableInstance => write!(f, "Failed to get mutable instance"),
+            Error::NotFound(key) => write!(f, "No data with key: {}", key),
             Error::UnderlyingLayerError(e) => write!(f, "Underlying layer error: {:?}", e),
         }
     }
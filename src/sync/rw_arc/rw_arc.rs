@@ -70,6 +70,30 @@ impl<T: ?Sized, R: RelaxStrategy> RwArc<T, R> {
         }
     }
 
+    /// Obtains an upgradeable read lock guard, spinning until it is available.
+    ///
+    /// The guard gives read access and can later be turned into a `WriteArc` with
+    /// `UpgradableArc::upgrade` without releasing the lock, so no writer can change the data
+    /// between the check and the write. Only one upgradeable guard exists at a time, and no new
+    /// readers are admitted while it is held.
+    ///
+    /// # Deadlocks
+    ///
+    /// While holding an upgradeable guard, the same thread must not call `read`, `write` or
+    /// `upgradeable_read` on the same lock, and must not hold a `ReadArc` when calling `upgrade`,
+    /// as each of these waits on a guard the thread itself owns.
+    ///
+    /// # Example
+    /// ```rust
+    /// use gearbox::sync::rw_arc::RwArc;
+    ///
+    /// let lock: RwArc<Option<i32>> = RwArc::new(None);
+    /// let guard = lock.upgradeable_read();
+    /// if guard.is_none() {
+    ///     *guard.upgrade() = Some(1);
+    /// }
+    /// assert_eq!(*lock.read(), Some(1));
+    /// ```
     pub fn upgradeable_read(&self) -> UpgradableArc<T, R> {
         loop {
             match self.try_upgradeable_read() {
@@ -86,6 +110,83 @@ impl<T: ?Sized, R: RelaxStrategy> RwArc<T, R> {
     }
 }
 
+#[cfg(feature = "std")]
+impl<T: ?Sized, R: RelaxStrategy> RwArc<T, R> {
+    /// Tries to obtain a read lock guard, giving up once `timeout` has elapsed.
+    ///
+    /// Returns `None` if a writer or an upgradeable guard held the lock for the whole duration.
+    ///
+    /// # Example
+    /// ```rust
+    /// use core::time::Duration;
+    /// use gearbox::sync::rw_arc::RwArc;
+    ///
+    /// let lock: RwArc<i32> = RwArc::new(0);
+    /// let writer = lock.write();
+    /// assert!(lock.read_timeout(Duration::from_millis(10)).is_none());
+    /// drop(writer);
+    /// assert!(lock.read_timeout(Duration::from_millis(10)).is_some());
+    /// ```
+    pub fn read_timeout(&self, timeout: core::time::Duration) -> Option<ReadArc<T, R>> {
+        Self::with_timeout(timeout, || self.try_read())
+    }
+
+    /// Tries to obtain a write lock guard, giving up once `timeout` has elapsed.
+    ///
+    /// Returns `None` if any other guard held the lock for the whole duration.
+    ///
+    /// # Example
+    /// ```rust
+    /// use core::time::Duration;
+    /// use gearbox::sync::rw_arc::RwArc;
+    ///
+    /// let lock: RwArc<i32> = RwArc::new(0);
+    /// let reader = lock.read();
+    /// assert!(lock.write_timeout(Duration::from_millis(10)).is_none());
+    /// drop(reader);
+    /// assert!(lock.write_timeout(Duration::from_millis(10)).is_some());
+    /// ```
+    pub fn write_timeout(&self, timeout: core::time::Duration) -> Option<WriteArc<T, R>> {
+        Self::with_timeout(timeout, || self.try_write_internal(false))
+    }
+
+    /// Calls `acquire` until it returns a guard or `timeout` has elapsed. Contended attempts first
+    /// spin, then yield the thread and finally sleep for up to `MAX_BACKOFF` between attempts, so
+    /// a long timeout does not keep a core busy.
+    fn with_timeout<G>(
+        timeout: core::time::Duration,
+        mut acquire: impl FnMut() -> Option<G>,
+    ) -> Option<G> {
+        const SPIN_ATTEMPTS: u32 = 64;
+        const YIELD_ATTEMPTS: u32 = 128;
+        const MIN_BACKOFF: core::time::Duration = core::time::Duration::from_micros(50);
+        const MAX_BACKOFF: core::time::Duration = core::time::Duration::from_millis(1);
+
+        let deadline = std::time::Instant::now().checked_add(timeout);
+        let mut attempts = 0u32;
+        let mut backoff = MIN_BACKOFF;
+        loop {
+            if let Some(guard) = acquire() {
+                return Some(guard);
+            }
+            let now = std::time::Instant::now();
+            if deadline.is_some_and(|deadline| now >= deadline) {
+                return None;
+            }
+            attempts = attempts.saturating_add(1);
+            if attempts < SPIN_ATTEMPTS {
+                R::relax();
+            } else if attempts < YIELD_ATTEMPTS {
+                std::thread::yield_now();
+            } else {
+                let remaining = deadline.map_or(backoff, |deadline| deadline - now);
+                std::thread::sleep(backoff.min(remaining));
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+    }
+}
+
 impl<T: ?Sized + Clone, R: RelaxStrategy> RwArc<T, R> {
     pub fn hyper_read(&self) -> HyperReadArc<T, R> {
         let data = unsafe { (*self.inner.data.get()).clone() };
@@ -128,6 +229,9 @@ impl<T: ?Sized, R> RwArc<T, R> {
     pub fn writer_count(&self) -> usize {
         self.inner.writer_count()
     }
+    /// Tries to obtain a read lock guard without blocking.
+    ///
+    /// Returns `None` if a writer or an upgradeable guard currently holds the lock.
     pub fn try_read(&self) -> Option<ReadArc<T, R>> {
         let value = self.inner.acquire_reader();
 
@@ -179,12 +283,17 @@ impl<T: ?Sized, R> RwArc<T, R> {
         }
     }
 
+    /// Tries to obtain a write lock guard without blocking.
+    ///
+    /// Returns `None` if any other guard currently holds the lock.
     #[inline]
     pub fn try_write(&self) -> Option<WriteArc<T, R>> {
         self.try_write_internal(true)
     }
 
-    /// Tries to obtain an upgradeable lock guard.
+    /// Tries to obtain an upgradeable lock guard without blocking.
+    ///
+    /// Returns `None` if a writer or another upgradeable guard currently holds the lock.
     #[inline]
     pub fn try_upgradeable_read(&self) -> Option<UpgradableArc<T, R>> {
         if self.inner.lock.fetch_or(UPGRADED, Ordering::Acquire) & (WRITER | UPGRADED) == 0 {
@@ -419,6 +528,56 @@ mod tests {
         drop(read_guard);
     }
 
+    #[test]
+    fn test_try_write_contended() {
+        let lock = RwArc::new(0);
+        let write_guard = lock.write();
+        assert!(lock.try_write().is_none());
+        assert!(lock.try_read().is_none());
+        drop(write_guard);
+
+        let mut write_guard = lock
+            .try_write()
+            .expect("uncontended try_write should succeed");
+        *write_guard = 1;
+        drop(write_guard);
+        assert_eq!(*lock.try_read().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_lock_timeout() {
+        let lock = Arc::new(RwArc::new(0));
+        let write_guard = lock.write();
+        let timeout = std::time::Duration::from_millis(20);
+        let start = std::time::Instant::now();
+        assert!(lock.read_timeout(timeout).is_none());
+        assert!(lock.write_timeout(timeout).is_none());
+        assert!(start.elapsed() >= timeout * 2);
+
+        let lock2 = lock.clone();
+        let writer = thread::spawn(move || {
+            *lock2
+                .write_timeout(std::time::Duration::from_secs(5))
+                .expect("lock should be released before the timeout") = 2;
+        });
+        thread::sleep(std::time::Duration::from_millis(10));
+        drop(write_guard);
+        writer.join().unwrap();
+        assert_eq!(*lock.read_timeout(timeout).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_lock_timeout_backs_off() {
+        // Busy spinning would make millions of attempts, backing off makes at most a few hundred.
+        let mut attempts = 0;
+        let guard = RwArc::<i32>::with_timeout(std::time::Duration::from_millis(50), || {
+            attempts += 1;
+            None::<()>
+        });
+        assert!(guard.is_none());
+        assert!(attempts < 2_000, "{} attempts", attempts);
+    }
+
     #[test]
     fn test_rw_try_read() {
         let m = RwArc::new(0);
@@ -596,6 +755,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn upgradeable_blocks_writers_until_upgraded() {
+        let lock = Arc::new(RwArc::new(5));
+        let upgradable_guard = lock.upgradeable_read();
+        let barrier = Arc::new(Barrier::new(2));
+
+        let lock2 = Arc::clone(&lock);
+        let barrier2 = Arc::clone(&barrier);
+        let writer = thread::spawn(move || {
+            barrier2.wait();
+            *lock2.write() *= 10;
+        });
+
+        barrier.wait();
+        thread::sleep(std::time::Duration::from_millis(20));
+        assert!(lock.try_write().is_none());
+        assert!(lock.try_read().is_none());
+        assert_eq!(*upgradable_guard, 5);
+
+        let mut write_guard = upgradable_guard.upgrade();
+        assert_eq!(*write_guard, 5);
+        *write_guard += 1;
+        drop(write_guard);
+
+        writer.join().unwrap();
+        assert_eq!(*lock.read(), 60);
+    }
+
     #[test]
     fn recursive_read_locks() {
         let lock = Arc::new(RwArc::new(5));
@@ -8,6 +8,11 @@ use crate::prelude::{
     sync::{atomic::Ordering, Arc},
 };
 
+/// A read lock guard that can be upgraded to a `WriteArc` without releasing the lock.
+///
+/// Obtained from `RwArc::upgradeable_read`. While it is held, other readers that already hold
+/// the lock keep their access, but no new readers, writers or upgradeable guards are admitted.
+/// Dropping it releases the lock.
 pub struct UpgradableArc<T: ?Sized, R = Spin> {
     pub(super) inner: Arc<RwArcInner<T, R>>,
 }
@@ -16,6 +21,11 @@ unsafe impl<T: ?Sized + Send + Sync, R> Send for UpgradableArc<T, R> {}
 unsafe impl<T: ?Sized + Send + Sync, R> Sync for UpgradableArc<T, R> {}
 
 impl<T: ?Sized, R: RelaxStrategy> UpgradableArc<T, R> {
+    /// Upgrades the guard to a write guard, spinning until all remaining readers are gone.
+    ///
+    /// The lock is never released in between, so no other writer can observe or modify the data
+    /// before the returned `WriteArc` is dropped. Calling this while the current thread still
+    /// holds a `ReadArc` of the same lock deadlocks.
     #[inline]
     pub fn upgrade(mut self) -> WriteArc<T, R> {
         loop {
@@ -53,6 +63,9 @@ impl<'rwlock, T: ?Sized, R> UpgradableArc<T, R> {
         }
     }
 
+    /// Tries to upgrade the guard to a write guard without blocking.
+    ///
+    /// Returns the upgradeable guard back if readers still hold the lock.
     pub fn try_upgrade(self) -> Result<WriteArc<T, R>, Self> {
         self.try_upgrade_internal(true)
     }
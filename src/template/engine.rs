@@ -1,8 +1,12 @@
 use crate::error::tracer::DynTracerError;
 use crate::error::TracerError;
-use crate::template::pipeline::{Pipeline, PipelineValue};
+use crate::template::pipeline::{Mapping, Pipeline, PipelineValue, Sequence};
 use crate::template::pipelines::date_time::DatePipeline;
-use crate::template::pipelines::{PipelineManager, PrefixPipeline};
+use crate::template::pipelines::{
+    DefaultPipeline, EscapeHtmlPipeline, EscapeJsonPipeline, HumanizeBytesPipeline,
+    HumanizeDurationPipeline, LowerPipeline, MathOp, MathPipeline, PipelineManager, PrefixPipeline,
+    TrimPipeline, TruncatePipeline, UpperPipeline,
+};
 use crate::{tracer_dyn_err, tracer_err};
 use alloc::boxed::Box;
 use core::any::Any;
@@ -14,6 +18,10 @@ use spin::Mutex;
 /// A global static for storing pipelines.
 static PIPELINES: Mutex<Option<PipelineManager>> = Mutex::new(None);
 
+/// How deep `{% include %}` blocks may be nested before rendering fails, which stops partials that
+/// include themselves.
+pub const MAX_INCLUDE_DEPTH: usize = 32;
+
 /// TemplateEngine is responsible for rendering templates using context data and applying
 /// pipelines for data transformations.
 ///
@@ -34,6 +42,10 @@ static PIPELINES: Mutex<Option<PipelineManager>> = Mutex::new(None);
 #[derive(Debug, Clone)]
 pub struct TemplateEngine {
     pipelines: PipelineManager,
+    local_pipelines: PipelineManager,
+    strict: bool,
+    syntax: Syntax,
+    partials: HashMap<String, Vec<Node>>,
 }
 
 impl TemplateEngine {
@@ -49,6 +61,39 @@ impl TemplateEngine {
         let mut map = PipelineManager::default();
         map.insert("date".to_string(), DatePipeline::new().boxed_clone());
         map.insert("prefix".to_string(), PrefixPipeline::new().boxed_clone());
+        map.insert("upper".to_string(), UpperPipeline::new().boxed_clone());
+        map.insert("lower".to_string(), LowerPipeline::new().boxed_clone());
+        map.insert("trim".to_string(), TrimPipeline::new().boxed_clone());
+        map.insert(
+            "truncate".to_string(),
+            TruncatePipeline::new().boxed_clone(),
+        );
+        map.insert("default".to_string(), DefaultPipeline::new().boxed_clone());
+        map.insert(
+            "escape_html".to_string(),
+            EscapeHtmlPipeline::new().boxed_clone(),
+        );
+        map.insert(
+            "escape_json".to_string(),
+            EscapeJsonPipeline::new().boxed_clone(),
+        );
+        for op in [
+            MathOp::Add,
+            MathOp::Sub,
+            MathOp::Mul,
+            MathOp::Div,
+            MathOp::Round,
+        ] {
+            map.insert(op.name().to_string(), MathPipeline::new(op).boxed_clone());
+        }
+        map.insert(
+            "humanize_bytes".to_string(),
+            HumanizeBytesPipeline::new().boxed_clone(),
+        );
+        map.insert(
+            "humanize_duration".to_string(),
+            HumanizeDurationPipeline::new().boxed_clone(),
+        );
         map
     }
 
@@ -133,15 +178,180 @@ impl TemplateEngine {
     pub fn new() -> Self {
         TemplateEngine {
             pipelines: Self::get_pipelines(),
+            local_pipelines: PipelineManager::default(),
+            strict: false,
+            syntax: Syntax::new(
+                ("{{".to_string(), "}}".to_string()),
+                ("{%".to_string(), "%}".to_string()),
+            ),
+            partials: HashMap::new(),
         }
     }
 
+    /// Enables or disables strict mode. In strict mode rendering fails with an error naming the
+    /// first placeholder whose variable is not in the context, instead of leaving the placeholder
+    /// in the output, and on pipeline options the pipeline cannot use, like `add:x` or `div:0`.
+    /// Conditions and loops over missing variables are not affected. Strict mode is disabled by
+    /// default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gearbox::template::{TemplateContext, TemplateEngine};
+    ///
+    /// let engine = TemplateEngine::new().strict(true);
+    /// let result = engine.render("Hello, {{ name }}!", &TemplateContext::new());
+    /// assert!(result.is_err());
+    /// ```
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Replaces the `{{ }}` delimiters of placeholders, e.g. to render content that uses double
+    /// braces itself. The delimiters may be several characters long, and have no special meaning
+    /// apart from that, so characters like `[` or `$` can be used. Trim markers are written right
+    /// inside the new delimiters, as in `<%- var -%>`.
+    ///
+    /// The delimiters must not be empty, and must not open like the block delimiters set with
+    /// `with_block_delimiters`, otherwise rendering fails. They are checked when parsing, so the
+    /// order of the builder calls does not matter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gearbox::template::{TemplateContext, TemplateEngine};
+    ///
+    /// let engine = TemplateEngine::new().with_delimiters("<%", "%>");
+    /// let mut context = TemplateContext::new();
+    /// context.insert("name", Box::new("John".to_string()));
+    ///
+    /// assert_eq!(
+    ///     engine.render("{{ literal }} <% name %>", &context).unwrap(),
+    ///     "{{ literal }} John"
+    /// );
+    /// ```
+    pub fn with_delimiters(mut self, open: &str, close: &str) -> Self {
+        self.syntax = Syntax::new(
+            (open.to_string(), close.to_string()),
+            self.syntax.block_delimiters.clone(),
+        );
+        self
+    }
+
+    /// Replaces the `{% %}` delimiters of `if` and `for` blocks, like `with_delimiters` does for
+    /// placeholders. The same rules apply to them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gearbox::template::{TemplateContext, TemplateEngine};
+    ///
+    /// let engine = TemplateEngine::new()
+    ///     .with_delimiters("[[", "]]")
+    ///     .with_block_delimiters("[%", "%]");
+    /// let mut context = TemplateContext::new();
+    /// context.insert("name", Box::new("John".to_string()));
+    ///
+    /// assert_eq!(
+    ///     engine
+    ///         .render("[% if name %]{% raw %} [[ name ]][% endif %]", &context)
+    ///         .unwrap(),
+    ///     "{% raw %} John"
+    /// );
+    /// ```
+    pub fn with_block_delimiters(mut self, open: &str, close: &str) -> Self {
+        self.syntax = Syntax::new(
+            self.syntax.delimiters.clone(),
+            (open.to_string(), close.to_string()),
+        );
+        self
+    }
+
+    /// Registers a pipeline on this engine only. Pipelines registered on the engine take
+    /// precedence over the global pipelines of `update_pipeline` and are kept by
+    /// `reload_pipelines`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the pipeline.
+    /// * `pipeline` - The pipeline to add.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gearbox::template::pipelines::PrefixPipeline;
+    /// use gearbox::template::{Pipeline, TemplateContext, TemplateEngine};
+    ///
+    /// let engine = TemplateEngine::new().with_pipeline("greet", PrefixPipeline::new().options("Hi "));
+    /// let mut context = TemplateContext::new();
+    /// context.insert("name", Box::new("John".to_string()));
+    ///
+    /// assert_eq!(engine.render("{{ name | greet }}", &context).unwrap(), "Hi John");
+    /// ```
+    pub fn with_pipeline<P: Pipeline + Send + Sync + 'static>(
+        mut self,
+        name: &str,
+        pipeline: P,
+    ) -> Self {
+        self.local_pipelines
+            .insert(name.to_string(), Box::new(pipeline));
+        self
+    }
+
+    /// Registers a partial template that templates render in place with `{% include "name" %}`,
+    /// using the same context. Partials may include other partials, up to `MAX_INCLUDE_DEPTH`
+    /// levels deep. Registering a partial with an existing name replaces it.
+    ///
+    /// The partial is parsed right away, with the delimiters the engine has at that point, so
+    /// syntax errors are reported here rather than by every render that includes it. Errors name
+    /// the partial they occur in.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gearbox::template::{TemplateContext, TemplateEngine};
+    ///
+    /// let mut engine = TemplateEngine::new();
+    /// engine.register_partial("user", r#"{"name": "{{ name }}"}"#).unwrap();
+    /// let mut context = TemplateContext::new();
+    /// context.insert("name", Box::new("John".to_string()));
+    ///
+    /// assert_eq!(
+    ///     engine.render(r#"{"user": {% include "user" %}}"#, &context).unwrap(),
+    ///     r#"{"user": {"name": "John"}}"#
+    /// );
+    /// ```
+    pub fn register_partial(&mut self, name: &str, template: &str) -> Result<(), DynTracerError> {
+        let nodes = self.syntax.parse(template, Some(name))?;
+        self.partials.insert(name.to_string(), nodes);
+        Ok(())
+    }
+
     pub fn reload_pipelines(&mut self) {
         self.pipelines = Self::get_pipelines();
     }
 
     /// Renders a template using the provided context.
     ///
+    /// Besides `{{ var | pipe }}` placeholders, templates may contain conditional blocks:
+    /// `{% if var %}...{% else %}...{% endif %}` renders the first branch if `var` is truthy
+    /// (a non-empty value that is not a numeric zero), `{% if var == "x" %}` and
+    /// `{% if var != "x" %}` compare the value of `var` with a literal or another variable.
+    /// `{% for item in list %}...{% endfor %}` renders its body once for every element of the
+    /// `Sequence` stored as `list`, with `item` bound to the element and `loop.index` to its 1 based
+    /// position. Blocks can be nested. `{% include "name" %}` renders the partial registered as
+    /// `name` with `register_partial` in its place.
+    ///
+    /// Any other `{% %}` tag fails with an "Unknown block" error instead of being rendered as
+    /// text. Templates that contain `{%` literally, which rendered unchanged before blocks were
+    /// supported, have to move the block delimiters out of the way with `with_block_delimiters`.
+    ///
+    /// A `-` right inside a tag, as in `{{- var -}}` or `{%- if var -%}`, removes all whitespace
+    /// on that side of the tag, newlines included. This keeps multi-line JSON or YAML bodies tidy
+    /// without cramming the template onto one line. The marker has to be separated from the rest
+    /// of the tag by whitespace.
+    ///
     /// # Arguments
     ///
     /// * `template` - The template string.
@@ -163,66 +373,155 @@ impl TemplateEngine {
     ///
     /// let result = engine.render("Hello, {{ name }}!", &context).unwrap();
     /// assert_eq!(result, "Hello, World!");
+    ///
+    /// let result = engine
+    ///     .render("{% if name == \"World\" %}Hi{% else %}Bye{% endif %}", &context)
+    ///     .unwrap();
+    /// assert_eq!(result, "Hi");
     /// ```
     pub fn render(
         &self,
         template: &str,
         context: &TemplateContext,
     ) -> Result<String, DynTracerError> {
-        let mut output = template.to_string();
-        let re = Regex::new(r"\{\{\s*(.*?)\s*\}\}")
-            .map_err(|_| tracer_dyn_err!("Failed to create regex"))?;
-
-        for cap in re.captures_iter(&output.clone()) {
-            let full_match = &cap[0];
-            let mut parts = cap[1].split('|').map(str::trim);
-            if let Some(key) = parts.next() {
-                if let Some(initial) = context.get(key) {
-                    let mut current_value = None;
-
-                    for pipe_segment in parts {
-                        let pipe_parts: Vec<&str> = pipe_segment.splitn(2, ':').collect();
-                        let pipe_name = pipe_parts[0].trim();
-                        let pipe_options = if pipe_parts.len() > 1 {
-                            let option = pipe_parts[1].trim().replace(r"\'", "'");
-                            if option.starts_with('\'') && option.ends_with('\'') {
-                                option[1..option.len() - 1].to_string()
-                            } else {
-                                option
-                            }
-                        } else {
-                            "".to_string()
-                        };
+        let nodes = self.syntax.parse(template, None)?;
+        let mut output = String::new();
+        self.render_nodes(&nodes, &Scope::Root(context), &mut output, 0, None)?;
+        Ok(output)
+    }
 
-                        if let Some(pipe) = self.pipelines.get(pipe_name) {
-                            let pipeline = if pipe_options.is_empty() {
-                                pipe.boxed_clone()
-                            } else {
-                                pipe.options(&pipe_options).boxed_clone()
-                            };
-                            if let Some(t) = current_value {
-                                current_value = Some(pipeline.format(&t));
-                            } else {
-                                current_value = Some(pipeline.format(initial));
-                            }
-                        } else {
-                            return Err(tracer_dyn_err!(format!(
-                                "Pipeline '{}' not found",
-                                pipe_name
-                            )));
-                        }
+    /// Renders parsed template nodes into `output`, `depth` being the number of includes they are
+    /// nested in and `partial` the name of the partial they belong to.
+    fn render_nodes(
+        &self,
+        nodes: &[Node],
+        scope: &Scope,
+        output: &mut String,
+        depth: usize,
+        partial: Option<&str>,
+    ) -> Result<(), DynTracerError> {
+        for node in nodes {
+            match node {
+                Node::Text(text) => output.push_str(text),
+                Node::Placeholder(placeholder) => match scope.get(&placeholder.key) {
+                    Some(value) => {
+                        output.push_str(&self.apply_pipes(value, placeholder, partial)?)
                     }
-
-                    if let Some(current_value) = current_value {
-                        output = output.replace(full_match, &current_value.to_string());
+                    None if self.strict => {
+                        return Err(tracer_dyn_err!(format!(
+                            "Unresolved variable '{}' at offset {}{}",
+                            placeholder.key,
+                            placeholder.offset,
+                            in_partial(partial)
+                        )))
+                    }
+                    None => output.push_str(&placeholder.source),
+                },
+                Node::If {
+                    condition,
+                    then,
+                    otherwise,
+                } => {
+                    if condition.evaluate(scope) {
+                        self.render_nodes(then, scope, output, depth, partial)?;
                     } else {
-                        output = output.replace(full_match, &initial.to_string());
+                        self.render_nodes(otherwise, scope, output, depth, partial)?;
+                    }
+                }
+                Node::For {
+                    variable,
+                    iterable,
+                    body,
+                } => {
+                    let items = match scope.get(iterable) {
+                        Some(value) => {
+                            value.as_any().downcast_ref::<Sequence>().ok_or_else(|| {
+                                tracer_dyn_err!(format!(
+                                    "'{}' is not a sequence{}",
+                                    iterable,
+                                    in_partial(partial)
+                                ))
+                            })?
+                        }
+                        None => continue,
+                    };
+                    for (index, item) in items.iter().enumerate() {
+                        let scope = Scope::Loop {
+                            parent: scope,
+                            variable,
+                            item: item.as_ref(),
+                            index: Box::new((index + 1) as i32),
+                        };
+                        self.render_nodes(body, &scope, output, depth, partial)?;
+                    }
+                }
+                Node::Include(name, offset) => {
+                    let nodes = self.partials.get(name).ok_or_else(|| {
+                        tracer_dyn_err!(format!(
+                            "Partial '{}' not found at offset {}{}",
+                            name,
+                            offset,
+                            in_partial(partial)
+                        ))
+                    })?;
+                    if depth >= MAX_INCLUDE_DEPTH {
+                        return Err(tracer_dyn_err!(format!(
+                            "Including partial '{}' exceeds the maximum include depth of {}, \
+                             partials may be including each other",
+                            name, MAX_INCLUDE_DEPTH
+                        )));
                     }
+                    self.render_nodes(nodes, scope, output, depth + 1, Some(name))?;
                 }
             }
         }
+        Ok(())
+    }
 
-        Ok(output)
+    /// Applies the pipelines of a placeholder to its value and renders the result.
+    fn apply_pipes(
+        &self,
+        value: &(dyn PipelineValue + Send + Sync),
+        placeholder: &Placeholder,
+        partial: Option<&str>,
+    ) -> Result<String, DynTracerError> {
+        let mut current: Option<Box<dyn PipelineValue + Send + Sync>> = None;
+        for (pipe_name, pipe_options) in &placeholder.pipes {
+            let pipe = self
+                .local_pipelines
+                .get(pipe_name)
+                .or_else(|| self.pipelines.get(pipe_name))
+                .ok_or_else(|| {
+                    tracer_dyn_err!(format!(
+                        "Pipeline '{}' not found{}",
+                        pipe_name,
+                        in_partial(partial)
+                    ))
+                })?;
+            let pipeline = if pipe_options.is_empty() {
+                pipe.boxed_clone()
+            } else if self.strict {
+                pipe.try_options(pipe_options).map_err(|e| {
+                    tracer_dyn_err!(format!(
+                        "Invalid options for pipeline '{}' at offset {}{}: {}",
+                        pipe_name,
+                        placeholder.offset,
+                        in_partial(partial),
+                        e
+                    ))
+                })?
+            } else {
+                pipe.options(pipe_options)
+            };
+            current = Some(match &current {
+                Some(current) => pipeline.format(current),
+                None => pipeline.format(&value.boxed_clone()),
+            });
+        }
+        Ok(match current {
+            Some(current) => current.to_string(),
+            None => value.to_string(),
+        })
     }
 }
 
@@ -241,8 +540,148 @@ impl TemplateContext {
         self.variables.insert(key.to_string(), value);
     }
 
-    pub fn get(&self, key: &str) -> Option<&Box<dyn PipelineValue + Send + Sync>> {
-        self.variables.get(key)
+    /// Creates a context from the fields of a JSON object, e.g. a decoded API response. Nested
+    /// values are converted recursively, so every field is addressable with a dotted placeholder
+    /// such as `{{ user.address.city }}`.
+    ///
+    /// | JSON    | Pipeline value                                        |
+    /// |---------|-------------------------------------------------------|
+    /// | object  | `Mapping`                                             |
+    /// | array   | `Sequence`                                            |
+    /// | string  | `String`                                              |
+    /// | number  | `i32`, `i64` or `u64` for integers, `f64` otherwise   |
+    /// | boolean | `bool`                                                |
+    /// | null    | an empty `String`                                     |
+    ///
+    /// A value that is not an object has no field names and yields an empty context.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gearbox::template::{TemplateContext, TemplateEngine};
+    ///
+    /// let response = serde_json::json!({ "user": { "name": "John", "roles": ["admin"] } });
+    /// let context = TemplateContext::from_json(response);
+    ///
+    /// let result = TemplateEngine::new()
+    ///     .render("{{ user.name }} is {{ user.roles.0 }}", &context)
+    ///     .unwrap();
+    /// assert_eq!(result, "John is admin");
+    /// ```
+    #[cfg(feature = "serde_json")]
+    pub fn from_json(value: serde_json::Value) -> Self {
+        let mut context = TemplateContext::new();
+        if let serde_json::Value::Object(fields) = value {
+            for (key, value) in fields {
+                context.variables.insert(key, json_value(value));
+            }
+        }
+        context
+    }
+
+    /// Gets a value by key. If no value is stored under the exact key, dotted keys like
+    /// `user.address.city` are resolved by walking nested `Mapping` values, numeric segments index
+    /// into `Sequence` values.
+    pub fn get(&self, key: &str) -> Option<&(dyn PipelineValue + Send + Sync)> {
+        if let Some(value) = self.variables.get(key) {
+            return Some(value.as_ref());
+        }
+        let (root, path) = key.split_once('.')?;
+        resolve_path(self.variables.get(root)?.as_ref(), path)
+    }
+}
+
+/// Resolves a dotted `path` inside `value` by walking nested `Mapping` values, numeric segments
+/// index into `Sequence` values.
+fn resolve_path<'a>(
+    value: &'a (dyn PipelineValue + Send + Sync),
+    path: &str,
+) -> Option<&'a (dyn PipelineValue + Send + Sync)> {
+    path.split('.').try_fold(value, |value, segment| {
+        let value = value.as_any();
+        let child = if let Some(mapping) = value.downcast_ref::<Mapping>() {
+            mapping.get(segment)
+        } else if let Some(sequence) = value.downcast_ref::<Sequence>() {
+            segment.parse::<usize>().ok().and_then(|t| sequence.get(t))
+        } else {
+            None
+        };
+        child.map(|t| t.as_ref())
+    })
+}
+
+/// The variables visible while rendering: the context, overlaid with the loop variables of the
+/// enclosing `{% for %}` blocks, without copying the context for every iteration.
+enum Scope<'a> {
+    Root(&'a TemplateContext),
+    Loop {
+        parent: &'a Scope<'a>,
+        variable: &'a str,
+        item: &'a (dyn PipelineValue + Send + Sync),
+        index: Box<dyn PipelineValue + Send + Sync>,
+    },
+}
+
+impl Scope<'_> {
+    /// Gets a value by key, the innermost loop taking precedence. Dotted keys are resolved like
+    /// `TemplateContext::get` does.
+    fn get(&self, key: &str) -> Option<&(dyn PipelineValue + Send + Sync)> {
+        match self {
+            Scope::Root(context) => context.get(key),
+            Scope::Loop {
+                parent,
+                variable,
+                item,
+                index,
+            } => {
+                if key == "loop.index" {
+                    Some(index.as_ref())
+                } else if key == *variable {
+                    Some(*item)
+                } else if let Some(path) = key
+                    .strip_prefix(*variable)
+                    .and_then(|t| t.strip_prefix('.'))
+                {
+                    resolve_path(*item, path)
+                } else {
+                    parent.get(key)
+                }
+            }
+        }
+    }
+}
+
+/// Converts a JSON value into the pipeline value used by `TemplateContext::from_json`.
+#[cfg(feature = "serde_json")]
+fn json_value(value: serde_json::Value) -> Box<dyn PipelineValue + Send + Sync> {
+    use serde_json::Value;
+
+    match value {
+        Value::Null => Box::new(String::new()),
+        Value::Bool(t) => Box::new(t),
+        Value::Number(t) => {
+            if let Some(t) = t.as_i64() {
+                match i32::try_from(t) {
+                    Ok(t) => Box::new(t),
+                    Err(_) => Box::new(t),
+                }
+            } else if let Some(t) = t.as_u64() {
+                Box::new(t)
+            } else {
+                Box::new(t.as_f64().unwrap_or(f64::NAN))
+            }
+        }
+        Value::String(t) => Box::new(t),
+        Value::Array(values) => Box::new(Sequence::from(
+            values.into_iter().map(json_value).collect::<Vec<_>>(),
+        )),
+        Value::Object(fields) => {
+            let mut mapping = Mapping::new();
+            for (key, value) in fields {
+                mapping.insert(key, json_value(value));
+            }
+            Box::new(mapping)
+        }
     }
 }
 
@@ -255,3 +694,452 @@ impl Clone for TemplateContext {
         TemplateContext { variables }
     }
 }
+
+/// A parsed template fragment.
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    /// Literal text.
+    Text(String),
+    /// A `{{ var | pipe }}` placeholder.
+    Placeholder(Placeholder),
+    /// An `{% if %}` block with its optional `{% else %}` branch.
+    If {
+        condition: Condition,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    /// A `{% for variable in iterable %}` block.
+    For {
+        variable: String,
+        iterable: String,
+        body: Vec<Node>,
+    },
+    /// An `{% include "name" %}` of a partial, with its byte offset in the template.
+    Include(String, usize),
+}
+
+/// A `{{ key | pipe:options }}` placeholder.
+#[derive(Debug, Clone, PartialEq)]
+struct Placeholder {
+    key: String,
+    /// The names and options of the pipelines, in the order they are applied.
+    pipes: Vec<(String, String)>,
+    /// The tag without trim markers, rendered as is if the variable is missing.
+    source: String,
+    /// The byte offset of the tag in the template.
+    offset: usize,
+}
+
+impl Placeholder {
+    fn parse(expression: &str, source: String, offset: usize) -> Self {
+        let mut parts = expression.split('|').map(str::trim);
+        let key = parts.next().unwrap_or_default().to_string();
+        let pipes = parts
+            .map(|segment| {
+                let (name, options) = segment.split_once(':').unwrap_or((segment, ""));
+                let options = options.trim().replace(r"\'", "'");
+                let options =
+                    if options.len() >= 2 && options.starts_with('\'') && options.ends_with('\'') {
+                        options[1..options.len() - 1].to_string()
+                    } else {
+                        options
+                    };
+                (name.trim().to_string(), options)
+            })
+            .collect();
+        Placeholder {
+            key,
+            pipes,
+            source,
+            offset,
+        }
+    }
+}
+
+/// The condition of an `{% if %}` block.
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    /// `{% if var %}`, true if the value is truthy.
+    Truthy(String),
+    /// `{% if left == right %}` or, when negated, `{% if left != right %}`.
+    Equals {
+        left: Operand,
+        right: Operand,
+        negated: bool,
+    },
+}
+
+/// A side of a comparison, either a quoted or numeric literal or a context variable.
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Literal(String),
+    Variable(String),
+}
+
+impl Condition {
+    fn parse(expression: &str) -> Result<Self, String> {
+        // The first `==` or `!=` outside a quoted literal separates the operands.
+        let mut quote = None;
+        for (index, c) in expression.char_indices() {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == '"' || c == '\'' => quote = Some(c),
+                None => {
+                    let negated = match &expression[index..] {
+                        rest if rest.starts_with("==") => false,
+                        rest if rest.starts_with("!=") => true,
+                        _ => continue,
+                    };
+                    return Ok(Condition::Equals {
+                        left: Operand::parse(&expression[..index])?,
+                        right: Operand::parse(&expression[index + 2..])?,
+                        negated,
+                    });
+                }
+            }
+        }
+        match Operand::parse(expression)? {
+            Operand::Variable(name) => Ok(Condition::Truthy(name)),
+            Operand::Literal(_) => Err(format!(
+                "Invalid condition '{}', expected a variable",
+                expression
+            )),
+        }
+    }
+
+    /// Evaluates the condition. A `bool` value is used as is, any other value is truthy if it
+    /// renders to a non-empty string that is not a numeric zero. Missing variables are falsy.
+    fn evaluate(&self, scope: &Scope) -> bool {
+        match self {
+            Condition::Truthy(name) => scope
+                .get(name)
+                .map(|value| {
+                    if let Some(t) = value.as_any().downcast_ref::<bool>() {
+                        return *t;
+                    }
+                    let value = value.to_string();
+                    !value.is_empty() && value.parse::<f64>().map(|t| t != 0.0).unwrap_or(true)
+                })
+                .unwrap_or(false),
+            Condition::Equals {
+                left,
+                right,
+                negated,
+            } => (left.resolve(scope) == right.resolve(scope)) != *negated,
+        }
+    }
+}
+
+impl Operand {
+    fn parse(operand: &str) -> Result<Self, String> {
+        let operand = operand.trim();
+        let quoted = |quote: char| {
+            operand.len() >= 2 && operand.starts_with(quote) && operand.ends_with(quote)
+        };
+        if quoted('"') || quoted('\'') {
+            Ok(Operand::Literal(operand[1..operand.len() - 1].to_string()))
+        } else if operand.parse::<f64>().is_ok() {
+            Ok(Operand::Literal(operand.to_string()))
+        } else if !operand.is_empty() && !operand.contains(char::is_whitespace) {
+            Ok(Operand::Variable(operand.to_string()))
+        } else {
+            Err(format!("Invalid operand '{}'", operand))
+        }
+    }
+
+    fn resolve(&self, scope: &Scope) -> Option<String> {
+        match self {
+            Operand::Literal(value) => Some(value.clone()),
+            Operand::Variable(name) => scope.get(name).map(|t| t.to_string()),
+        }
+    }
+}
+
+/// The kind of a block that is still being parsed.
+enum OpenKind {
+    If(Condition),
+    For { variable: String, iterable: String },
+}
+
+/// A block that is still being parsed.
+struct OpenBlock {
+    kind: OpenKind,
+    body: Vec<Node>,
+    otherwise: Option<Vec<Node>>,
+    offset: usize,
+}
+
+impl OpenBlock {
+    fn name(&self) -> &'static str {
+        match self.kind {
+            OpenKind::If(_) => "if",
+            OpenKind::For { .. } => "for",
+        }
+    }
+}
+
+/// Names the partial a template belongs to for error messages, empty for the rendered template.
+fn in_partial(partial: Option<&str>) -> String {
+    partial
+        .map(|name| format!(" in partial '{}'", name))
+        .unwrap_or_default()
+}
+
+/// The delimiters of the tags of an engine, with the regex matching the tags, which is compiled
+/// once when the delimiters are set.
+#[derive(Debug, Clone)]
+struct Syntax {
+    delimiters: (String, String),
+    block_delimiters: (String, String),
+    tags: Regex,
+}
+
+impl Syntax {
+    fn new(delimiters: (String, String), block_delimiters: (String, String)) -> Self {
+        // A tag captures its content without the surrounding whitespace, and its trim markers,
+        // which are separated from the content by whitespace.
+        let tag = |kind: &str, (open, close): &(String, String)| {
+            format!(
+                r"{}(?P<{kind}_trim_start>-\s)?\s*(?P<{kind}>.*?)\s*(?P<{kind}_trim_end>\s-)?{}",
+                regex::escape(open),
+                regex::escape(close),
+                kind = kind
+            )
+        };
+        let mut tags = [
+            tag("placeholder", &delimiters),
+            tag("block", &block_delimiters),
+        ];
+        // The longer opening delimiter first, so a delimiter that starts with the other one is
+        // matched as a whole.
+        if block_delimiters.0.len() > delimiters.0.len() {
+            tags.swap(0, 1);
+        }
+        let tags = Regex::new(&tags.join("|")).expect("escaped delimiters form a valid regex");
+        Syntax {
+            delimiters,
+            block_delimiters,
+            tags,
+        }
+    }
+
+    /// Checks that the delimiters are usable.
+    fn check(&self) -> Result<(), DynTracerError> {
+        let (open, close) = &self.delimiters;
+        let (block_open, block_close) = &self.block_delimiters;
+        if [open, close, block_open, block_close]
+            .iter()
+            .any(|t| t.is_empty())
+        {
+            return Err(tracer_dyn_err!("Template delimiters must not be empty"));
+        }
+        // With the same opening delimiter a tag could be a placeholder as well as a block.
+        if open == block_open {
+            return Err(tracer_dyn_err!(format!(
+                "Template delimiters must differ from the block delimiters, both open with '{}'",
+                open
+            )));
+        }
+        Ok(())
+    }
+
+    /// Parses a template, or the partial named `partial`, into text, placeholders and blocks. The
+    /// trim markers of a tag, e.g. `{{-` and `-}}`, remove the whitespace of the text before or
+    /// after it.
+    fn parse(&self, template: &str, partial: Option<&str>) -> Result<Vec<Node>, DynTracerError> {
+        self.check()?;
+        let (open_tag, close_tag) = &self.block_delimiters;
+
+        let mut root: Vec<Node> = Vec::new();
+        let mut open: Vec<OpenBlock> = Vec::new();
+        let mut last = 0;
+        let mut trim_next = false;
+
+        fn current<'a>(root: &'a mut Vec<Node>, open: &'a mut [OpenBlock]) -> &'a mut Vec<Node> {
+            match open.last_mut() {
+                Some(block) => match &mut block.otherwise {
+                    Some(otherwise) => otherwise,
+                    None => &mut block.body,
+                },
+                None => root,
+            }
+        }
+
+        for cap in self.tags.captures_iter(template) {
+            let tag = cap.get(0).unwrap();
+            let (expression, trim_start, trim_end) = match cap.name("block") {
+                Some(expression) => (
+                    expression,
+                    cap.name("block_trim_start"),
+                    cap.name("block_trim_end"),
+                ),
+                None => (
+                    cap.name("placeholder").unwrap(),
+                    cap.name("placeholder_trim_start"),
+                    cap.name("placeholder_trim_end"),
+                ),
+            };
+
+            let mut text = &template[last..tag.start()];
+            if trim_next {
+                text = text.trim_start();
+            }
+            if trim_start.is_some() {
+                text = text.trim_end();
+            }
+            if !text.is_empty() {
+                current(&mut root, &mut open).push(Node::Text(text.to_string()));
+            }
+            last = tag.end();
+            trim_next = trim_end.is_some();
+
+            let expression = expression.as_str();
+            if cap.name("block").is_none() {
+                // Keep the tag without its trim markers, to render it if the variable is missing.
+                let (open_placeholder, close_placeholder) = &self.delimiters;
+                let start =
+                    trim_start.map_or(tag.start() + open_placeholder.len(), |t| t.start() + 1);
+                let end = trim_end.map_or(tag.end() - close_placeholder.len(), |t| t.end() - 1);
+                let source = format!(
+                    "{}{}{}",
+                    open_placeholder,
+                    &template[start..end],
+                    close_placeholder
+                );
+                current(&mut root, &mut open).push(Node::Placeholder(Placeholder::parse(
+                    expression,
+                    source,
+                    tag.start(),
+                )));
+                continue;
+            }
+
+            let (keyword, rest) = expression
+                .split_once(char::is_whitespace)
+                .map(|(k, r)| (k, r.trim()))
+                .unwrap_or((expression, ""));
+            let unexpected = || {
+                tracer_dyn_err!(format!(
+                    "Unexpected '{} {} {}' at offset {}{}",
+                    open_tag,
+                    expression,
+                    close_tag,
+                    tag.start(),
+                    in_partial(partial)
+                ))
+            };
+            match keyword {
+                "if" => open.push(OpenBlock {
+                    kind: OpenKind::If(Condition::parse(rest).map_err(|e| {
+                        tracer_dyn_err!(format!(
+                            "{} at offset {}{}",
+                            e,
+                            tag.start(),
+                            in_partial(partial)
+                        ))
+                    })?),
+                    body: Vec::new(),
+                    otherwise: None,
+                    offset: tag.start(),
+                }),
+                "for" => {
+                    let (variable, iterable) = rest
+                        .split_once(" in ")
+                        .map(|(v, i)| (v.trim(), i.trim()))
+                        .filter(|(v, i)| !v.is_empty() && !i.is_empty())
+                        .ok_or_else(|| {
+                            tracer_dyn_err!(format!(
+                                "Invalid loop '{} {} {}' at offset {}{}, expected 'for item in list'",
+                                open_tag,
+                                expression,
+                                close_tag,
+                                tag.start(), in_partial(partial)
+                            ))
+                        })?;
+                    open.push(OpenBlock {
+                        kind: OpenKind::For {
+                            variable: variable.to_string(),
+                            iterable: iterable.to_string(),
+                        },
+                        body: Vec::new(),
+                        otherwise: None,
+                        offset: tag.start(),
+                    })
+                }
+                "include" => {
+                    let quoted = |quote: char| {
+                        rest.len() >= 2 && rest.starts_with(quote) && rest.ends_with(quote)
+                    };
+                    if !quoted('"') && !quoted('\'') {
+                        return Err(tracer_dyn_err!(format!(
+                            "Invalid include '{} {} {}' at offset {}{}, expected 'include \"name\"'",
+                            open_tag,
+                            expression,
+                            close_tag,
+                            tag.start(), in_partial(partial)
+                        )));
+                    }
+                    current(&mut root, &mut open).push(Node::Include(
+                        rest[1..rest.len() - 1].to_string(),
+                        tag.start(),
+                    ));
+                }
+                "else" => match open.last_mut() {
+                    Some(block) if block.otherwise.is_none() && block.name() == "if" => {
+                        block.otherwise = Some(Vec::new())
+                    }
+                    _ => return Err(unexpected()),
+                },
+                "endif" | "endfor" => {
+                    let block = match open.pop() {
+                        Some(block) if keyword[3..] == *block.name() => block,
+                        _ => return Err(unexpected()),
+                    };
+                    let node = match block.kind {
+                        OpenKind::If(condition) => Node::If {
+                            condition,
+                            then: block.body,
+                            otherwise: block.otherwise.unwrap_or_default(),
+                        },
+                        OpenKind::For { variable, iterable } => Node::For {
+                            variable,
+                            iterable,
+                            body: block.body,
+                        },
+                    };
+                    current(&mut root, &mut open).push(node);
+                }
+                _ => {
+                    return Err(tracer_dyn_err!(format!(
+                        "Unknown block '{} {} {}' at offset {}{}",
+                        open_tag,
+                        expression,
+                        close_tag,
+                        tag.start(),
+                        in_partial(partial)
+                    )))
+                }
+            }
+        }
+
+        if let Some(block) = open.last() {
+            return Err(tracer_dyn_err!(format!(
+                "Unterminated '{} {} {}' at offset {}{}",
+                open_tag,
+                block.name(),
+                close_tag,
+                block.offset,
+                in_partial(partial)
+            )));
+        }
+        let mut text = &template[last..];
+        if trim_next {
+            text = text.trim_start();
+        }
+        if !text.is_empty() {
+            root.push(Node::Text(text.to_string()));
+        }
+        Ok(root)
+    }
+}
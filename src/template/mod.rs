@@ -98,6 +98,7 @@
 //!   - `update_pipeline<P: Pipeline + Send + Sync + 'static>(name: &str, pipeline: P)`: Updates or adds a pipeline with the specified name.
 //!   - `get_pipeline(name: &str) -> Option<Box<dyn Pipeline + Send + Sync>>`: Retrieves a specific pipeline by name.
 //!   - `get_pipelines() -> PipelineManager`: Retrieves all available pipelines.
+//!   - `register_partial(&mut self, name: &str, template: &str) -> Result<(), DynTracerError>`: Parses and registers a partial that templates render with `{% include "name" %}`.
 //!   - `render(&self, template: &str, context: &TemplateContext) -> Result<String, DynTracerError>`: Renders a template using the provided context.
 //!
 //! ```rust
@@ -213,13 +214,13 @@ pub mod pipeline;
 pub mod pipelines;
 
 pub use engine::{TemplateContext, TemplateEngine};
-pub use pipeline::{Pipeline, PipelineValue};
+pub use pipeline::{Mapping, Pipeline, PipelineValue, Sequence};
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::template::engine::TemplateContext;
-    use crate::template::pipelines::DatePipeline;
+    use crate::template::pipelines::{DatePipeline, PrefixPipeline};
     use crate::time::DateTime;
     use core::{any::Any, fmt};
 
@@ -431,4 +432,741 @@ mod tests {
             handle.join().unwrap();
         }
     }
+
+    #[test]
+    fn test_render_if_blocks() {
+        let engine = TemplateEngine::new();
+        let mut context = TemplateContext::new();
+        context.insert("name", Box::new("John".to_string()));
+        context.insert("empty", Box::new("".to_string()));
+        context.insert("zero", Box::new(0));
+        context.insert("count", Box::new(3));
+
+        let render = |template: &str| engine.render(template, &context).unwrap();
+        assert_eq!(render("{% if name %}Hi {{ name }}{% endif %}!"), "Hi John!");
+        assert_eq!(
+            render("{% if empty %}set{% else %}unset{% endif %}"),
+            "unset"
+        );
+        assert_eq!(
+            render("{% if zero %}set{% else %}unset{% endif %}"),
+            "unset"
+        );
+        assert_eq!(render("{% if count %}set{% else %}unset{% endif %}"), "set");
+        assert_eq!(
+            render("{% if unknown %}set{% else %}unset{% endif %}"),
+            "unset"
+        );
+        assert_eq!(
+            render("{% if name == \"John\" %}yes{% else %}no{% endif %}"),
+            "yes"
+        );
+        assert_eq!(
+            render("{% if name != 'John' %}yes{% else %}no{% endif %}"),
+            "no"
+        );
+        assert_eq!(render("{% if count == 3 %}three{% endif %}"), "three");
+        assert_eq!(
+            render("{% if name != \"x==y\" %}yes{% else %}no{% endif %}"),
+            "yes"
+        );
+        assert_eq!(
+            render("{% if 'a!=b' == \"a!=b\" %}yes{% else %}no{% endif %}"),
+            "yes"
+        );
+    }
+
+    #[test]
+    fn test_render_nested_if_blocks() {
+        let engine = TemplateEngine::new();
+        let mut context = TemplateContext::new();
+        context.insert("user", Box::new("John".to_string()));
+        context.insert("role", Box::new("admin".to_string()));
+
+        let template = "{% if user %}[{% if role == \"admin\" %}admin{% else %}user{% endif %}]\
+                        {% else %}anonymous{% endif %}";
+        assert_eq!(engine.render(template, &context).unwrap(), "[admin]");
+
+        context.insert("role", Box::new("guest".to_string()));
+        assert_eq!(engine.render(template, &context).unwrap(), "[user]");
+    }
+
+    #[test]
+    fn test_render_unterminated_if_block() {
+        let engine = TemplateEngine::new();
+        let mut context = TemplateContext::new();
+        context.insert("name", Box::new("John".to_string()));
+
+        let result = engine.render("Hello{% if name %}, {{ name }}", &context);
+        assert!(result
+            .err()
+            .unwrap()
+            .err_to_string()
+            .contains("Unterminated '{% if %}' at offset 5"));
+
+        assert!(engine.render("{% endif %}", &context).is_err());
+        assert!(engine
+            .render("{% if name %}{% else %}{% else %}{% endif %}", &context)
+            .is_err());
+        assert!(engine
+            .render("{% raw %}", &context)
+            .err()
+            .unwrap()
+            .err_to_string()
+            .contains("Unknown block '{% raw %}' at offset 0"));
+    }
+
+    #[test]
+    fn test_render_for_blocks() {
+        let engine = TemplateEngine::new();
+        let mut context = TemplateContext::new();
+        let list: Vec<Box<dyn PipelineValue + Send + Sync>> = vec![
+            Box::new("a".to_string()),
+            Box::new("b".to_string()),
+            Box::new("c".to_string()),
+        ];
+        context.insert("list", Box::new(Sequence::from(list)));
+        context.insert("empty", Box::new(Sequence::new()));
+
+        let template =
+            "[{% for item in list %}{% if loop.index != 1 %},{% endif %}{{ item }}{% endfor %}]";
+        assert_eq!(engine.render(template, &context).unwrap(), "[a,b,c]");
+
+        let template = "{% for item in list %}{{ loop.index }}={{ item }};{% endfor %}";
+        assert_eq!(engine.render(template, &context).unwrap(), "1=a;2=b;3=c;");
+
+        let template = "{% for item in empty %}{{ item }}{% endfor %}done";
+        assert_eq!(engine.render(template, &context).unwrap(), "done");
+
+        assert_eq!(engine.render("{{ list }}", &context).unwrap(), "a,b,c");
+    }
+
+    #[test]
+    fn test_render_nested_for_blocks() {
+        let engine = TemplateEngine::new();
+        let mut context = TemplateContext::new();
+        let rows: Vec<Box<dyn PipelineValue + Send + Sync>> = vec![
+            Box::new(Sequence::from(vec![
+                Box::new(1) as Box<dyn PipelineValue + Send + Sync>,
+                Box::new(2),
+            ])),
+            Box::new(Sequence::from(vec![
+                Box::new(3) as Box<dyn PipelineValue + Send + Sync>
+            ])),
+        ];
+        context.insert("rows", Box::new(Sequence::from(rows)));
+        context.insert("item", Box::new("outer".to_string()));
+
+        let template = "{% for row in rows %}({% for item in row %}{{ item }}{% endfor %}){% endfor %}{{ item }}";
+        assert_eq!(engine.render(template, &context).unwrap(), "(12)(3)outer");
+    }
+
+    #[test]
+    fn test_render_invalid_for_blocks() {
+        let engine = TemplateEngine::new();
+        let mut context = TemplateContext::new();
+        context.insert("name", Box::new("John".to_string()));
+
+        assert!(engine
+            .render("{% for item in name %}{{ item }}{% endfor %}", &context)
+            .err()
+            .unwrap()
+            .err_to_string()
+            .contains("'name' is not a sequence"));
+        assert!(engine
+            .render("{% for item in name %}{% endif %}", &context)
+            .is_err());
+        assert!(engine
+            .render("{% for item %}{% endfor %}", &context)
+            .is_err());
+        assert!(engine
+            .render("{% for item in name %}", &context)
+            .err()
+            .unwrap()
+            .err_to_string()
+            .contains("Unterminated '{% for %}'"));
+    }
+
+    fn user_context() -> TemplateContext {
+        let mut address = Mapping::new();
+        address.insert("city".to_string(), Box::new("Oslo".to_string()));
+        let mut user = Mapping::new();
+        user.insert("name".to_string(), Box::new("John".to_string()));
+        user.insert("address".to_string(), Box::new(address));
+        user.insert(
+            "tags".to_string(),
+            Box::new(Sequence::from(vec![
+                Box::new("admin".to_string()) as Box<dyn PipelineValue + Send + Sync>
+            ])),
+        );
+
+        let mut context = TemplateContext::new();
+        context.insert("user", Box::new(user));
+        context
+    }
+
+    #[test]
+    fn test_render_dotted_access() {
+        let engine = TemplateEngine::new();
+        let context = user_context();
+
+        assert_eq!(engine.render("{{ user.name }}", &context).unwrap(), "John");
+        assert_eq!(
+            engine.render("{{ user.address.city }}", &context).unwrap(),
+            "Oslo"
+        );
+        assert_eq!(
+            engine.render("{{ user.tags.0 }}", &context).unwrap(),
+            "admin"
+        );
+        assert_eq!(
+            engine
+                .render(
+                    "{% if user.address.city == 'Oslo' %}yes{% endif %}",
+                    &context
+                )
+                .unwrap(),
+            "yes"
+        );
+    }
+
+    #[test]
+    fn test_render_dotted_access_missing_segment() {
+        let engine = TemplateEngine::new();
+        let context = user_context();
+
+        assert_eq!(
+            engine.render("{{ user.missing.city }}", &context).unwrap(),
+            "{{ user.missing.city }}"
+        );
+        assert_eq!(
+            engine.render("{{ user.name.first }}", &context).unwrap(),
+            "{{ user.name.first }}"
+        );
+        assert_eq!(
+            engine.render("{{ user.tags.1 }}", &context).unwrap(),
+            "{{ user.tags.1 }}"
+        );
+    }
+
+    #[test]
+    fn test_render_strict_mode() {
+        let mut context = TemplateContext::new();
+        context.insert("name", Box::new("John".to_string()));
+
+        let lenient = TemplateEngine::new();
+        assert_eq!(
+            lenient
+                .render("{{ name }} {{ unknown }}", &context)
+                .unwrap(),
+            "John {{ unknown }}"
+        );
+
+        let strict = TemplateEngine::new().strict(true);
+        assert_eq!(strict.render("{{ name }}", &context).unwrap(), "John");
+        let error = strict
+            .render(
+                "Hi {{ name }}, {% if name %}{{ unknown | upper }}{% endif %}",
+                &context,
+            )
+            .unwrap_err();
+        assert!(error
+            .err_to_string()
+            .contains("Unresolved variable 'unknown' at offset 28"));
+    }
+
+    #[test]
+    fn test_engine_scoped_pipelines() {
+        let mut context = TemplateContext::new();
+        context.insert("value", Box::new(TestValue(21)));
+        context.insert("name", Box::new("John".to_string()));
+
+        let mut engine = TemplateEngine::new()
+            .with_pipeline("scoped_double", TestValuePipeline::new())
+            .with_pipeline("prefix", PrefixPipeline::new().options("local:"));
+        let other = TemplateEngine::new();
+
+        assert_eq!(
+            engine
+                .render("{{ value | scoped_double }}", &context)
+                .unwrap(),
+            "42"
+        );
+        assert_eq!(
+            engine
+                .render("{{ name | prefix: 'global:' }}", &context)
+                .unwrap(),
+            "global:John"
+        );
+        assert_eq!(
+            engine.render("{{ name | prefix }}", &context).unwrap(),
+            "local:John"
+        );
+        assert!(other
+            .render("{{ value | scoped_double }}", &context)
+            .is_err());
+        assert!(TemplateEngine::get_pipeline("scoped_double").is_none());
+
+        engine.reload_pipelines();
+        assert_eq!(
+            engine
+                .render("{{ value | scoped_double }}", &context)
+                .unwrap(),
+            "42"
+        );
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_template_context_from_json() {
+        let response: serde_json::Value = serde_json::from_str(
+            r#"{
+                "user": {
+                    "name": "John",
+                    "age": 42,
+                    "score": 9.5,
+                    "active": true,
+                    "deleted": false,
+                    "manager": null,
+                    "address": { "city": "Oslo" },
+                    "roles": ["admin", "dev"]
+                },
+                "total": 3000000000
+            }"#,
+        )
+        .unwrap();
+        let context = TemplateContext::from_json(response);
+        let engine = TemplateEngine::new();
+        let render = |template: &str| engine.render(template, &context).unwrap();
+
+        assert_eq!(render("{{ user.name }} ({{ user.age }})"), "John (42)");
+        assert_eq!(render("{{ user.address.city | upper }}"), "OSLO");
+        assert_eq!(render("{{ user.roles.1 }}"), "dev");
+        assert_eq!(render("{{ user.score }} {{ total }}"), "9.5 3000000000");
+        assert_eq!(
+            render("{% for role in user.roles %}[{{ role }}]{% endfor %}"),
+            "[admin][dev]"
+        );
+        assert_eq!(render("{% if user.age == 42 %}yes{% endif %}"), "yes");
+        assert_eq!(render("{% if user.active %}active{% endif %}"), "active");
+        assert_eq!(
+            render("{% if user.deleted %}deleted{% else %}kept{% endif %}"),
+            "kept"
+        );
+        assert_eq!(
+            render("{% if user.manager %}managed{% else %}none{% endif %}"),
+            "none"
+        );
+
+        assert!(TemplateContext::from_json(serde_json::json!([1, 2]))
+            .get("0")
+            .is_none());
+    }
+
+    #[test]
+    fn test_render_trim_markers() {
+        let engine = TemplateEngine::new();
+        let mut context = TemplateContext::new();
+        context.insert("x", Box::new("value".to_string()));
+
+        let render = |template: &str| engine.render(template, &context).unwrap();
+        assert_eq!(render("a  {{ x }}  b"), "a  value  b");
+        assert_eq!(render("a  {{- x -}}  b"), "avalueb");
+        assert_eq!(render("a \n\t{{- x }}\n b"), "avalue\n b");
+        assert_eq!(render("a\n {{ x -}} \n\nb"), "a\n valueb");
+        assert_eq!(render("{{- x -}}"), "value");
+
+        // Markers only trim at tags, and offsets refer to the template as written.
+        assert_eq!(render("a -}}  {{ x }}  {{- b"), "a -}}  value  {{- b");
+        assert_eq!(render("{{- missing -}}  b"), "{{ missing }}b");
+        let error = TemplateEngine::new()
+            .strict(true)
+            .render("a  \n {{- missing }}", &context)
+            .unwrap_err();
+        assert!(error
+            .err_to_string()
+            .contains("Unresolved variable 'missing' at offset 5"));
+    }
+
+    #[test]
+    fn test_render_trim_markers_in_blocks() {
+        let engine = TemplateEngine::new();
+        let mut context = TemplateContext::new();
+        let ids: Vec<Box<dyn PipelineValue + Send + Sync>> = vec![Box::new(1), Box::new(2)];
+        context.insert("ids", Box::new(Sequence::from(ids)));
+        context.insert("name", Box::new("John".to_string()));
+
+        let template = r#"{
+    "name": "{{ name }}",
+    "ids": [
+        {%- for id in ids -%}
+            {%- if loop.index != 1 %}, {% endif -%}
+            {{ id }}
+        {%- endfor -%}
+    ]
+}"#;
+        assert_eq!(
+            engine.render(template, &context).unwrap(),
+            "{\n    \"name\": \"John\",\n    \"ids\": [1, 2]\n}"
+        );
+
+        // Without markers the whitespace around the tags is kept.
+        let template = "[\n  {% for id in ids %}{{ id }} {% endfor %}\n]";
+        assert_eq!(engine.render(template, &context).unwrap(), "[\n  1 2 \n]");
+    }
+
+    #[test]
+    fn test_render_custom_delimiters() {
+        let mut context = TemplateContext::new();
+        context.insert("name", Box::new("John".to_string()));
+        context.insert("count", Box::new(3));
+
+        let engine = TemplateEngine::new().with_delimiters("<%", "%>");
+        assert_eq!(
+            engine
+                .render("{{ name }} is <% name | upper %>, {{{{ raw }}}}", &context)
+                .unwrap(),
+            "{{ name }} is JOHN, {{{{ raw }}}}"
+        );
+        assert_eq!(
+            engine
+                .render("{% if count == 3 %}<%- count -%>  {% endif %}", &context)
+                .unwrap(),
+            "3"
+        );
+
+        // Delimiters made of regex metacharacters are matched literally.
+        let engine = TemplateEngine::new()
+            .with_delimiters("[[", "]]")
+            .with_block_delimiters("(*", "*)")
+            .strict(true);
+        let template =
+            "key: {{ .Values.name }}\n{% raw %}\n(* if name -*)\n  name: [[ name ]]\n(*- endif *)";
+        assert_eq!(
+            engine.render(template, &context).unwrap(),
+            "key: {{ .Values.name }}\n{% raw %}\nname: John"
+        );
+        assert!(engine
+            .render("(* if name *)", &context)
+            .unwrap_err()
+            .err_to_string()
+            .contains("Unterminated '(* if *)' at offset 0"));
+    }
+
+    #[test]
+    fn test_render_clashing_delimiters() {
+        let context = TemplateContext::new();
+
+        let engine = TemplateEngine::new().with_delimiters("{%", "%}");
+        assert!(engine
+            .render("text", &context)
+            .unwrap_err()
+            .err_to_string()
+            .contains("Template delimiters must differ from the block delimiters"));
+        assert!(TemplateEngine::new()
+            .with_block_delimiters("", "%}")
+            .render("text", &context)
+            .is_err());
+
+        // Only the final delimiters count, whatever the order of the builder calls.
+        let engine = TemplateEngine::new()
+            .with_delimiters("{%", "%}")
+            .with_block_delimiters("<%", "%>");
+        assert_eq!(engine.render("{% x %}", &context).unwrap(), "{% x %}");
+    }
+
+    #[test]
+    fn test_string_pipelines() {
+        let engine = TemplateEngine::new();
+        let mut context = TemplateContext::new();
+        context.insert("name", Box::new("  John Doe ".to_string()));
+        context.insert("empty", Box::new("".to_string()));
+        context.insert("city", Box::new("Zürich".to_string()));
+
+        let render = |template: &str| engine.render(template, &context).unwrap();
+        assert_eq!(render("{{ name | upper }}"), "  JOHN DOE ");
+        assert_eq!(render("{{ name | lower }}"), "  john doe ");
+        assert_eq!(render("{{ name | trim }}"), "John Doe");
+        assert_eq!(render("{{ name | trim | truncate:4 }}"), "John");
+        assert_eq!(render("{{ name | trim | truncate:100 }}"), "John Doe");
+        assert_eq!(render("{{ name | trim | truncate }}"), "John Doe");
+        assert_eq!(render("{{ empty | default:'anonymous' }}"), "anonymous");
+        assert_eq!(
+            render("{{ name | trim | default:'anonymous' }}"),
+            "John Doe"
+        );
+    }
+
+    #[test]
+    fn test_math_pipelines() {
+        let engine = TemplateEngine::new();
+        let mut context = TemplateContext::new();
+        context.insert("n", Box::new(10));
+        context.insert("price", Box::new(19.99));
+        context.insert("text", Box::new("7".to_string()));
+        context.insert("name", Box::new("John".to_string()));
+
+        let render = |template: &str| engine.render(template, &context).unwrap();
+        assert_eq!(render("{{ n | add:5 }}"), "15");
+        assert_eq!(render("{{ n | sub:12 }}"), "-2");
+        assert_eq!(render("{{ n | mul:3 }}"), "30");
+        assert_eq!(render("{{ n | div:2 }}"), "5");
+        assert_eq!(render("{{ n | div:4 }}"), "2.5");
+        assert_eq!(render("{{ n | add:0.5 }}"), "10.5");
+        assert_eq!(render("{{ text | add:1 }}"), "8");
+        assert_eq!(render("{{ price | mul:3 | round:2 }}"), "59.97");
+        assert_eq!(render("{{ price | round }}"), "20");
+        assert_eq!(render("{{ n | round:2 }}"), "10.00");
+        assert_eq!(render("{{ n | mul:2 | add:1 | div:3 }}"), "7");
+
+        // Values and operands that are not numbers are left unchanged.
+        assert_eq!(render("{{ name | add:1 }}"), "John");
+        assert_eq!(render("{{ n | add:x }}"), "10");
+        assert_eq!(render("{{ name | round:2 }}"), "John");
+
+        // Strict engines fail on operands that are not numbers.
+        let strict = TemplateEngine::new().strict(true);
+        assert_eq!(strict.render("{{ n | add:5 }}", &context).unwrap(), "15");
+        let error = strict
+            .render("{{ n | add:x }}", &context)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("'add'") && error.contains("'x'"),
+            "{}",
+            error
+        );
+        assert!(strict.render("{{ n | round:-1 }}", &context).is_err());
+    }
+
+    #[test]
+    fn test_render_include() {
+        let mut engine = TemplateEngine::new();
+        engine
+            .register_partial("greeting", "Hello, {{ name }}!")
+            .unwrap();
+        engine
+            .register_partial(
+                "items",
+                "{% for item in items %}{% include 'item' %}{% endfor %}",
+            )
+            .unwrap();
+        engine
+            .register_partial("item", "[{{ item | upper }}]")
+            .unwrap();
+
+        let mut context = TemplateContext::new();
+        let items: Vec<Box<dyn PipelineValue + Send + Sync>> =
+            vec![Box::new("a".to_string()), Box::new("b".to_string())];
+        context.insert("name", Box::new("John".to_string()));
+        context.insert("items", Box::new(Sequence::from(items)));
+
+        assert_eq!(
+            engine
+                .render("<p>{% include \"greeting\" %}</p>", &context)
+                .unwrap(),
+            "<p>Hello, John!</p>"
+        );
+        assert_eq!(
+            engine.render("{% include \"items\" %}", &context).unwrap(),
+            "[A][B]"
+        );
+
+        let error = engine
+            .render("{% include \"unknown\" %}", &context)
+            .unwrap_err();
+        assert!(error
+            .err_to_string()
+            .contains("Partial 'unknown' not found at offset 0"));
+        assert!(engine.render("{% include greeting %}", &context).is_err());
+    }
+
+    #[test]
+    fn test_render_include_errors() {
+        let mut engine = TemplateEngine::new().strict(true);
+
+        // Syntax errors are reported when registering the partial.
+        let error = engine
+            .register_partial("broken", "{% if name %}")
+            .unwrap_err();
+        assert!(error
+            .err_to_string()
+            .contains("Unterminated '{% if %}' at offset 0 in partial 'broken'"));
+        assert!(engine
+            .render("{% include \"broken\" %}", &TemplateContext::new())
+            .unwrap_err()
+            .err_to_string()
+            .contains("Partial 'broken' not found"));
+
+        engine
+            .register_partial("user", "Hello, {{ name }}!")
+            .unwrap();
+        let error = engine
+            .render("{% include \"user\" %}", &TemplateContext::new())
+            .unwrap_err();
+        assert!(error
+            .err_to_string()
+            .contains("Unresolved variable 'name' at offset 7 in partial 'user'"));
+    }
+
+    #[test]
+    fn test_render_include_recursion() {
+        let mut engine = TemplateEngine::new();
+        engine
+            .register_partial("loop", "again {% include \"loop\" %}")
+            .unwrap();
+        engine
+            .register_partial("ping", "{% include \"pong\" %}")
+            .unwrap();
+        engine
+            .register_partial("pong", "{% include \"ping\" %}")
+            .unwrap();
+        let context = TemplateContext::new();
+
+        for template in ["{% include \"loop\" %}", "{% include \"ping\" %}"] {
+            let error = engine.render(template, &context).unwrap_err();
+            assert!(error.err_to_string().contains(&format!(
+                "exceeds the maximum include depth of {}",
+                engine::MAX_INCLUDE_DEPTH
+            )));
+        }
+    }
+
+    #[test]
+    fn test_humanize_bytes() {
+        let engine = TemplateEngine::new();
+        let render = |bytes: u64, template: &str| {
+            let mut context = TemplateContext::new();
+            context.insert("bytes", Box::new(bytes));
+            engine.render(template, &context).unwrap()
+        };
+
+        assert_eq!(render(0, "{{ bytes | humanize_bytes }}"), "0 B");
+        assert_eq!(render(1023, "{{ bytes | humanize_bytes }}"), "1023 B");
+        assert_eq!(render(1024, "{{ bytes | humanize_bytes }}"), "1 KiB");
+        assert_eq!(
+            render(1536 * 1024, "{{ bytes | humanize_bytes }}"),
+            "1.5 MiB"
+        );
+        assert_eq!(
+            render(1024 * 1024 - 1, "{{ bytes | humanize_bytes }}"),
+            "1 MiB"
+        );
+        assert_eq!(render(u64::MAX, "{{ bytes | humanize_bytes }}"), "16 EiB");
+
+        assert_eq!(render(999, "{{ bytes | humanize_bytes:si }}"), "999 B");
+        assert_eq!(render(1000, "{{ bytes | humanize_bytes:si }}"), "1 kB");
+        assert_eq!(
+            render(1_250_000, "{{ bytes | humanize_bytes:si }}"),
+            "1.3 MB"
+        );
+        assert_eq!(render(1024, "{{ bytes | humanize_bytes:si }}"), "1 kB");
+
+        let mut context = TemplateContext::new();
+        context.insert("text", Box::new("2048".to_string()));
+        context.insert("name", Box::new("John".to_string()));
+        assert_eq!(
+            engine
+                .render("{{ text | humanize_bytes }}", &context)
+                .unwrap(),
+            "2 KiB"
+        );
+        assert_eq!(
+            engine
+                .render("{{ name | humanize_bytes }}", &context)
+                .unwrap(),
+            "John"
+        );
+    }
+
+    #[test]
+    fn test_humanize_duration() {
+        let engine = TemplateEngine::new();
+        let render = |secs: f64| {
+            let mut context = TemplateContext::new();
+            context.insert("secs", Box::new(secs));
+            engine
+                .render("{{ secs | humanize_duration }}", &context)
+                .unwrap()
+        };
+
+        assert_eq!(render(0.0), "0s");
+        assert_eq!(render(0.5), "0s");
+        assert_eq!(render(59.0), "59s");
+        assert_eq!(render(60.0), "1m");
+        assert_eq!(render(7385.0), "2h 3m");
+        assert_eq!(render(3601.0), "1h");
+        assert_eq!(render(90061.0), "1d 1h");
+        assert_eq!(render(-125.0), "-2m 5s");
+    }
+
+    #[test]
+    fn test_div_by_zero() {
+        let engine = TemplateEngine::new();
+        let mut context = TemplateContext::new();
+        context.insert("n", Box::new(10));
+        context.insert("price", Box::new(19.99));
+
+        assert_eq!(
+            engine.render("{{ n | div:0 }}", &context).unwrap(),
+            pipelines::math::DIVISION_BY_ZERO
+        );
+        assert_eq!(
+            engine.render("{{ price | div:0.0 }}", &context).unwrap(),
+            "NaN"
+        );
+
+        let err = TemplateEngine::new()
+            .strict(true)
+            .render("{{ n | div:0 }}", &context)
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Division by zero for 'div'"),
+            "{}",
+            err
+        );
+        let strict = TemplateEngine::new().strict(true);
+        assert_eq!(strict.render("{{ n | div:4 }}", &context).unwrap(), "2.5");
+    }
+
+    #[test]
+    fn test_truncate_multibyte() {
+        let engine = TemplateEngine::new();
+        let mut context = TemplateContext::new();
+        context.insert("city", Box::new("Zürich".to_string()));
+        context.insert("emoji", Box::new("😀😃😄".to_string()));
+
+        assert_eq!(
+            engine.render("{{ city | truncate:2 }}", &context).unwrap(),
+            "Zü"
+        );
+        assert_eq!(
+            engine.render("{{ emoji | truncate:2 }}", &context).unwrap(),
+            "😀😃"
+        );
+    }
+
+    #[test]
+    fn test_escape_pipelines() {
+        let engine = TemplateEngine::new();
+        let mut context = TemplateContext::new();
+        context.insert(
+            "value",
+            Box::new(r#"<a href="x">Tom & 'Jerry'</a>"#.to_string()),
+        );
+        context.insert("json", Box::new("say \"hi\"\\\n\t<done>\u{1}".to_string()));
+
+        assert_eq!(
+            engine
+                .render("{{ value | escape_html }}", &context)
+                .unwrap(),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#39;Jerry&#39;&lt;/a&gt;"
+        );
+        assert_eq!(
+            engine
+                .render(r#"{"text": "{{ json | escape_json }}"}"#, &context)
+                .unwrap(),
+            r#"{"text": "say \"hi\"\\\n\t<done>\u0001"}"#
+        );
+    }
 }
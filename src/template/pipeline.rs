@@ -1,8 +1,12 @@
 use alloc::boxed::Box;
+use alloc::string::String;
 use alloc::sync::Arc;
+use alloc::vec::Vec;
 use core::any::Any;
 use core::fmt::Debug;
 use core::fmt::Display;
+use core::ops::{Deref, DerefMut};
+use hashbrown::HashMap;
 
 /// A trait for pipeline transformations.
 pub trait Pipeline: Debug {
@@ -31,6 +35,16 @@ pub trait Pipeline: Debug {
     /// A boxed pipeline instance with the specified options.
     fn options(&self, options: &str) -> Box<dyn Pipeline + Send + Sync>;
 
+    /// Like `options`, but fails with a description of the problem if the pipeline cannot use the
+    /// options. Strict engines use this instead of `options`. By default all options are accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options for the pipeline.
+    fn try_options(&self, options: &str) -> Result<Box<dyn Pipeline + Send + Sync>, String> {
+        Ok(self.options(options))
+    }
+
     /// Clones the pipeline into a boxed instance.
     ///
     /// # Returns
@@ -39,6 +53,27 @@ pub trait Pipeline: Debug {
     fn boxed_clone(&self) -> Box<dyn Pipeline + Send + Sync>;
 }
 
+impl Pipeline for Box<dyn Pipeline + Send + Sync> {
+    fn format(
+        &self,
+        pipe_object: &Box<dyn PipelineValue + Send + Sync>,
+    ) -> Box<dyn PipelineValue + Send + Sync> {
+        (**self).format(pipe_object)
+    }
+
+    fn options(&self, options: &str) -> Box<dyn Pipeline + Send + Sync> {
+        (**self).options(options)
+    }
+
+    fn try_options(&self, options: &str) -> Result<Box<dyn Pipeline + Send + Sync>, String> {
+        (**self).try_options(options)
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pipeline + Send + Sync> {
+        (**self).boxed_clone()
+    }
+}
+
 /// A trait for values that can be used in pipelines.
 pub trait PipelineValue: Any + Display {
     /// Returns the value as a reference to `Any`.
@@ -77,6 +112,30 @@ impl PipelineValue for i32 {
         Box::new(Clone::clone(self))
     }
 }
+impl PipelineValue for i64 {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn boxed_clone(&self) -> Box<dyn PipelineValue + Send + Sync> {
+        Box::new(Clone::clone(self))
+    }
+}
+impl PipelineValue for u64 {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn boxed_clone(&self) -> Box<dyn PipelineValue + Send + Sync> {
+        Box::new(Clone::clone(self))
+    }
+}
+impl PipelineValue for bool {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn boxed_clone(&self) -> Box<dyn PipelineValue + Send + Sync> {
+        Box::new(Clone::clone(self))
+    }
+}
 impl PipelineValue for f32 {
     fn as_any(&self) -> &dyn Any {
         self
@@ -109,3 +168,178 @@ impl PipelineValue for Arc<String> {
         Box::new(Clone::clone(self))
     }
 }
+
+/// A sequence of pipeline values that can be iterated with `{% for %}` blocks. Rendered directly,
+/// the elements are joined with commas.
+///
+/// # Example
+///
+/// ```
+/// use gearbox::template::{PipelineValue, Sequence, TemplateContext, TemplateEngine};
+///
+/// let mut context = TemplateContext::new();
+/// let list: Vec<Box<dyn PipelineValue + Send + Sync>> =
+///     vec![Box::new("a".to_string()), Box::new("b".to_string())];
+/// context.insert("list", Box::new(Sequence::from(list)));
+///
+/// let result = TemplateEngine::new()
+///     .render("{% for item in list %}<{{ item }}>{% endfor %}", &context)
+///     .unwrap();
+/// assert_eq!(result, "<a><b>");
+/// ```
+pub struct Sequence(Vec<Box<dyn PipelineValue + Send + Sync>>);
+
+impl Sequence {
+    /// Creates an empty sequence.
+    pub fn new() -> Self {
+        Sequence(Vec::new())
+    }
+
+    /// Appends a value to the sequence.
+    pub fn push(&mut self, value: Box<dyn PipelineValue + Send + Sync>) {
+        self.0.push(value);
+    }
+}
+
+impl Default for Sequence {
+    fn default() -> Self {
+        Sequence::new()
+    }
+}
+
+impl From<Vec<Box<dyn PipelineValue + Send + Sync>>> for Sequence {
+    fn from(values: Vec<Box<dyn PipelineValue + Send + Sync>>) -> Self {
+        Sequence(values)
+    }
+}
+
+impl Deref for Sequence {
+    type Target = Vec<Box<dyn PipelineValue + Send + Sync>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Clone for Sequence {
+    fn clone(&self) -> Self {
+        Sequence(self.0.iter().map(|t| t.boxed_clone()).collect())
+    }
+}
+
+impl Debug for Sequence {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|t| t.to_string()))
+            .finish()
+    }
+}
+
+impl Display for Sequence {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (index, value) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        Ok(())
+    }
+}
+
+impl PipelineValue for Sequence {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn boxed_clone(&self) -> Box<dyn PipelineValue + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+/// A map of named pipeline values. Nested values are addressed with dotted placeholders such as
+/// `{{ user.address.city }}`. Rendered directly, the entries are written as `key=value` pairs
+/// sorted by key and joined with commas.
+///
+/// # Example
+///
+/// ```
+/// use gearbox::template::{Mapping, TemplateContext, TemplateEngine};
+///
+/// let mut address = Mapping::new();
+/// address.insert("city".to_string(), Box::new("Oslo".to_string()));
+/// let mut user = Mapping::new();
+/// user.insert("address".to_string(), Box::new(address));
+///
+/// let mut context = TemplateContext::new();
+/// context.insert("user", Box::new(user));
+///
+/// let result = TemplateEngine::new()
+///     .render("{{ user.address.city }}", &context)
+///     .unwrap();
+/// assert_eq!(result, "Oslo");
+/// ```
+#[derive(Default)]
+pub struct Mapping(HashMap<String, Box<dyn PipelineValue + Send + Sync>>);
+
+impl Mapping {
+    /// Creates an empty mapping.
+    pub fn new() -> Self {
+        Mapping(HashMap::new())
+    }
+}
+
+impl Deref for Mapping {
+    type Target = HashMap<String, Box<dyn PipelineValue + Send + Sync>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Mapping {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Clone for Mapping {
+    fn clone(&self) -> Self {
+        Mapping(
+            self.0
+                .iter()
+                .map(|(k, v)| (k.clone(), v.boxed_clone()))
+                .collect(),
+        )
+    }
+}
+
+impl Debug for Mapping {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(k, v)| (k, v.to_string())))
+            .finish()
+    }
+}
+
+impl Display for Mapping {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut keys = self.0.keys().collect::<Vec<_>>();
+        keys.sort();
+        for (index, key) in keys.into_iter().enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}={}", key, self.0[key])?;
+        }
+        Ok(())
+    }
+}
+
+impl PipelineValue for Mapping {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn boxed_clone(&self) -> Box<dyn PipelineValue + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,104 @@
+use crate::template::pipeline::{Pipeline, PipelineValue};
+use core::fmt::Write;
+
+/// A pipeline escaping the HTML special characters `<`, `>`, `&`, `"` and `'`.
+#[derive(Debug, Clone, Default)]
+pub struct EscapeHtmlPipeline;
+
+impl EscapeHtmlPipeline {
+    /// Creates a new EscapeHtmlPipeline.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gearbox::template::pipelines::escape::EscapeHtmlPipeline;
+    ///
+    /// let pipeline = EscapeHtmlPipeline::new();
+    /// ```
+    pub fn new() -> Self {
+        EscapeHtmlPipeline
+    }
+}
+
+impl Pipeline for EscapeHtmlPipeline {
+    fn format(
+        &self,
+        pipe_object: &Box<dyn PipelineValue + Send + Sync>,
+    ) -> Box<dyn PipelineValue + Send + Sync + 'static> {
+        let value = pipe_object.to_string();
+        let mut escaped = String::with_capacity(value.len());
+        for c in value.chars() {
+            match c {
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '&' => escaped.push_str("&amp;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&#39;"),
+                c => escaped.push(c),
+            }
+        }
+        Box::new(escaped)
+    }
+
+    fn options(&self, _options: &str) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+/// A pipeline escaping values for use inside a JSON string literal. Quotes, backslashes and
+/// control characters are escaped, the surrounding quotes are not added.
+#[derive(Debug, Clone, Default)]
+pub struct EscapeJsonPipeline;
+
+impl EscapeJsonPipeline {
+    /// Creates a new EscapeJsonPipeline.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gearbox::template::pipelines::escape::EscapeJsonPipeline;
+    ///
+    /// let pipeline = EscapeJsonPipeline::new();
+    /// ```
+    pub fn new() -> Self {
+        EscapeJsonPipeline
+    }
+}
+
+impl Pipeline for EscapeJsonPipeline {
+    fn format(
+        &self,
+        pipe_object: &Box<dyn PipelineValue + Send + Sync>,
+    ) -> Box<dyn PipelineValue + Send + Sync + 'static> {
+        let value = pipe_object.to_string();
+        let mut escaped = String::with_capacity(value.len());
+        for c in value.chars() {
+            match c {
+                '"' => escaped.push_str("\\\""),
+                '\\' => escaped.push_str("\\\\"),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                '\t' => escaped.push_str("\\t"),
+                '\u{08}' => escaped.push_str("\\b"),
+                '\u{0c}' => escaped.push_str("\\f"),
+                c if (c as u32) < 0x20 => {
+                    let _ = write!(escaped, "\\u{:04x}", c as u32);
+                }
+                c => escaped.push(c),
+            }
+        }
+        Box::new(escaped)
+    }
+
+    fn options(&self, _options: &str) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,128 @@
+use crate::template::pipeline::{Pipeline, PipelineValue};
+use crate::template::pipelines::math::number_value;
+
+const BINARY_UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+const SI_UNITS: [&str; 7] = ["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+const DURATION_UNITS: [(u64, &str); 4] = [(86400, "d"), (3600, "h"), (60, "m"), (1, "s")];
+
+/// A pipeline rendering a number of bytes in the largest unit it fills, with at most one decimal,
+/// e.g. `1536` as `1.5 KiB`. Units are binary (`KiB`, `MiB`, ...) by default and decimal (`kB`,
+/// `MB`, ...) with `humanize_bytes:si`. Values that are not numbers are left unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct HumanizeBytesPipeline {
+    si: bool,
+}
+
+impl HumanizeBytesPipeline {
+    /// Creates a new HumanizeBytesPipeline using binary units.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gearbox::template::pipelines::humanize::HumanizeBytesPipeline;
+    ///
+    /// let pipeline = HumanizeBytesPipeline::new();
+    /// ```
+    pub fn new() -> Self {
+        HumanizeBytesPipeline { si: false }
+    }
+}
+
+impl Pipeline for HumanizeBytesPipeline {
+    fn format(
+        &self,
+        pipe_object: &Box<dyn PipelineValue + Send + Sync>,
+    ) -> Box<dyn PipelineValue + Send + Sync + 'static> {
+        let Some(bytes) = number_value(&**pipe_object) else {
+            return pipe_object.boxed_clone();
+        };
+        let (base, units) = match self.si {
+            true => (1000.0, SI_UNITS),
+            false => (1024.0, BINARY_UNITS),
+        };
+
+        let mut value = bytes.abs();
+        let mut unit = 0;
+        // Rounding may carry into the next unit, e.g. 1023.96 KiB renders as 1 MiB.
+        while unit < units.len() - 1 && (value * 10.0).round() / 10.0 >= base {
+            value /= base;
+            unit += 1;
+        }
+        // Rounded before formatting, as `format!` alone rounds ties to even.
+        let value = match unit {
+            0 => format!("{:.0}", value.round()),
+            _ => format!("{:.1}", (value * 10.0).round() / 10.0)
+                .trim_end_matches(".0")
+                .to_string(),
+        };
+        let sign = if bytes < 0.0 { "-" } else { "" };
+        Box::new(format!("{}{} {}", sign, value, units[unit]))
+    }
+
+    fn options(&self, options: &str) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(HumanizeBytesPipeline {
+            si: options.trim().eq_ignore_ascii_case("si"),
+        })
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+/// A pipeline rendering a number of seconds in the largest unit of days, hours, minutes and seconds
+/// it fills and the unit after it, e.g. `7385` as `2h 3m`. Smaller units are cut off, not rounded. Values that
+/// are not numbers are left unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct HumanizeDurationPipeline;
+
+impl HumanizeDurationPipeline {
+    /// Creates a new HumanizeDurationPipeline.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gearbox::template::pipelines::humanize::HumanizeDurationPipeline;
+    ///
+    /// let pipeline = HumanizeDurationPipeline::new();
+    /// ```
+    pub fn new() -> Self {
+        HumanizeDurationPipeline
+    }
+}
+
+impl Pipeline for HumanizeDurationPipeline {
+    fn format(
+        &self,
+        pipe_object: &Box<dyn PipelineValue + Send + Sync>,
+    ) -> Box<dyn PipelineValue + Send + Sync + 'static> {
+        let Some(secs) = number_value(&**pipe_object) else {
+            return pipe_object.boxed_clone();
+        };
+
+        let mut rest = secs.abs().trunc() as u64;
+        let Some(first) = DURATION_UNITS.iter().position(|(size, _)| rest >= *size) else {
+            return Box::new("0s".to_string());
+        };
+        // The largest unit and the one after it, so `3601` renders as `1h` and not `1h 1s`.
+        let parts: Vec<String> = DURATION_UNITS[first..]
+            .iter()
+            .take(2)
+            .filter_map(|(size, unit)| {
+                let count = rest / size;
+                rest %= size;
+                (count > 0).then(|| format!("{}{}", count, unit))
+            })
+            .collect();
+        let sign = if secs < 0.0 { "-" } else { "" };
+        Box::new(format!("{}{}", sign, parts.join(" ")))
+    }
+
+    fn options(&self, _options: &str) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,235 @@
+use crate::template::pipeline::{Pipeline, PipelineValue};
+
+/// What `div` renders when dividing by zero, instead of panicking or rendering `inf`. Strict
+/// engines fail on a zero divisor instead.
+pub const DIVISION_BY_ZERO: &str = "NaN";
+
+/// A number read from a pipeline value or from the options of a pipeline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+impl Number {
+    /// Reads a number from a numeric pipeline value, or from a value that renders as a number.
+    fn from_value(value: &(dyn PipelineValue + Send + Sync)) -> Option<Self> {
+        let value = value.as_any();
+        if let Some(t) = value.downcast_ref::<i32>() {
+            Some(Number::Int(*t as i64))
+        } else if let Some(t) = value.downcast_ref::<i64>() {
+            Some(Number::Int(*t))
+        } else if let Some(t) = value.downcast_ref::<u64>() {
+            Some(i64::try_from(*t).map_or(Number::Float(*t as f64), Number::Int))
+        } else if let Some(t) = value.downcast_ref::<f32>() {
+            Some(Number::Float(*t as f64))
+        } else if let Some(t) = value.downcast_ref::<f64>() {
+            Some(Number::Float(*t))
+        } else if let Some(t) = value.downcast_ref::<String>() {
+            Self::parse(t)
+        } else {
+            None
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        value
+            .parse::<i64>()
+            .map(Number::Int)
+            .ok()
+            .or_else(|| value.parse::<f64>().ok().map(Number::Float))
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            Number::Int(t) => t as f64,
+            Number::Float(t) => t,
+        }
+    }
+
+    fn is_zero(self) -> bool {
+        self.as_f64() == 0.0
+    }
+
+    /// Applies `int` if both numbers are integers and it does not overflow, `float` otherwise.
+    fn apply(
+        self,
+        other: Number,
+        int: fn(i64, i64) -> Option<i64>,
+        float: fn(f64, f64) -> f64,
+    ) -> Number {
+        match (self, other) {
+            (Number::Int(a), Number::Int(b)) => int(a, b)
+                .map(Number::Int)
+                .unwrap_or_else(|| Number::Float(float(a as f64, b as f64))),
+            (a, b) => Number::Float(float(a.as_f64(), b.as_f64())),
+        }
+    }
+
+    fn boxed(self) -> Box<dyn PipelineValue + Send + Sync + 'static> {
+        match self {
+            Number::Int(t) => Box::new(t),
+            Number::Float(t) => Box::new(t),
+        }
+    }
+}
+
+/// Reads a numeric pipeline value, or a value that renders as a number, as an `f64`.
+pub(super) fn number_value(value: &(dyn PipelineValue + Send + Sync)) -> Option<f64> {
+    Number::from_value(value).map(Number::as_f64)
+}
+
+/// The operation of a `MathPipeline`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathOp {
+    /// Adds the operand, e.g. `add:5`. Integers stay integers unless the result overflows.
+    Add,
+    /// Subtracts the operand, e.g. `sub:5`.
+    Sub,
+    /// Multiplies by the operand, e.g. `mul:1.5`.
+    Mul,
+    /// Divides by the operand, e.g. `div:4`. The result is an integer if the division of two
+    /// integers has no remainder. Dividing by zero renders `DIVISION_BY_ZERO`, or fails in a
+    /// strict engine.
+    Div,
+    /// Rounds to the number of decimals given as operand, e.g. `round:2`. The value is rendered
+    /// with exactly that many decimals, without operand it is rounded to an integer.
+    Round,
+}
+
+impl MathOp {
+    /// The name the operation is registered under in the default pipelines.
+    pub fn name(self) -> &'static str {
+        match self {
+            MathOp::Add => "add",
+            MathOp::Sub => "sub",
+            MathOp::Mul => "mul",
+            MathOp::Div => "div",
+            MathOp::Round => "round",
+        }
+    }
+}
+
+/// A pipeline doing arithmetic on numeric values, or on values that render as a number, with the
+/// number given as option. Values that are not numbers are left unchanged, as are all values if
+/// the option is not a number. Strict engines fail on such options instead.
+#[derive(Debug, Clone)]
+pub struct MathPipeline {
+    op: MathOp,
+    operand: Option<Number>,
+}
+
+impl MathPipeline {
+    /// Creates a new MathPipeline for `op` without an operand.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gearbox::template::pipelines::math::{MathOp, MathPipeline};
+    ///
+    /// let pipeline = MathPipeline::new(MathOp::Add);
+    /// ```
+    pub fn new(op: MathOp) -> Self {
+        MathPipeline { op, operand: None }
+    }
+
+    /// Parses the operand of the pipeline, the number of decimals for `MathOp::Round`.
+    fn parse_operand(&self, options: &str) -> Option<Number> {
+        match self.op {
+            MathOp::Round => options
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .map(|t| Number::Int(t as i64)),
+            _ => Number::parse(options),
+        }
+    }
+
+    fn round(value: Number, decimals: usize) -> Box<dyn PipelineValue + Send + Sync + 'static> {
+        match value {
+            Number::Int(t) if decimals == 0 => Box::new(t),
+            t => {
+                // `format!` alone rounds ties to even, round half away from zero like `f64::round`.
+                // An `f64` has no more than 15 significant decimals to round.
+                let rounded = match decimals {
+                    0..=15 => {
+                        let factor = 10f64.powi(decimals as i32);
+                        (t.as_f64() * factor).round() / factor
+                    }
+                    _ => t.as_f64(),
+                };
+                Box::new(format!("{:.*}", decimals, rounded))
+            }
+        }
+    }
+}
+
+impl Pipeline for MathPipeline {
+    fn format(
+        &self,
+        pipe_object: &Box<dyn PipelineValue + Send + Sync>,
+    ) -> Box<dyn PipelineValue + Send + Sync + 'static> {
+        let value = match Number::from_value(&**pipe_object) {
+            Some(value) => value,
+            None => return pipe_object.boxed_clone(),
+        };
+        if self.op == MathOp::Round {
+            let decimals = match self.operand {
+                Some(Number::Int(t)) => t as usize,
+                _ => 0,
+            };
+            return Self::round(value, decimals);
+        }
+        let operand = match self.operand {
+            Some(operand) => operand,
+            None => return pipe_object.boxed_clone(),
+        };
+        match self.op {
+            MathOp::Add => value.apply(operand, i64::checked_add, |a, b| a + b),
+            MathOp::Sub => value.apply(operand, i64::checked_sub, |a, b| a - b),
+            MathOp::Mul => value.apply(operand, i64::checked_mul, |a, b| a * b),
+            MathOp::Div if operand.is_zero() => return Box::new(DIVISION_BY_ZERO.to_string()),
+            MathOp::Div => value.apply(
+                operand,
+                |a, b| (a.checked_rem(b) == Some(0)).then(|| a / b),
+                |a, b| a / b,
+            ),
+            MathOp::Round => unreachable!("rounding is handled above"),
+        }
+        .boxed()
+    }
+
+    fn options(&self, options: &str) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(MathPipeline {
+            op: self.op,
+            operand: self.parse_operand(options),
+        })
+    }
+
+    fn try_options(&self, options: &str) -> Result<Box<dyn Pipeline + Send + Sync>, String> {
+        match self.parse_operand(options) {
+            Some(operand) if self.op == MathOp::Div && operand.is_zero() => {
+                Err(format!("Division by zero for '{}'", self.op.name()))
+            }
+            Some(operand) => Ok(Box::new(MathPipeline {
+                op: self.op,
+                operand: Some(operand),
+            })),
+            None if self.op == MathOp::Round => Err(format!(
+                "'{}' is not a number of decimals for '{}'",
+                options,
+                self.op.name()
+            )),
+            None => Err(format!(
+                "'{}' is not a number for '{}'",
+                options,
+                self.op.name()
+            )),
+        }
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
@@ -1,11 +1,19 @@
 pub mod date_time;
+pub mod escape;
+pub mod humanize;
+pub mod math;
 pub mod prefix;
+pub mod string;
 
 use crate::template::Pipeline;
 use core::ops::{Deref, DerefMut};
 pub use date_time::DatePipeline;
+pub use escape::{EscapeHtmlPipeline, EscapeJsonPipeline};
 use hashbrown::HashMap;
+pub use humanize::{HumanizeBytesPipeline, HumanizeDurationPipeline};
+pub use math::{MathOp, MathPipeline};
 pub use prefix::PrefixPipeline;
+pub use string::{DefaultPipeline, LowerPipeline, TrimPipeline, TruncatePipeline, UpperPipeline};
 
 #[derive(Debug, Default)]
 pub struct PipelineManager(HashMap<String, Box<dyn Pipeline + Send + Sync>>);
@@ -0,0 +1,201 @@
+use crate::template::pipeline::{Pipeline, PipelineValue};
+
+/// A pipeline converting values to upper case.
+#[derive(Debug, Clone, Default)]
+pub struct UpperPipeline;
+
+impl UpperPipeline {
+    /// Creates a new UpperPipeline.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gearbox::template::pipelines::string::UpperPipeline;
+    ///
+    /// let pipeline = UpperPipeline::new();
+    /// ```
+    pub fn new() -> Self {
+        UpperPipeline
+    }
+}
+
+impl Pipeline for UpperPipeline {
+    fn format(
+        &self,
+        pipe_object: &Box<dyn PipelineValue + Send + Sync>,
+    ) -> Box<dyn PipelineValue + Send + Sync + 'static> {
+        Box::new(pipe_object.to_string().to_uppercase())
+    }
+
+    fn options(&self, _options: &str) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+/// A pipeline converting values to lower case.
+#[derive(Debug, Clone, Default)]
+pub struct LowerPipeline;
+
+impl LowerPipeline {
+    /// Creates a new LowerPipeline.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gearbox::template::pipelines::string::LowerPipeline;
+    ///
+    /// let pipeline = LowerPipeline::new();
+    /// ```
+    pub fn new() -> Self {
+        LowerPipeline
+    }
+}
+
+impl Pipeline for LowerPipeline {
+    fn format(
+        &self,
+        pipe_object: &Box<dyn PipelineValue + Send + Sync>,
+    ) -> Box<dyn PipelineValue + Send + Sync + 'static> {
+        Box::new(pipe_object.to_string().to_lowercase())
+    }
+
+    fn options(&self, _options: &str) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+/// A pipeline removing leading and trailing whitespace.
+#[derive(Debug, Clone, Default)]
+pub struct TrimPipeline;
+
+impl TrimPipeline {
+    /// Creates a new TrimPipeline.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gearbox::template::pipelines::string::TrimPipeline;
+    ///
+    /// let pipeline = TrimPipeline::new();
+    /// ```
+    pub fn new() -> Self {
+        TrimPipeline
+    }
+}
+
+impl Pipeline for TrimPipeline {
+    fn format(
+        &self,
+        pipe_object: &Box<dyn PipelineValue + Send + Sync>,
+    ) -> Box<dyn PipelineValue + Send + Sync + 'static> {
+        Box::new(pipe_object.to_string().trim().to_string())
+    }
+
+    fn options(&self, _options: &str) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+/// A pipeline shortening values to at most the given number of characters, e.g. `truncate:10`.
+/// Without a valid length the value is left unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct TruncatePipeline {
+    length: Option<usize>,
+}
+
+impl TruncatePipeline {
+    /// Creates a new TruncatePipeline without a length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gearbox::template::pipelines::string::TruncatePipeline;
+    ///
+    /// let pipeline = TruncatePipeline::new();
+    /// ```
+    pub fn new() -> Self {
+        TruncatePipeline { length: None }
+    }
+}
+
+impl Pipeline for TruncatePipeline {
+    fn format(
+        &self,
+        pipe_object: &Box<dyn PipelineValue + Send + Sync>,
+    ) -> Box<dyn PipelineValue + Send + Sync + 'static> {
+        let value = pipe_object.to_string();
+        match self.length {
+            Some(length) => Box::new(value.chars().take(length).collect::<String>()),
+            None => Box::new(value),
+        }
+    }
+
+    fn options(&self, options: &str) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(TruncatePipeline {
+            length: options.trim().parse().ok(),
+        })
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+/// A pipeline replacing values that render empty with a fallback, e.g. `default:'n/a'`.
+#[derive(Debug, Clone, Default)]
+pub struct DefaultPipeline {
+    value: String,
+}
+
+impl DefaultPipeline {
+    /// Creates a new DefaultPipeline with an empty fallback.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gearbox::template::pipelines::string::DefaultPipeline;
+    ///
+    /// let pipeline = DefaultPipeline::new();
+    /// ```
+    pub fn new() -> Self {
+        DefaultPipeline {
+            value: String::new(),
+        }
+    }
+}
+
+impl Pipeline for DefaultPipeline {
+    fn format(
+        &self,
+        pipe_object: &Box<dyn PipelineValue + Send + Sync>,
+    ) -> Box<dyn PipelineValue + Send + Sync + 'static> {
+        let value = pipe_object.to_string();
+        if value.is_empty() {
+            Box::new(self.value.clone())
+        } else {
+            Box::new(value)
+        }
+    }
+
+    fn options(&self, options: &str) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(DefaultPipeline {
+            value: options.to_string(),
+        })
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pipeline + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
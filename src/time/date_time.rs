@@ -9,8 +9,8 @@ use crate::time::utils::{days_in_month, is_leap_year};
 
 #[cfg(feature = "template")]
 use crate::template::PipelineValue;
-use crate::time::constants_utils::{Mdf, YearFlags};
-use crate::time::{utils, SecondsFormat, TimeNow};
+use crate::time::constants_utils::YearFlags;
+use crate::time::{utils, SecondsFormat, TimeNow, Weekday};
 use alloc::{
     format,
     string::{String, ToString},
@@ -373,7 +373,7 @@ impl DateTime {
                 duration.remove_secs(days as u64 * SECS_PER_DAY as u64);
             }
             duration.remove_secs(
-                days_in_month(&year, &month) as u64 - day as u64 * SECS_PER_DAY as u64,
+                (days_in_month(&year, &month) as u64 - day as u64 + 1) * SECS_PER_DAY as u64,
             );
         } else {
             for i in EPOCH..year {
@@ -387,7 +387,7 @@ impl DateTime {
                 let days = days_in_month(&year, &i);
                 duration.add_secs(days as u64 * SECS_PER_DAY as u64);
             }
-            duration.add_secs(day as u64 * SECS_PER_DAY as u64);
+            duration.add_secs((day as u64).saturating_sub(1) * SECS_PER_DAY as u64);
         }
 
         Self {
@@ -396,6 +396,34 @@ impl DateTime {
             cache: Default::default(),
         }
     }
+    /// Constructs a `DateTime` from a specified date, validating the date components.
+    ///
+    /// Unlike [`DateTime::from_date`], out of range months and days (such as February 29th in a
+    /// non-leap year) are rejected instead of rolling over.
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - Year component of the date.
+    /// * `month` - Month component of the date (1-12).
+    /// * `day` - Day component of the date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gearbox::time::DateTime;
+    ///
+    /// assert!(DateTime::try_from_date(2024, 2, 29).is_ok());
+    /// assert!(DateTime::try_from_date(2023, 2, 29).is_err());
+    /// ```
+    pub fn try_from_date(year: i32, month: u8, day: u8) -> Result<Self, Error> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(&year, &month) {
+            return Err(Error::InvalidDate(format!(
+                "{:04}-{:02}-{:02}",
+                year, month, day
+            )));
+        }
+        Ok(Self::from_date(year, month, day))
+    }
     /// Constructs a `DateTime` with a detailed date and time specification including timezone.
     ///
     /// # Arguments
@@ -643,6 +671,67 @@ impl DateTime {
     }
 }
 
+/// DateTime - Duration arithmetic
+///
+/// Adding or subtracting a `core::time::Duration` saturates at the range a `DateTime` can
+/// represent (`u64::MAX` seconds before or after the UNIX epoch) instead of overflowing.
+impl DateTime {
+    /// Returns a new `DateTime` that is `duration` later than this instance, saturating at the
+    /// latest representable time.
+    ///
+    /// # Example
+    /// ```
+    /// use core::time::Duration;
+    /// use gearbox::time::DateTime;
+    ///
+    /// let expires = DateTime::from_secs(1_700_000_000).add(Duration::from_secs(3600));
+    /// assert_eq!(expires, DateTime::from_secs(1_700_003_600));
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, duration: core::time::Duration) -> Self {
+        let nanos = self.time.as_nanos() + duration.as_nanos() as i128;
+        self.with_time_nanos(nanos)
+    }
+
+    /// Returns a new `DateTime` that is `duration` earlier than this instance, saturating at the
+    /// earliest representable time.
+    ///
+    /// # Example
+    /// ```
+    /// use core::time::Duration;
+    /// use gearbox::time::DateTime;
+    ///
+    /// let issued = DateTime::from_secs(1_700_000_000).sub(Duration::from_secs(60));
+    /// assert_eq!(issued, DateTime::from_secs(1_699_999_940));
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn sub(self, duration: core::time::Duration) -> Self {
+        let nanos = self.time.as_nanos() - duration.as_nanos() as i128;
+        self.with_time_nanos(nanos)
+    }
+
+    fn with_time_nanos(self, nanos: i128) -> Self {
+        Self {
+            time: duration_from_nanos_saturating(nanos),
+            zone: self.zone,
+            cache: Default::default(),
+        }
+    }
+}
+
+/// Converts nanoseconds into a `Duration`, saturating at `u64::MAX` seconds in either direction.
+fn duration_from_nanos_saturating(nanos: i128) -> Duration {
+    let max = u64::MAX as i128 * NANOS_PER_SEC as i128 + (NANOS_PER_SEC as i128 - 1);
+    let abs = nanos.clamp(-max, max).abs();
+    let secs = (abs / NANOS_PER_SEC as i128) as u64;
+    let sub_nanos = (abs % NANOS_PER_SEC as i128) as u32;
+    if nanos.is_negative() {
+        Duration::Negative(secs, sub_nanos)
+    } else {
+        Duration::Positive(secs, sub_nanos)
+    }
+}
+
 /// Represents a date and time, providing utility functions for detailed manipulation and retrieval of individual time components.
 ///
 /// This struct assumes a supporting `Time` implementation that allows precise control and querying of time data.
@@ -725,17 +814,17 @@ impl DateTime {
     /// Returns the day of the year.
     ///
     /// # Returns
-    /// * `u32`: The day number within the current year.
+    /// * `u16`: The day number within the current year (1-366).
     ///
     /// # Example
     /// ```
     /// use gearbox::time::DateTime;
     ///
     /// let dt = DateTime::new();
-    /// assert_eq!(dt.day_of_year(), 1); // 150th day of the year
+    /// assert_eq!(dt.day_of_year(), 1); // 1st of January
     /// ```
-    pub fn day_of_year(&self) -> u32 {
-        self.day_of_year_with_overflow().0 as u32
+    pub fn day_of_year(&self) -> u16 {
+        self.day_of_year_with_overflow().0
     }
 
     /// Returns the day of the month.
@@ -797,6 +886,51 @@ impl DateTime {
         }
     }
 
+    /// Returns the number of days in the month of the date, considering leap years.
+    ///
+    /// # Returns
+    /// * `u8`: The number of days in the month.
+    ///
+    /// # Example
+    /// ```
+    /// use gearbox::time::DateTime;
+    ///
+    /// assert_eq!(DateTime::from_date(2024, 2, 10).days_in_month(), 29);
+    /// assert_eq!(DateTime::from_date(2023, 2, 10).days_in_month(), 28);
+    /// ```
+    pub fn days_in_month(&self) -> u8 {
+        days_in_month(&self.year(), &self.month())
+    }
+
+    /// Returns whether the year of the date is a leap year.
+    ///
+    /// # Example
+    /// ```
+    /// use gearbox::time::DateTime;
+    ///
+    /// assert!(DateTime::from_date(2024, 7, 1).is_leap_year());
+    /// assert!(!DateTime::from_date(2100, 7, 1).is_leap_year());
+    /// ```
+    pub fn is_leap_year(&self) -> bool {
+        is_leap_year(&self.year())
+    }
+
+    /// Returns the day of the week.
+    ///
+    /// # Returns
+    /// * `Weekday`: The day of the week.
+    ///
+    /// # Example
+    /// ```
+    /// use gearbox::time::{DateTime, Weekday};
+    ///
+    /// let dt = DateTime::new();
+    /// assert_eq!(dt.weekday(), Weekday::Thursday);
+    /// ```
+    pub fn weekday(&self) -> Weekday {
+        Weekday::from_iso(self.day_of_week()).expect("day of week is always within 1-7")
+    }
+
     /// Returns the ISO day of the week.
     ///
     /// # Returns
@@ -957,13 +1091,17 @@ impl DateTime {
         self.time.as_nanos()
     }
 
-    /// Calculates the duration since a specified duration ago until this DateTime instance.
+    /// Calculates the time elapsed from `earlier` until this DateTime instance.
+    ///
+    /// `earlier` can either be another `DateTime` or a `Duration` since the UNIX epoch. The result
+    /// is negative if `earlier` is actually later than this instance. Both instances are compared
+    /// by their time since the epoch, the timezones are not taken into account.
     ///
     /// # Arguments
-    /// * `duration`: A `std::time::Duration` to measure back from now.
+    /// * `earlier`: The `DateTime` or `Duration` to measure from.
     ///
     /// # Returns
-    /// * `Duration`: The amount of time from the duration specified until now.
+    /// * `Duration`: The amount of time from `earlier` until this instance.
     ///
     /// # Example
     /// ```
@@ -972,9 +1110,12 @@ impl DateTime {
     /// let dt = DateTime::new();
     /// let duration_since = dt.duration_since(Duration::from_secs(300));
     /// assert_eq!(duration_since, Duration::from_secs(-300));
+    ///
+    /// let later = DateTime::from_secs(3600);
+    /// assert_eq!(later.duration_since(DateTime::new()), Duration::from_secs(3600));
     /// ```
-    pub fn duration_since(self, duration: Duration) -> Duration {
-        self.time - duration
+    pub fn duration_since<D: Into<Duration>>(self, earlier: D) -> Duration {
+        duration_from_nanos_saturating(self.time.as_nanos() - earlier.into().as_nanos())
     }
 }
 
@@ -1197,11 +1338,18 @@ impl DateTime {
         }
 
         let (year, overflow_dur) = self.year_with_overflow();
-        let overflow_sec = overflow_dur.as_secs();
-        // Day starts from 1 and not 0 so we add 1 to the days elapsed in the year.
-        let days = (overflow_sec / (SECS_PER_DAY as i64)) + 1;
-        let overflow = overflow_sec % SECS_PER_DAY as i64;
-        let month = Mdf::from_ol(days as i32, YearFlags::from_year(year)).month();
+        let mut overflow = overflow_dur.as_secs();
+        // Remove the full months from the time elapsed in the year, the overflow is the time
+        // elapsed since the start of the month.
+        let mut month = 1;
+        while month < 12 {
+            let month_secs = days_in_month(&year, &month) as i64 * SECS_PER_DAY as i64;
+            if overflow < month_secs {
+                break;
+            }
+            overflow -= month_secs;
+            month += 1;
+        }
 
         self.cache_update(Month, month as i32, (overflow, overflow_dur.as_sub_nanos()))
     }
@@ -1209,7 +1357,7 @@ impl DateTime {
     pub(super) fn week_of_year_with_overflow(&self) -> (i32, u8) {
         let year = self.year();
         let year_flag = YearFlags::from_year(year);
-        let week_num = (self.day_of_year() + year_flag.isoweek_delta()) / 7;
+        let week_num = (self.day_of_year() as u32 + year_flag.isoweek_delta()) / 7;
         if week_num < 1 {
             // previous year
             let prevlastweek = YearFlags::from_year(self.year() - 1).nisoweeks();
@@ -1224,21 +1372,21 @@ impl DateTime {
         }
     }
 
-    pub(super) fn day_of_year_with_overflow(&self) -> (u8, Duration) {
-        let (_current_month, overflow_dur) = self.month_with_overflow();
+    pub(super) fn day_of_year_with_overflow(&self) -> (u16, Duration) {
+        let (_year, overflow_dur) = self.year_with_overflow();
 
         let overflow_sec = overflow_dur.as_secs();
-        // Calculate the total days elapsed in the current month based on the overflow nanoseconds
-        let days_elapsed_in_month = overflow_sec / SECS_PER_DAY as i64;
+        // Calculate the total days elapsed in the current year
+        let days_elapsed_in_year = overflow_sec / SECS_PER_DAY as i64;
 
-        // Calculate the excess nanoseconds that don't complete a full day
+        // Calculate the excess seconds that don't complete a full day
         let excess = overflow_sec % SECS_PER_DAY as i64;
 
-        // The day of the month is days_elapsed_in_month + 1 (since days are 1-indexed)
-        let day_of_month = (days_elapsed_in_month + 1) as u8;
+        // The day of the year is days_elapsed_in_year + 1 (since days are 1-indexed)
+        let day_of_year = (days_elapsed_in_year + 1) as u16;
 
         (
-            day_of_month,
+            day_of_year,
             Duration::from_secs_nanos(&excess, &overflow_dur.as_sub_nanos()),
         )
     }
@@ -1365,10 +1513,6 @@ impl DateTime {
             _ => "th",
         }
     }
-
-    pub(super) fn is_leap_year(&self) -> bool {
-        utils::is_leap_year(&self.year())
-    }
 }
 
 /// Implementation for to_* conversions
@@ -1664,6 +1808,85 @@ impl DateTime {
         result
     }
 
+    /// Formats the date and time using strftime-style format specifiers.
+    ///
+    /// Unlike [`DateTime::format_to_str`], the specifiers follow the C `strftime` conventions.
+    /// Unknown specifiers are copied to the output as they are.
+    ///
+    /// # Format Specifiers
+    /// | Specifier | Description                                  | Example    |
+    /// |-----------|----------------------------------------------|------------|
+    /// | `%Y`      | Full numeric year                            | 1999, 2023 |
+    /// | `%m`      | Month, zero-padded (01-12)                   | 01, 12     |
+    /// | `%d`      | Day of the month, zero-padded (01-31)        | 01, 31     |
+    /// | `%H`      | Hour in 24-hour format, zero-padded          | 00, 23     |
+    /// | `%M`      | Minute, zero-padded                          | 00, 59     |
+    /// | `%S`      | Second, zero-padded                          | 00, 59     |
+    /// | `%j`      | Day of the year, zero-padded (001-366)       | 001, 365   |
+    /// | `%a`      | Abbreviated day of the week                  | Mon, Thu   |
+    /// | `%b`      | Abbreviated month name                       | Jan, Dec   |
+    /// | `%p`      | Ante meridiem or Post meridiem               | AM, PM     |
+    /// | `%z`      | Timezone offset in hours and minutes         | +0000, -0430 |
+    /// | `%%`      | A literal `%`                                | %          |
+    ///
+    /// # Arguments
+    /// * `fmt` - A string slice that specifies the desired format.
+    ///
+    /// # Returns
+    /// * `String`: A string representing the formatted date and time.
+    ///
+    /// # Examples
+    /// ```
+    /// use gearbox::time::*;
+    ///
+    /// let dt = DateTime::new();
+    /// assert_eq!(dt.format("%Y-%m-%d %H:%M:%S"), "1970-01-01 00:00:00");
+    /// assert_eq!(dt.format("%a, %d %b %Y %z"), "Thu, 01 Jan 1970 +0000");
+    /// assert_eq!(dt.format("%Q"), "%Q");
+    /// ```
+    pub fn format(&self, fmt: &str) -> String {
+        let mut result = String::with_capacity(fmt.len());
+        let mut chars = fmt.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                result.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('Y') => result.push_str(&self.year().to_string()),
+                Some('m') => result.push_str(&format!("{:02}", self.month())),
+                Some('d') => result.push_str(&format!("{:02}", self.day_of_month())),
+                Some('H') => result.push_str(&format!("{:02}", self.hour())),
+                Some('M') => result.push_str(&format!("{:02}", self.minute())),
+                Some('S') => result.push_str(&format!("{:02}", self.second())),
+                Some('j') => result.push_str(&format!("{:03}", self.day_of_year())),
+                Some('a') => result.push_str(self.day_of_week_short()),
+                Some('b') => result.push_str(self.month_short()),
+                Some('p') => result.push_str(if self.hour() < 12 { "AM" } else { "PM" }),
+                Some('z') => {
+                    let offset = self.timezone_offset_seconds();
+                    let sign = if offset < 0 { '-' } else { '+' };
+                    let offset = offset.unsigned_abs();
+                    result.push_str(&format!(
+                        "{}{:02}{:02}",
+                        sign,
+                        offset / SECS_PER_HOUR as u64,
+                        (offset % SECS_PER_HOUR as u64) / SECS_PER_MINUTE as u64
+                    ));
+                }
+                Some('%') => result.push('%'),
+                Some(other) => {
+                    result.push('%');
+                    result.push(other);
+                }
+                None => result.push('%'),
+            }
+        }
+
+        result
+    }
+
     pub fn to_swatch_internet_time(&self) -> String {
         let time = self.time.as_secs() % SECS_PER_DAY as i64;
         let beats = (time * 1000 / SECS_PER_DAY as i64) as u16;
@@ -1743,6 +1966,36 @@ impl Sub for &mut DateTime {
     }
 }
 
+impl Add<core::time::Duration> for DateTime {
+    type Output = Self;
+
+    fn add(self, rhs: core::time::Duration) -> Self::Output {
+        DateTime::add(self, rhs)
+    }
+}
+
+impl Sub<core::time::Duration> for DateTime {
+    type Output = Self;
+
+    fn sub(self, rhs: core::time::Duration) -> Self::Output {
+        DateTime::sub(self, rhs)
+    }
+}
+
+impl From<DateTime> for Duration {
+    /// Returns the time since the UNIX epoch.
+    fn from(date_time: DateTime) -> Self {
+        date_time.time
+    }
+}
+
+impl From<&DateTime> for Duration {
+    /// Returns the time since the UNIX epoch.
+    fn from(date_time: &DateTime) -> Self {
+        date_time.time.clone()
+    }
+}
+
 impl Display for DateTime {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.to_rfc3339())
@@ -1841,7 +2094,7 @@ impl<'de> Deserialize<'de> for DateTime {
 #[cfg(test)]
 mod test {
     use crate::time::constants::COMMON_TIMESTAMP_FORMATS;
-    use crate::time::{utils, DateTime};
+    use crate::time::{utils, DateTime, Duration, Error, Weekday};
     use alloc::{
         string::{String, ToString},
         vec,
@@ -1893,6 +2146,184 @@ mod test {
         ]
     }
 
+    #[test]
+    fn test_from_date() {
+        // Used to be one day late (1_609_545_600, 2021-01-02).
+        assert_eq!(
+            DateTime::from_date(2021, 1, 1),
+            DateTime::from_secs(1_609_459_200)
+        );
+        // Used to be 1_709_251_200, 2024-03-01.
+        assert_eq!(
+            DateTime::from_date(2024, 2, 29),
+            DateTime::from_secs(1_709_164_800)
+        );
+        // Used to overflow while subtracting the days of the month.
+        assert_eq!(
+            DateTime::from_date(1969, 12, 31),
+            DateTime::from_secs(-86_400)
+        );
+    }
+
+    #[test]
+    fn test_month_and_day_of_year() {
+        // 2024-01-31, the day of the month and of the year both used to be 1.
+        let date_time = DateTime::from_secs(1_706_659_200);
+        assert_eq!(date_time.month(), 1);
+        assert_eq!(date_time.day_of_month(), 31);
+        assert_eq!(date_time.day_of_year(), 31);
+
+        // 2024-03-01, the day of the year used to be 1.
+        let date_time = DateTime::from_secs(1_709_251_200);
+        assert_eq!(date_time.month(), 3);
+        assert_eq!(date_time.day_of_month(), 1);
+        assert_eq!(date_time.day_of_year(), 61);
+
+        // 2023-12-31, beyond the 255 days a `u8` day of the year could hold, used to be 1.
+        let date_time = DateTime::from_secs(1_703_980_800);
+        assert_eq!(date_time.month(), 12);
+        assert_eq!(date_time.day_of_year(), 365);
+    }
+
+    #[test]
+    fn test_add_duration_day_boundary() {
+        let date_time = DateTime::from((2023, 6, 14, 23, 30, 0, 0, (0, 0)));
+        let later = date_time + core::time::Duration::from_secs(45 * 60);
+        assert_eq!(later.to_rfc3339(), "2023-06-15T00:15:00Z");
+        assert_eq!(later.day_of_month(), 15);
+
+        let earlier = later - core::time::Duration::from_secs(45 * 60);
+        assert_eq!(earlier.to_rfc3339(), "2023-06-14T23:30:00Z");
+    }
+
+    #[test]
+    fn test_add_duration_month_boundary() {
+        let date_time = DateTime::from((2024, 2, 28, 12, 0, 0, 0, (0, 0)));
+        let leap_day = date_time
+            .clone()
+            .add(core::time::Duration::from_secs(86_400));
+        assert_eq!(leap_day.to_rfc3339(), "2024-02-29T12:00:00Z");
+
+        let march = date_time
+            .clone()
+            .add(core::time::Duration::from_secs(2 * 86_400));
+        assert_eq!(march.to_rfc3339(), "2024-03-01T12:00:00Z");
+        assert_eq!(
+            march
+                .clone()
+                .sub(core::time::Duration::from_secs(2 * 86_400)),
+            date_time
+        );
+
+        let new_year = DateTime::from((2023, 12, 31, 23, 59, 59, 0, (0, 0)))
+            .add(core::time::Duration::from_secs(1));
+        assert_eq!(new_year.to_rfc3339(), "2024-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn test_duration_since() {
+        let issued = DateTime::from((2024, 1, 31, 22, 0, 0, 0, (0, 0)));
+        let expires = issued.clone().add(core::time::Duration::from_secs(7200));
+        assert_eq!(expires.to_rfc3339(), "2024-02-01T00:00:00Z");
+        assert_eq!(
+            expires.clone().duration_since(issued.clone()),
+            Duration::from_secs(7200)
+        );
+        assert_eq!(issued.duration_since(&expires), Duration::from_secs(-7200));
+    }
+
+    #[test]
+    fn test_format() {
+        let date_time = DateTime::from((2024, 3, 5, 14, 7, 9, 0, (0, 0)));
+        assert_eq!(date_time.format("%Y"), "2024");
+        assert_eq!(date_time.format("%m"), "03");
+        assert_eq!(date_time.format("%d"), "05");
+        assert_eq!(date_time.format("%H"), "14");
+        assert_eq!(date_time.format("%M"), "07");
+        assert_eq!(date_time.format("%S"), "09");
+        assert_eq!(date_time.format("%j"), "065");
+        assert_eq!(date_time.format("%a"), "Tue");
+        assert_eq!(date_time.format("%b"), "Mar");
+        assert_eq!(date_time.format("%p"), "PM");
+        assert_eq!(date_time.format("%z"), "+0000");
+        assert_eq!(
+            date_time.format("%Y-%m-%dT%H:%M:%S%z"),
+            "2024-03-05T14:07:09+0000"
+        );
+
+        let morning = DateTime::from((1999, 12, 31, 9, 0, 0, 0, (0, 0)));
+        assert_eq!(morning.format("%a %b %d %j %p"), "Fri Dec 31 365 AM");
+    }
+
+    #[test]
+    fn test_format_zone_offset() {
+        let mut date_time = DateTime::from((2024, 3, 5, 14, 7, 9, 0, (0, 0)));
+        *date_time.timezone_mut() = Duration::from_secs(-(4 * 3600 + 30 * 60));
+        assert_eq!(date_time.format("%z"), "-0430");
+        *date_time.timezone_mut() = Duration::from_secs(2 * 3600);
+        assert_eq!(date_time.format("%z"), "+0200");
+    }
+
+    #[test]
+    fn test_format_unknown_specifier() {
+        let date_time = DateTime::new();
+        assert_eq!(date_time.format("%Q"), "%Q");
+        assert_eq!(date_time.format("100%% %Y%"), "100% 1970%");
+    }
+
+    #[test]
+    fn test_leap_day_validity() {
+        let leap_day = DateTime::try_from_date(2024, 2, 29).unwrap();
+        assert_eq!(leap_day.to_rfc3339(), "2024-02-29T00:00:00Z");
+        assert!(leap_day.is_leap_year());
+        assert_eq!(leap_day.days_in_month(), 29);
+        assert_eq!(leap_day.day_of_year(), 60);
+
+        assert!(matches!(
+            DateTime::try_from_date(2023, 2, 29),
+            Err(Error::InvalidDate(_))
+        ));
+        assert!(DateTime::try_from_date(2023, 13, 1).is_err());
+        assert!(DateTime::try_from_date(2023, 4, 0).is_err());
+
+        let common = DateTime::from_date(2023, 2, 1);
+        assert!(!common.is_leap_year());
+        assert_eq!(common.days_in_month(), 28);
+        assert!(!DateTime::from_date(1900, 1, 1).is_leap_year());
+        assert!(DateTime::from_date(2000, 1, 1).is_leap_year());
+    }
+
+    #[test]
+    fn test_calendar_helpers() {
+        assert_eq!(DateTime::new().weekday(), Weekday::Thursday);
+        assert_eq!(DateTime::from_date(2024, 7, 1).weekday(), Weekday::Monday);
+        assert_eq!(DateTime::from_date(2000, 1, 1).weekday(), Weekday::Saturday);
+        assert_eq!(DateTime::from_date(1969, 7, 20).weekday(), Weekday::Sunday);
+
+        assert_eq!(DateTime::from_date(2024, 7, 1).day_of_year(), 183);
+        assert_eq!(DateTime::from_date(2023, 12, 31).day_of_year(), 365);
+        assert_eq!(DateTime::from_date(2024, 12, 31).day_of_year(), 366);
+        assert_eq!(DateTime::from_date(2024, 4, 15).days_in_month(), 30);
+    }
+
+    #[test]
+    fn test_add_duration_saturates() {
+        let max = DateTime::from_secs(0).add(core::time::Duration::MAX);
+        let max = max.add(core::time::Duration::MAX);
+        assert_eq!(
+            max.as_nanos_since_epoch(),
+            u64::MAX as i128 * 1_000_000_000 + 999_999_999
+        );
+
+        let min = DateTime::from_secs(0)
+            .sub(core::time::Duration::MAX)
+            .sub(core::time::Duration::MAX);
+        assert_eq!(
+            min.as_nanos_since_epoch(),
+            -(u64::MAX as i128 * 1_000_000_000 + 999_999_999)
+        );
+    }
+
     #[test]
     fn test_from_str_auto_date_0() {
         if let Some((input, expected)) = get_test_data().get(0) {
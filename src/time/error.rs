@@ -17,6 +17,7 @@ pub enum Error {
     InvalidPattern,
     CalculationOverflow(String),
     InvalidZoneState,
+    InvalidDate(String),
 }
 
 impl Display for Error {
@@ -32,6 +33,7 @@ impl Display for Error {
             Error::InvalidPattern => write!(f, "Invalid Pattern"),
             Error::CalculationOverflow(e) => write!(f, "Calculation overflow: {}", e),
             Error::InvalidZoneState => write!(f, "Invalid Zone State"),
+            Error::InvalidDate(e) => write!(f, "Invalid date: {}", e),
         }
     }
 }
@@ -6,6 +6,7 @@ mod duration;
 mod error;
 mod seconds_format;
 mod utils;
+mod weekday;
 
 pub use constants::{
     EPOCH, NANOS_PER_DAY, NANOS_PER_HOUR, NANOS_PER_LEAP_MONTH, NANOS_PER_LEAP_YEAR,
@@ -20,6 +21,7 @@ pub use date_time::DateTime;
 pub use duration::Duration;
 pub use error::Error;
 pub use seconds_format::SecondsFormat;
+pub use weekday::Weekday;
 
 use crate::rails::ext::syn::RailsMapErrTracer;
 #[cfg(feature = "std")]
@@ -0,0 +1,78 @@
+use core::fmt::{Display, Formatter};
+
+/// A day of the week, following ISO-8601 where the week starts on Monday.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    /// Creates a `Weekday` from its ISO-8601 number (1 for Monday, 7 for Sunday).
+    ///
+    /// # Examples
+    /// ```
+    /// use gearbox::time::Weekday;
+    ///
+    /// assert_eq!(Weekday::from_iso(4), Some(Weekday::Thursday));
+    /// assert_eq!(Weekday::from_iso(8), None);
+    /// ```
+    pub fn from_iso(day: u8) -> Option<Self> {
+        match day {
+            1 => Some(Weekday::Monday),
+            2 => Some(Weekday::Tuesday),
+            3 => Some(Weekday::Wednesday),
+            4 => Some(Weekday::Thursday),
+            5 => Some(Weekday::Friday),
+            6 => Some(Weekday::Saturday),
+            7 => Some(Weekday::Sunday),
+            _ => None,
+        }
+    }
+
+    /// Returns the ISO-8601 number of the day (1 for Monday, 7 for Sunday).
+    ///
+    /// # Examples
+    /// ```
+    /// use gearbox::time::Weekday;
+    ///
+    /// assert_eq!(Weekday::Sunday.number_from_monday(), 7);
+    /// ```
+    pub fn number_from_monday(&self) -> u8 {
+        *self as u8 + 1
+    }
+
+    /// Returns the full name of the day, e.g. `Monday`.
+    pub fn long_name(&self) -> &'static str {
+        match self {
+            Weekday::Monday => "Monday",
+            Weekday::Tuesday => "Tuesday",
+            Weekday::Wednesday => "Wednesday",
+            Weekday::Thursday => "Thursday",
+            Weekday::Friday => "Friday",
+            Weekday::Saturday => "Saturday",
+            Weekday::Sunday => "Sunday",
+        }
+    }
+
+    /// Returns the three-letter abbreviation of the day, e.g. `Mon`.
+    pub fn short_name(&self) -> &'static str {
+        &self.long_name()[..3]
+    }
+
+    /// Returns `true` for Saturday and Sunday.
+    pub fn is_weekend(&self) -> bool {
+        matches!(self, Weekday::Saturday | Weekday::Sunday)
+    }
+}
+
+impl Display for Weekday {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.long_name())
+    }
+}